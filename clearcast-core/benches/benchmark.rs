@@ -4,6 +4,8 @@
 //! to help identify optimal buffer sizes for different use cases.
 
 use clearcast_core::AudioEngine;
+use clearcast_core::filters::{reduce_noise_wiener, BandParams, MultibandCompressor, WienerDenoiser};
+use clearcast_core::utils::simd;
 use criterion::{
     criterion_group, criterion_main, BatchSize, Criterion, Throughput,
 };
@@ -105,6 +107,86 @@ fn benchmark_noise_reduction(c: &mut Criterion) {
     }
 }
 
+/// Benchmark the multiband compressor with an increasing number of bands
+///
+/// Run with and without `--features rayon` to compare serial and parallel band processing.
+fn benchmark_multiband(c: &mut Criterion) {
+    let sample_rate = 44100.0;
+    let size = 1 << 16;
+    let input = generate_audio_samples(size);
+
+    let mut group = c.benchmark_group(format!("multiband/{}_samples", size));
+    group.throughput(Throughput::Elements(size as u64));
+
+    for &num_bands in &[2, 4, 8] {
+        // Bands spaced logarithmically between 20 Hz and Nyquist
+        let ratio = (sample_rate * 0.5 / 20.0f32).powf(1.0 / num_bands as f32);
+        let bands: Vec<BandParams> = (0..num_bands)
+            .map(|i| BandParams {
+                low_freq: if i == 0 { 0.0 } else { 20.0 * ratio.powi(i) },
+                high_freq: 20.0 * ratio.powi(i + 1),
+                ..BandParams::default()
+            })
+            .collect();
+
+        group.bench_function(format!("{}_bands", num_bands).as_str(), |b| {
+            let mut compressor = MultibandCompressor::new(bands.clone(), sample_rate).unwrap();
+            b.iter(|| {
+                let output = compressor.process(&input);
+                criterion::black_box(output);
+            })
+        });
+    }
+
+    group.finish();
+}
+
+/// Benchmark the unrolled gain kernels against their scalar reference versions
+fn benchmark_gain_kernels(c: &mut Criterion) {
+    let size = 1 << 16;
+    let input = generate_audio_samples(size);
+
+    let mut group = c.benchmark_group(format!("gain/{}_samples", size));
+    group.throughput(Throughput::Elements(size as u64));
+
+    group.bench_function("scale_unrolled", |b| {
+        let mut buffer = input.clone();
+        b.iter(|| simd::scale_in_place(criterion::black_box(&mut buffer), 0.999))
+    });
+    group.bench_function("scale_scalar", |b| {
+        let mut buffer = input.clone();
+        b.iter(|| simd::scale_in_place_scalar(criterion::black_box(&mut buffer), 0.999))
+    });
+    group.bench_function("peak_unrolled", |b| {
+        b.iter(|| simd::peak_abs(criterion::black_box(&input)))
+    });
+    group.bench_function("peak_scalar", |b| {
+        b.iter(|| simd::peak_abs_scalar(criterion::black_box(&input)))
+    });
+
+    group.finish();
+}
+
+/// Benchmark per-block Wiener filtering with and without cached FFT plans
+fn benchmark_wiener(c: &mut Criterion) {
+    let fft_size = 1024;
+    let block = generate_audio_samples(4096);
+    let noise_profile = vec![0.01; fft_size / 2 + 1];
+
+    let mut group = c.benchmark_group("wiener/4096_samples");
+    group.throughput(Throughput::Elements(block.len() as u64));
+
+    group.bench_function("free_function", |b| {
+        b.iter(|| reduce_noise_wiener(criterion::black_box(&block), &noise_profile, fft_size, fft_size / 2, 0.9))
+    });
+    group.bench_function("cached_denoiser", |b| {
+        let mut denoiser = WienerDenoiser::new(fft_size);
+        b.iter(|| denoiser.process(criterion::black_box(&block), &noise_profile, fft_size / 2, 0.9))
+    });
+
+    group.finish();
+}
+
 // Configuration for benchmark groups
 criterion_group! {
     name = benches;
@@ -116,7 +198,10 @@ criterion_group! {
     targets = 
         benchmark_processing,
         benchmark_normalize,
-        benchmark_noise_reduction
+        benchmark_noise_reduction,
+        benchmark_multiband,
+        benchmark_gain_kernels,
+        benchmark_wiener
 }
 
 criterion_main!(benches);
@@ -0,0 +1,123 @@
+//! Implementación de un efecto de autopaneo (AutoPan)
+//!
+//! Un LFO mueve la posición de paneo de un lado a otro del campo estéreo. Las
+//! ganancias de cada canal siguen la ley de potencia constante (coseno y seno
+//! del ángulo de paneo), de modo que el volumen percibido no cae al pasar por
+//! el centro como ocurriría con un paneo lineal.
+
+use std::f32::consts::FRAC_PI_4;
+
+use super::{AudioEffect, Lfo};
+
+/// Autopaneo estéreo con oscilador senoidal
+///
+/// La posición empieza en el centro y se mueve primero hacia la derecha. En el
+/// centro cada canal queda atenuado 3 dB, y la suma de las potencias de los
+/// dos canales se mantiene constante en todo el recorrido.
+///
+/// Como [`AudioEffect`] solo actúa sobre pares de canales: `process_sample`
+/// deja pasar la señal mono sin cambios.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::AutoPan;
+///
+/// let mut autopan = AutoPan::new(1.0, 1.0, 4.0);
+/// let mut left = vec![1.0; 2];
+/// let mut right = vec![1.0; 2];
+/// autopan.process_stereo(&mut left, &mut right);
+/// // Un cuarto de ciclo después el sonido está a la derecha del todo
+/// assert!(left[1].abs() < 1e-6);
+/// assert!((right[1] - 1.0).abs() < 1e-6);
+/// ```
+#[derive(Debug, Clone)]
+pub struct AutoPan {
+    depth: f32,
+    lfo: Lfo,
+}
+
+impl AutoPan {
+    /// Crea un nuevo autopaneo
+    ///
+    /// # Argumentos
+    /// * `rate_hz` - Frecuencia del barrido en Hz (ciclos izquierda-derecha por segundo)
+    /// * `depth` - Amplitud del barrido (0.0 = fijo en el centro, 1.0 = de
+    ///   un extremo a otro)
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(rate_hz: f32, depth: f32, sample_rate: f32) -> Self {
+        Self {
+            depth: depth.clamp(0.0, 1.0),
+            lfo: Lfo::new(rate_hz, sample_rate),
+        }
+    }
+
+    /// Procesa un par de canales en su lugar
+    ///
+    /// Si los canales tienen distinta longitud, las muestras sobrantes del más
+    /// largo no se modifican.
+    pub fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            // Posición en [-1, 1] llevada a un ángulo en [0, π/2]
+            let angle = (1.0 + self.depth * self.lfo.next()) * FRAC_PI_4;
+            *l *= angle.cos();
+            *r *= angle.sin();
+        }
+    }
+
+    /// Devuelve el barrido al centro
+    pub fn reset(&mut self) {
+        self.lfo.reset();
+    }
+}
+
+impl AudioEffect for AutoPan {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        sample
+    }
+
+    fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        AutoPan::process_stereo(self, left, right);
+    }
+
+    fn reset(&mut self) {
+        AutoPan::reset(self);
+    }
+
+    fn name(&self) -> &'static str {
+        "AutoPan"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_autopan_extremes_and_constant_power() {
+        // 1 Hz a 1 kHz: extremo derecho en la muestra 250 e izquierdo en la 750
+        let mut autopan = AutoPan::new(1.0, 1.0, 1000.0);
+        let mut left = vec![0.8; 1000];
+        let mut right = vec![0.8; 1000];
+        autopan.process_stereo(&mut left, &mut right);
+
+        assert!(left[250].abs() < 1e-3 && (right[250] - 0.8).abs() < 1e-3);
+        assert!(right[750].abs() < 1e-3 && (left[750] - 0.8).abs() < 1e-3);
+
+        // Potencia constante: L² + R² no depende de la posición
+        for (l, r) in left.iter().zip(&right) {
+            assert!((l * l + r * r - 0.64).abs() < 1e-4);
+        }
+
+        // Con profundidad parcial ningún canal llega a silenciarse
+        let mut partial = AutoPan::new(1.0, 0.5, 1000.0);
+        let (mut left, mut right) = (vec![0.8; 1000], vec![0.8; 1000]);
+        partial.process_stereo(&mut left, &mut right);
+        assert!(left.iter().chain(&right).all(|x| x.abs() > 0.2));
+
+        // Tras reset vuelve a empezar desde el centro
+        autopan.reset();
+        let (mut l, mut r) = ([1.0], [1.0]);
+        autopan.process_stereo(&mut l, &mut r);
+        assert!((l[0] - r[0]).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,122 @@
+//! Implementación de un efecto bitcrusher (reducción de resolución)
+//!
+//! El bitcrusher degrada la señal de dos formas: cuantiza cada muestra a una
+//! profundidad de bits menor, lo que añade ruido de cuantización, y retiene
+//! cada valor durante varias muestras (sample-and-hold), lo que reduce la
+//! frecuencia de muestreo efectiva y produce aliasing. Es un efecto creativo
+//! de sonido "lo-fi".
+
+use super::AudioEffect;
+
+/// Profundidad máxima admitida; por encima la cuantización ya no es audible
+const MAX_BIT_DEPTH: u32 = 24;
+
+/// Efecto de reducción de profundidad de bits y de frecuencia de muestreo
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, BitCrusher};
+///
+/// let mut crusher = BitCrusher::new(4, 2);
+/// let mut buffer = vec![0.1, 0.2, 0.3, 0.4];
+/// crusher.process_buffer(&mut buffer);
+/// assert_eq!(buffer[0], buffer[1]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BitCrusher {
+    /// Distancia entre niveles de cuantización
+    step: f32,
+    downsample_factor: u32,
+    held_sample: f32,
+    counter: u32,
+}
+
+impl BitCrusher {
+    /// Crea un nuevo bitcrusher
+    ///
+    /// # Argumentos
+    /// * `bit_depth` - Bits de resolución de la salida (1 a 24)
+    /// * `downsample_factor` - Número de muestras durante las que se retiene
+    ///   cada valor (1 = sin reducción de frecuencia de muestreo)
+    pub fn new(bit_depth: u32, downsample_factor: u32) -> Self {
+        let bit_depth = bit_depth.clamp(1, MAX_BIT_DEPTH);
+
+        Self {
+            // Un bit de signo y el resto para la magnitud
+            step: 1.0 / (1u32 << (bit_depth - 1)) as f32,
+            downsample_factor: downsample_factor.max(1),
+            held_sample: 0.0,
+            counter: 0,
+        }
+    }
+
+    fn quantize(&self, sample: f32) -> f32 {
+        ((sample / self.step).round() * self.step).clamp(-1.0, 1.0)
+    }
+}
+
+impl AudioEffect for BitCrusher {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        if self.counter == 0 {
+            self.held_sample = self.quantize(sample);
+        }
+        self.counter = (self.counter + 1) % self.downsample_factor;
+
+        self.held_sample
+    }
+
+    fn reset(&mut self) {
+        self.held_sample = 0.0;
+        self.counter = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "BitCrusher"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ramp(len: usize) -> Vec<f32> {
+        (0..len).map(|i| -1.0 + 2.0 * i as f32 / (len - 1) as f32).collect()
+    }
+
+    #[test]
+    fn test_bitcrusher_one_bit_levels() {
+        let mut crusher = BitCrusher::new(1, 1);
+        let mut buffer = ramp(1000);
+        crusher.process_buffer(&mut buffer);
+
+        let mut levels: Vec<f32> = Vec::new();
+        for &x in &buffer {
+            if !levels.contains(&x) {
+                levels.push(x);
+            }
+        }
+        assert!(levels.len() <= 3, "Too many output levels: {:?}", levels);
+        assert!(buffer.iter().all(|x| x.abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_bitcrusher_sample_and_hold() {
+        let mut crusher = BitCrusher::new(MAX_BIT_DEPTH, 4);
+        let input = ramp(64);
+        let mut output = input.clone();
+        crusher.process_buffer(&mut output);
+
+        for (block, (out, inp)) in output.chunks(4).zip(input.chunks(4)).enumerate() {
+            assert!(out.iter().all(|&x| x == out[0]), "Block {} not held: {:?}", block, out);
+            assert!((out[0] - inp[0]).abs() < 1e-5);
+        }
+
+        // Tras reiniciar se captura de nuevo la primera muestra
+        crusher.reset();
+        assert_eq!(crusher.process_sample(0.5), 0.5);
+    }
+}
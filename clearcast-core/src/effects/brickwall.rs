@@ -0,0 +1,211 @@
+//! Implementación de un limitador brick-wall con anticipación (lookahead)
+//!
+//! Un limitador brick-wall garantiza que ninguna muestra supere el techo. Si la
+//! ganancia se calcula muestra a muestra, cambia de golpe en cada pico y
+//! produce distorsión audible. Este limitador retrasa la señal el tiempo de
+//! anticipación y usa ese margen para bajar la ganancia con una rampa antes de
+//! que llegue el pico (ataque), y la recupera con una curva exponencial
+//! (release), de modo que la reducción de ganancia es suave y el techo se sigue
+//! respetando.
+
+use std::collections::VecDeque;
+use super::AudioEffect;
+
+/// Limitador brick-wall con anticipación y reducción de ganancia suavizada
+///
+/// La salida está retrasada [`BrickWallLimiter::latency_samples`] muestras
+/// respecto a la entrada.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, BrickWallLimiter};
+///
+/// let mut limiter = BrickWallLimiter::new(0.9, 5.0, 50.0, 44100.0);
+/// let mut buffer = vec![0.5, 1.5, -2.0, 0.3];
+/// limiter.process_buffer(&mut buffer);
+/// assert!(buffer.iter().all(|x| x.abs() <= 0.9));
+/// ```
+#[derive(Debug, Clone)]
+pub struct BrickWallLimiter {
+    ceiling: f32,
+    lookahead: usize,
+    release_coeff: f32,
+    /// Muestras retrasadas a la espera de salir
+    delay_line: VecDeque<f32>,
+    /// Mínimos candidatos de la ganancia requerida (índice, ganancia), crecientes
+    min_window: VecDeque<(u64, f32)>,
+    /// Ganancia mantenida con release, en la ventana de la rampa de ataque
+    ramp: VecDeque<f32>,
+    ramp_sum: f64,
+    held_gain: f32,
+    gain: f32,
+    index: u64,
+}
+
+impl BrickWallLimiter {
+    /// Crea un nuevo limitador brick-wall
+    ///
+    /// # Argumentos
+    /// * `ceiling` - Amplitud máxima de salida (lineal, p. ej. 0.9)
+    /// * `lookahead_ms` - Tiempo de anticipación en milisegundos; también es la
+    ///   duración de la rampa de ataque y la latencia del efecto
+    /// * `release_ms` - Constante de tiempo de la recuperación de la ganancia
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(ceiling: f32, lookahead_ms: f32, release_ms: f32, sample_rate: f32) -> Self {
+        let lookahead = (lookahead_ms.max(0.0) * 0.001 * sample_rate).round() as usize;
+        let release_samples = release_ms.max(0.0) * 0.001 * sample_rate;
+        let release_coeff = if release_samples > 0.0 {
+            (-1.0 / release_samples).exp()
+        } else {
+            0.0
+        };
+
+        let mut limiter = Self {
+            ceiling: ceiling.abs(),
+            lookahead,
+            release_coeff,
+            delay_line: VecDeque::with_capacity(lookahead + 1),
+            min_window: VecDeque::with_capacity(lookahead + 1),
+            ramp: VecDeque::with_capacity(lookahead + 1),
+            ramp_sum: 0.0,
+            held_gain: 1.0,
+            gain: 1.0,
+            index: 0,
+        };
+        limiter.reset();
+        limiter
+    }
+
+    /// Latencia introducida por la anticipación, en muestras
+    pub fn latency_samples(&self) -> usize {
+        self.lookahead
+    }
+
+    /// Ganancia aplicada a la última muestra de salida (lineal)
+    pub fn current_gain(&self) -> f32 {
+        self.gain
+    }
+}
+
+impl AudioEffect for BrickWallLimiter {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let window = self.lookahead as u64 + 1;
+
+        // Ganancia necesaria para que esta muestra no supere el techo
+        let level = sample.abs();
+        let required = if level > self.ceiling { self.ceiling / level } else { 1.0 };
+
+        // Mínimo de la ganancia requerida en la ventana de anticipación
+        while self.min_window.back().is_some_and(|&(_, g)| g >= required) {
+            self.min_window.pop_back();
+        }
+        self.min_window.push_back((self.index, required));
+        if self.min_window.front().is_some_and(|&(i, _)| i + window <= self.index) {
+            self.min_window.pop_front();
+        }
+        let window_min = self.min_window.front().map_or(1.0, |&(_, g)| g);
+
+        // La ganancia baja al instante y se recupera con el release; nunca
+        // supera el mínimo de la ventana, así que el techo sigue garantizado
+        let released = window_min + self.release_coeff * (self.held_gain - window_min);
+        self.held_gain = released.min(window_min);
+
+        // Media móvil sobre la ventana: convierte los escalones en rampas de
+        // `lookahead + 1` muestras que terminan justo cuando sale el pico
+        self.ramp.push_back(self.held_gain);
+        self.ramp_sum += self.held_gain as f64;
+        if let Some(oldest) = self.ramp.pop_front() {
+            self.ramp_sum -= oldest as f64;
+        }
+        self.gain = (self.ramp_sum / window as f64) as f32;
+
+        self.index += 1;
+        self.delay_line.push_back(sample);
+        let delayed = self.delay_line.pop_front().unwrap_or(0.0);
+
+        // La comparación final solo protege contra errores de redondeo
+        (delayed * self.gain).clamp(-self.ceiling, self.ceiling)
+    }
+
+    fn reset(&mut self) {
+        self.delay_line.clear();
+        self.delay_line.resize(self.lookahead, 0.0);
+        self.min_window.clear();
+        self.ramp.clear();
+        self.ramp.resize(self.lookahead + 1, 1.0);
+        self.ramp_sum = (self.lookahead + 1) as f64;
+        self.held_gain = 1.0;
+        self.gain = 1.0;
+        self.index = 0;
+    }
+
+    fn name(&self) -> &'static str {
+        "BrickWallLimiter"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_brickwall_respects_ceiling_with_smooth_gain() {
+        let sample_rate = 44100.0;
+        let ceiling = 0.8;
+        let mut limiter = BrickWallLimiter::new(ceiling, 5.0, 50.0, sample_rate);
+        let lookahead = limiter.latency_samples();
+        assert_eq!(lookahead, 221);
+
+        // Tono con ráfagas fuertes e impulsos aislados
+        let mut rng = rand::rngs::StdRng::seed_from_u64(289);
+        let input: Vec<f32> = (0..44100)
+            .map(|i| {
+                let tone = 0.5 * (i as f32 * 0.05).sin();
+                let burst = if (i / 4000) % 2 == 1 { 3.0 } else { 1.0 };
+                let spike = if rng.gen_ratio(1, 2000) { rng.gen_range(-4.0..4.0) } else { 0.0 };
+                tone * burst + spike
+            })
+            .collect();
+
+        // La rampa más brusca posible reparte la reducción total en la ventana
+        let max_step = 1.0 / (lookahead + 1) as f32 + 1e-6;
+        let mut previous_gain = limiter.current_gain();
+        for (i, &x) in input.iter().enumerate() {
+            let y = limiter.process_sample(x);
+            assert!(y.abs() <= ceiling, "Sample {} exceeds the ceiling: {}", i, y);
+
+            // La ganancia ya basta por sí sola, sin depender del recorte final
+            let gain = limiter.current_gain();
+            if i >= lookahead {
+                assert!(input[i - lookahead].abs() * gain <= ceiling * (1.0 + 1e-5));
+            }
+            assert!(
+                (gain - previous_gain).abs() <= max_step,
+                "Gain jumped by {} at sample {}",
+                (gain - previous_gain).abs(),
+                i
+            );
+            previous_gain = gain;
+        }
+    }
+
+    #[test]
+    fn test_brickwall_latency_and_transparency() {
+        let mut limiter = BrickWallLimiter::new(0.9, 1.0, 20.0, 1000.0);
+        assert_eq!(limiter.latency_samples(), 1);
+
+        // Por debajo del techo solo se retrasa la señal
+        let input = [0.1, -0.2, 0.3, -0.4, 0.5];
+        let output: Vec<f32> = input.iter().map(|&x| limiter.process_sample(x)).collect();
+        assert_eq!(output, vec![0.0, 0.1, -0.2, 0.3, -0.4]);
+
+        limiter.reset();
+        assert_eq!(limiter.process_sample(0.5), 0.0);
+        assert_eq!(limiter.current_gain(), 1.0);
+    }
+}
@@ -0,0 +1,145 @@
+//! Implementación de un de-esser
+//!
+//! Las sibilantes ("s", "sh", "ch") concentran mucha energía en una banda
+//! estrecha de agudos, normalmente entre 5 y 8 kHz. El de-esser separa esa banda
+//! con un filtro pasa-banda, la comprime por separado y la vuelve a sumar con el
+//! resto de la señal, de modo que solo se atenúa cuando una sibilante la dispara.
+
+use biquad::frequency::*;
+use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType};
+
+use super::AudioEffect;
+use crate::filters::{EnvelopeFollower, EnvelopeMode};
+use crate::utils::{db_to_linear, linear_to_db};
+
+/// Factor de calidad del pasa-banda; cubre aproximadamente una octava
+const BAND_Q: f32 = 1.4;
+/// Tiempos del detector de la banda, rápidos para atrapar cada sibilante
+const ATTACK_MS: f32 = 1.0;
+const RELEASE_MS: f32 = 60.0;
+
+/// De-esser de banda dividida
+///
+/// La salida es `x - (1 - g) * banda`, donde `g` es la ganancia del compresor
+/// de la banda. Mientras la banda no supera el umbral, `g` vale 1 y la señal
+/// sale intacta.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, DeEsser};
+///
+/// let mut deesser = DeEsser::new(6500.0, -30.0, 6.0, 44100.0);
+/// let output = deesser.process_sample(0.1);
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeEsser {
+    /// Pasa-banda centrado en las sibilantes; `None` si la frecuencia no es válida
+    band: Option<DirectForm1<f32>>,
+    detector: EnvelopeFollower,
+    threshold_db: f32,
+    ratio: f32,
+}
+
+impl DeEsser {
+    /// Crea un nuevo de-esser
+    ///
+    /// # Argumentos
+    /// * `freq_hz` - Frecuencia central de las sibilantes en Hz (p. ej. 6500.0)
+    /// * `threshold_db` - Nivel de la banda en dBFS a partir del cual se atenúa
+    /// * `ratio` - Relación de compresión de la banda (al menos 1.0)
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    ///
+    /// Una frecuencia fuera del rango válido (por encima de Nyquist, p. ej.)
+    /// deja el efecto sin actuar.
+    pub fn new(freq_hz: f32, threshold_db: f32, ratio: f32, sample_rate: f32) -> Self {
+        let band = Coefficients::<f32>::from_params(FilterType::BandPass, sample_rate.hz(), freq_hz.hz(), BAND_Q)
+            .ok()
+            .map(DirectForm1::<f32>::new);
+
+        Self {
+            band,
+            detector: EnvelopeFollower::new(ATTACK_MS, RELEASE_MS, sample_rate, EnvelopeMode::Peak),
+            threshold_db,
+            ratio: ratio.max(1.0),
+        }
+    }
+}
+
+impl AudioEffect for DeEsser {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let Some(band) = self.band.as_mut() else {
+            return sample;
+        };
+
+        let sibilance = band.run(sample);
+        let level_db = linear_to_db(self.detector.process(sibilance));
+        let over_db = (level_db - self.threshold_db).max(0.0);
+        let gain = db_to_linear(-over_db * (1.0 - 1.0 / self.ratio));
+
+        // Restar la parte de la banda que sobra equivale a recombinar la banda
+        // comprimida con el resto de la señal
+        sample - (1.0 - gain) * sibilance
+    }
+
+    fn reset(&mut self) {
+        if let Some(band) = self.band.as_mut() {
+            band.reset_state();
+        }
+        self.detector.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "DeEsser"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn rms(signal: &[f32]) -> f32 {
+        (signal.iter().map(|x| x * x).sum::<f32>() / signal.len() as f32).sqrt()
+    }
+
+    #[test]
+    fn test_deesser_attenuates_sibilance_burst() {
+        let sample_rate = 44100.0;
+        let voice: Vec<f32> = (0..44100).map(|i| 0.3 * (2.0 * PI * 200.0 * i as f32 / sample_rate).sin()).collect();
+        // Ráfaga de 7 kHz entre 0.4 s y 0.6 s, como una "s" fuerte
+        let burst: Vec<f32> = (0..44100)
+            .map(|i| {
+                if (17640..26460).contains(&i) {
+                    0.4 * (2.0 * PI * 7000.0 * i as f32 / sample_rate).sin()
+                } else {
+                    0.0
+                }
+            })
+            .collect();
+        let input: Vec<f32> = voice.iter().zip(&burst).map(|(v, b)| v + b).collect();
+
+        let mut deesser = DeEsser::new(7000.0, -30.0, 8.0, sample_rate);
+        let mut output = input.clone();
+        deesser.process_buffer(&mut output);
+
+        // Sin sibilantes la voz pasa intacta
+        let before = 0..17000;
+        let max_diff = before.clone().map(|i| (output[i] - voice[i]).abs()).fold(0.0f32, f32::max);
+        assert!(max_diff < 1e-3, "Voice changed by {}", max_diff);
+
+        // Lo que queda de la ráfaga, una vez descontada la voz, baja más de 10 dB
+        let during = 18000..26000;
+        let residual: Vec<f32> = during.clone().map(|i| output[i] - voice[i]).collect();
+        let attenuation_db = 20.0 * (rms(&burst[during]) / rms(&residual)).log10();
+        assert!(attenuation_db > 10.0, "Burst attenuated only {} dB", attenuation_db);
+
+        deesser.reset();
+        let mut again = input.clone();
+        deesser.process_buffer(&mut again);
+        assert_eq!(again, output);
+    }
+}
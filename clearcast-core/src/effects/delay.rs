@@ -5,18 +5,60 @@
 //! para ajustar el tiempo de retardo, retroalimentación y mezcla de señal.
 
 use std::collections::VecDeque;
-use super::AudioEffect;
+use super::{check_parameter, AudioEffect, ParamDescriptor, SoftLimiter};
+
+/// Retardo mínimo en muestras; por debajo la lectura alcanzaría la muestra actual
+const MIN_DELAY_SAMPLES: f32 = 1.0;
+
+/// Retardo máximo que se puede elegir con [`AudioEffect::set_parameter`]
+const MAX_DELAY_MS: f32 = 5000.0;
+/// Retroalimentación máxima; con 1.0 o más los ecos no se apagarían nunca
+const MAX_FEEDBACK: f32 = 0.99;
+/// Nivel a partir del cual el limitador del lazo empieza a actuar
+const FEEDBACK_LIMIT_THRESHOLD: f32 = 0.8;
+/// Margen de la rodilla del limitador del lazo: la señal recirculada nunca
+/// supera `FEEDBACK_LIMIT_THRESHOLD + FEEDBACK_LIMIT_KNEE`
+const FEEDBACK_LIMIT_KNEE: f32 = 0.2;
 
 /// Efecto de delay/eco digital con retroalimentación configurable
+///
+/// El tiempo de retardo puede ser fraccionario: la muestra retrasada se obtiene
+/// interpolando linealmente entre las dos muestras más cercanas del historial,
+/// de modo que el retardo se puede modular sin cuantizarlo a muestras enteras.
+///
+/// Con un tiempo de deslizamiento (ver [`Delay::set_glide_ms`]) los cambios de
+/// retardo desplazan la posición de lectura gradualmente en lugar de saltar,
+/// evitando el chasquido al mover el control durante la reproducción.
+///
+/// Con amortiguación (ver [`Delay::set_feedback_damping`]) un filtro paso bajo
+/// en el lazo de retroalimentación oscurece cada repetición un poco más que la
+/// anterior, como en un delay de cinta o analógico.
+///
+/// La señal que vuelve por el lazo pasa por un limitador suave que no la
+/// toca por debajo de 0.8 y no la deja pasar de 1.0. Así una entrada
+/// sostenida con mucha retroalimentación no acumula energía sin control.
+#[derive(Debug, Clone)]
 pub struct Delay {
+    /// Historial de la señal (entrada más retroalimentación), la más antigua al frente
     buffer: VecDeque<f32>,
-    #[allow(dead_code)]
     max_delay_samples: usize,
-    delay_samples: usize,
+    delay_samples: f32,
+    /// Retardo hacia el que se desliza la posición de lectura
+    target_delay_samples: f32,
+    /// Avance máximo de la posición de lectura por muestra durante el deslizamiento
+    glide_step: f32,
+    glide_ms: f32,
     feedback: f32,
+    /// Frecuencia de corte del paso bajo de la retroalimentación, si lo hay
+    damping_cutoff_hz: Option<f32>,
+    /// Coeficiente del filtro de un polo
+    damping_coeff: f32,
+    /// Última salida del filtro de amortiguación
+    damping_state: f32,
+    /// Limitador de la señal recirculada
+    feedback_limiter: SoftLimiter,
     wet: f32,
     dry: f32,
-    #[allow(dead_code)]
     sample_rate: u32,
 }
 
@@ -29,22 +71,139 @@ impl Delay {
         dry: f32,
         sample_rate: u32,
     ) -> Self {
-        let delay_samples = (delay_ms * sample_rate as f32 / 1000.0).round() as usize;
+        let delay_samples = Self::ms_to_samples(delay_ms, sample_rate);
+        let max_delay_samples = delay_samples.ceil() as usize;
         
         // Crear un buffer con ceros del tamaño del retardo
-        let mut buffer = VecDeque::with_capacity(delay_samples);
-        buffer.resize(delay_samples, 0.0);
+        let mut buffer = VecDeque::with_capacity(max_delay_samples);
+        buffer.resize(max_delay_samples, 0.0);
         
         Self {
             buffer,
-            max_delay_samples: delay_samples,
+            max_delay_samples,
             delay_samples,
-            feedback: feedback.clamp(0.0, 0.99), // Evitar inestabilidad
+            target_delay_samples: delay_samples,
+            glide_step: 0.0,
+            glide_ms: 0.0,
+            feedback: feedback.clamp(0.0, MAX_FEEDBACK), // Evitar inestabilidad
+            damping_cutoff_hz: None,
+            damping_coeff: 1.0,
+            damping_state: 0.0,
+            feedback_limiter: SoftLimiter::new(FEEDBACK_LIMIT_THRESHOLD, FEEDBACK_LIMIT_KNEE),
             wet: wet.clamp(0.0, 1.0),
             dry: dry.clamp(0.0, 1.0),
             sample_rate,
         }
     }
+
+    /// Cambia el tiempo de retardo en milisegundos
+    ///
+    /// Se puede llamar entre muestras; el historial se conserva, y si el nuevo
+    /// retardo es mayor que el buffer actual este crece con silencio por delante.
+    /// Con un tiempo de deslizamiento configurado, el retardo llega al nuevo
+    /// valor en una rampa lineal de esa duración; si no, cambia al instante.
+    pub fn set_delay_ms(&mut self, ms: f32) {
+        self.target_delay_samples = Self::ms_to_samples(ms, self.sample_rate);
+
+        let glide_samples = self.glide_ms * self.sample_rate as f32 / 1000.0;
+        if glide_samples >= 1.0 {
+            self.glide_step = (self.target_delay_samples - self.delay_samples).abs() / glide_samples;
+        } else {
+            self.delay_samples = self.target_delay_samples;
+            self.glide_step = 0.0;
+        }
+
+        let required = self.target_delay_samples.ceil() as usize;
+        if required > self.max_delay_samples {
+            for _ in self.max_delay_samples..required {
+                self.buffer.push_front(0.0);
+            }
+            self.max_delay_samples = required;
+        }
+    }
+
+    /// Tiempo de retardo actual en milisegundos
+    ///
+    /// Durante un deslizamiento es el retardo aplicado en este momento, no el objetivo.
+    pub fn delay_ms(&self) -> f32 {
+        self.delay_samples * 1000.0 / self.sample_rate as f32
+    }
+
+    /// Establece el tiempo que tarda un cambio de retardo en completarse
+    ///
+    /// Afecta a las siguientes llamadas a [`Delay::set_delay_ms`]. Con 0 ms (el
+    /// valor por defecto) el retardo cambia de golpe.
+    pub fn set_glide_ms(&mut self, glide_ms: f32) {
+        self.glide_ms = glide_ms.max(0.0);
+    }
+
+    /// Tiempo de deslizamiento en milisegundos
+    pub fn glide_ms(&self) -> f32 {
+        self.glide_ms
+    }
+
+    /// Cambia la retroalimentación, limitada como en [`Delay::new`] a 0.0-0.99
+    pub fn set_feedback(&mut self, feedback: f32) {
+        self.feedback = feedback.clamp(0.0, MAX_FEEDBACK);
+    }
+
+    /// Retroalimentación actual
+    pub fn feedback(&self) -> f32 {
+        self.feedback
+    }
+
+    /// Activa un paso bajo de un polo en el lazo de retroalimentación
+    ///
+    /// La primera repetición sale con todo su brillo y cada vuelta por el lazo
+    /// atenúa los agudos por encima de `cutoff_hz`, así que los ecos
+    /// posteriores suenan cada vez más apagados. Una frecuencia no positiva o
+    /// no finita desactiva la amortiguación.
+    pub fn set_feedback_damping(&mut self, cutoff_hz: f32) {
+        if cutoff_hz > 0.0 && cutoff_hz.is_finite() {
+            let omega = 2.0 * std::f32::consts::PI * cutoff_hz / self.sample_rate as f32;
+            self.damping_cutoff_hz = Some(cutoff_hz);
+            self.damping_coeff = 1.0 - (-omega).exp();
+        } else {
+            self.damping_cutoff_hz = None;
+            self.damping_coeff = 1.0;
+        }
+    }
+
+    /// Frecuencia de corte de la amortiguación, o `None` si está desactivada
+    pub fn feedback_damping(&self) -> Option<f32> {
+        self.damping_cutoff_hz
+    }
+
+    fn ms_to_samples(ms: f32, sample_rate: u32) -> f32 {
+        (ms * sample_rate as f32 / 1000.0).max(MIN_DELAY_SAMPLES)
+    }
+
+    /// Lee el historial `delay` muestras atrás interpolando linealmente
+    fn read_delayed(&self, delay: f32) -> f32 {
+        // El final del buffer es la muestra anterior a la actual (retardo 1)
+        let delay = delay.clamp(MIN_DELAY_SAMPLES, self.buffer.len() as f32);
+        read_interpolated(&self.buffer, delay - 1.0)
+    }
+}
+
+/// Lee un historial `offset` muestras por detrás de la más reciente (el final
+/// del búfer), interpolando linealmente entre las dos muestras vecinas
+///
+/// `offset` se limita al historial disponible. Lo comparten los efectos de
+/// retardo modulado para que todos lean el historial del mismo modo.
+pub(crate) fn read_interpolated(buffer: &VecDeque<f32>, offset: f32) -> f32 {
+    let newest = buffer.len() - 1;
+    let offset = offset.clamp(0.0, newest as f32);
+    let whole = offset.floor() as usize;
+    let frac = offset - whole as f32;
+
+    let near = buffer[newest - whole];
+    if frac > 0.0 {
+        let far = buffer[newest - whole - 1];
+        near + frac * (far - near)
+    } else {
+        near
+    }
 }
 
 impl AudioEffect for Delay {
@@ -55,50 +214,79 @@ impl AudioEffect for Delay {
     }
     
     fn process_sample(&mut self, sample: f32) -> f32 {
-        // Inicializar la salida con la señal seca
-        let mut output = sample * self.dry;
-        
-        // Si el buffer está vacío, inicializarlo con ceros
-        if self.buffer.is_empty() {
-            // El buffer debe tener capacidad para almacenar las muestras de retardo
-            self.buffer.resize(self.delay_samples, 0.0);
-        }
-        
-        // Obtener la muestra retrasada (la más antigua en el buffer)
-        let delayed = if !self.buffer.is_empty() {
-            self.buffer.pop_front().unwrap_or(0.0)
-        } else {
-            0.0
-        };
+        // Obtener la muestra retrasada del historial
+        let delayed = self.read_delayed(self.delay_samples);
         
-        // Solo aplicar la señal húmeda si el buffer está lleno (ha pasado el tiempo de retardo)
-        if self.buffer.len() >= self.delay_samples {
-            output += delayed * self.wet;
-        }
+        // Mezclar la señal seca con la húmeda
+        let output = sample * self.dry + delayed * self.wet;
         
-        // Calcular la retroalimentación
-        let feedback = if self.feedback > 0.0 {
-            delayed * self.feedback
+        // Mezclar la señal de entrada con la retroalimentación, amortiguada
+        // por el paso bajo antes de aplicarle la ganancia y limitada después
+        let fed_back = if self.damping_cutoff_hz.is_some() {
+            self.damping_state += self.damping_coeff * (delayed - self.damping_state);
+            self.damping_state
         } else {
-            0.0
+            delayed
         };
+        let input = sample + self.feedback_limiter.process_sample(fed_back * self.feedback);
         
-        // Mezclar la señal de entrada con la retroalimentación
-        let input = sample + feedback;
-        
-        // Agregar la nueva muestra al final del buffer
+        // Agregar la nueva muestra al final del buffer y descartar la más antigua
         self.buffer.push_back(input);
+        self.buffer.pop_front();
+        
+        // Avanzar la posición de lectura hacia el retardo objetivo; se hace
+        // después de leer para no adelantarse al historial que ya existe
+        if self.delay_samples != self.target_delay_samples {
+            let diff = self.target_delay_samples - self.delay_samples;
+            if diff.abs() <= self.glide_step {
+                self.delay_samples = self.target_delay_samples;
+            } else {
+                self.delay_samples += self.glide_step.copysign(diff);
+            }
+        }
         
         output
     }
     
     fn reset(&mut self) {
-        self.buffer.clear();
+        self.buffer.iter_mut().for_each(|x| *x = 0.0);
+        self.damping_state = 0.0;
+        self.delay_samples = self.target_delay_samples;
     }
     
     fn name(&self) -> &'static str {
         "Delay"
     }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+
+    fn parameters(&self) -> Vec<ParamDescriptor> {
+        vec![
+            ParamDescriptor {
+                name: "delay_ms",
+                min: 0.0,
+                max: MAX_DELAY_MS,
+                value: self.target_delay_samples * 1000.0 / self.sample_rate as f32,
+            },
+            ParamDescriptor { name: "feedback", min: 0.0, max: MAX_FEEDBACK, value: self.feedback },
+            ParamDescriptor { name: "wet", min: 0.0, max: 1.0, value: self.wet },
+            ParamDescriptor { name: "dry", min: 0.0, max: 1.0, value: self.dry },
+        ]
+    }
+
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), String> {
+        check_parameter(&self.parameters(), name, value)?;
+        match name {
+            "delay_ms" => self.set_delay_ms(value),
+            "feedback" => self.set_feedback(value),
+            "wet" => self.wet = value,
+            "dry" => self.dry = value,
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -141,9 +329,8 @@ mod tests {
         
         // Verificar que la señal húmeda se aplica después del retardo
         // La señal húmeda debería aparecer después de delay_samples
-        // Como el buffer se llena con ceros inicialmente, la primera señal húmeda debería ser 0
         if delay_samples < output.len() {
-            let expected_wet = 0.0; // El buffer se inicializa con ceros
+            let expected_wet = signal[0] * wet;
             assert!(
                 (output[delay_samples] - expected_wet).abs() < 1e-6,
                 "Expected wet signal at delay_samples ({}): {}, got: {}",
@@ -178,4 +365,158 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_fractional_delay_splits_impulse() {
+        // A 1 kHz cada milisegundo es una muestra
+        let mut delay = Delay::new(10.5, 0.0, 1.0, 0.0, 1000);
+        let mut impulse = vec![0.0; 16];
+        impulse[0] = 1.0;
+
+        let mut output = impulse.clone();
+        delay.process_buffer(&mut output);
+        assert!((output[10] - 0.5).abs() < 1e-6);
+        assert!((output[11] - 0.5).abs() < 1e-6);
+        assert!(output.iter().enumerate().all(|(i, &x)| i == 10 || i == 11 || x == 0.0));
+
+        // La energía se reparte según la parte fraccionaria del retardo
+        delay.reset();
+        delay.set_delay_ms(10.25);
+        assert!((delay.delay_ms() - 10.25).abs() < 1e-6);
+        let mut output = impulse.clone();
+        delay.process_buffer(&mut output);
+        assert!((output[10] - 0.75).abs() < 1e-6);
+        assert!((output[11] - 0.25).abs() < 1e-6);
+
+        // Un retardo mayor que el inicial amplía el historial
+        delay.reset();
+        delay.set_delay_ms(12.0);
+        let mut output = impulse;
+        delay.process_buffer(&mut output);
+        assert!((output[12] - 1.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_feedback_damping_darkens_later_echoes() {
+        use rand::{Rng, SeedableRng};
+
+        let sample_rate = 8000;
+        let echo_len = 400; // 50 ms
+        let mut rng = rand::rngs::StdRng::seed_from_u64(352);
+        let mut input = vec![0.0f32; 6 * echo_len];
+        for x in input.iter_mut().take(64) {
+            *x = rng.gen_range(-0.5..0.5);
+        }
+
+        // Proporción de energía en la primera diferencia: mayor cuanto más agudo
+        let brightness = |segment: &[f32]| {
+            let diff: f32 = segment.windows(2).map(|w| (w[1] - w[0]).powi(2)).sum();
+            let energy: f32 = segment.iter().map(|x| x * x).sum();
+            diff / energy
+        };
+        let echoes = |damping_hz: f32| {
+            let mut delay = Delay::new(50.0, 0.8, 1.0, 0.0, sample_rate);
+            delay.set_feedback_damping(damping_hz);
+            let mut output = input.clone();
+            delay.process_buffer(&mut output);
+            (1..=4).map(|k| brightness(&output[k * echo_len..(k + 1) * echo_len])).collect::<Vec<f32>>()
+        };
+
+        // Sin amortiguación todas las repeticiones son copias escaladas
+        let bright = echoes(0.0);
+        for echo in &bright[1..] {
+            assert!((echo - bright[0]).abs() < 1e-3 * bright[0]);
+        }
+
+        // Con amortiguación la primera queda igual y cada una es más oscura
+        let damped = echoes(800.0);
+        assert!((damped[0] - bright[0]).abs() < 1e-3 * bright[0]);
+        for pair in damped.windows(2) {
+            assert!(pair[1] < 0.8 * pair[0], "Echoes not darkening: {:?}", damped);
+        }
+
+        let mut delay = Delay::new(50.0, 0.8, 1.0, 0.0, sample_rate);
+        assert_eq!(delay.feedback_damping(), None);
+        delay.set_feedback_damping(800.0);
+        assert_eq!(delay.feedback_damping(), Some(800.0));
+        delay.set_feedback_damping(-1.0);
+        assert_eq!(delay.feedback_damping(), None);
+    }
+
+    #[test]
+    fn test_delay_glide_avoids_discontinuities() {
+        let sample_rate = 44100;
+        let input: Vec<f32> = (0..44100)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+
+        // Mayor diferencia entre muestras consecutivas tras el cambio de retardo
+        let max_step = |glide_ms: f32| {
+            let mut delay = Delay::new(10.0, 0.0, 1.0, 0.0, sample_rate);
+            delay.set_glide_ms(glide_ms);
+            let mut output = input.clone();
+            let (first, second) = output.split_at_mut(22050);
+            delay.process_buffer(first);
+            delay.set_delay_ms(20.0);
+            delay.process_buffer(second);
+            assert!((delay.delay_ms() - 20.0).abs() < 1e-3);
+            output[1000..].windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0f32, f32::max)
+        };
+
+        // Pendiente máxima del seno: 0.5 * 2π * 440 / 44100 ≈ 0.031 por muestra
+        let threshold = 0.05;
+        let snapped = max_step(0.0);
+        let glided = max_step(50.0);
+        assert!(snapped > threshold, "Snapping should click, max step {}", snapped);
+        assert!(glided < threshold, "Glide should be smooth, max step {}", glided);
+    }
+
+    #[test]
+    fn test_parameters_by_name() {
+        let mut delay = Delay::new(10.0, 0.0, 1.0, 0.0, 1000);
+        let names: Vec<&str> = delay.parameters().iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["delay_ms", "feedback", "wet", "dry"]);
+        assert_eq!(delay.parameters()[0].value, 10.0);
+
+        delay.set_parameter("feedback", 0.5).unwrap();
+        let feedback = delay.parameters().into_iter().find(|p| p.name == "feedback").unwrap();
+        assert_eq!(feedback.value, 0.5);
+
+        // Con retroalimentación el impulso reaparece a mitad de nivel 10 muestras después
+        let mut buffer = vec![0.0; 25];
+        buffer[0] = 1.0;
+        delay.process_buffer(&mut buffer);
+        assert!((buffer[10] - 1.0).abs() < 1e-6);
+        assert!((buffer[20] - 0.5).abs() < 1e-6);
+
+        assert!(delay.set_parameter("feedback", 1.5).is_err());
+        assert!(delay.set_parameter("time", 20.0).is_err());
+        assert_eq!(delay.parameters()[1].value, 0.5);
+    }
+
+    #[test]
+    fn test_high_feedback_stays_bounded() {
+        // Entrada constante durante 10 s a 1 kHz con la retroalimentación máxima:
+        // sin límite el lazo se acercaría a 1 / (1 - 0.99) = 100
+        let mut delay = Delay::new(10.0, 0.0, 1.0, 0.0, 1000);
+        delay.set_feedback(2.0);
+        assert_eq!(delay.feedback(), 0.99);
+
+        let mut buffer = vec![1.0; 10_000];
+        delay.process_buffer(&mut buffer);
+        assert!(buffer.iter().all(|x| x.is_finite() && x.abs() <= 2.0));
+        assert!(buffer[9_990..].iter().all(|&x| x > 1.5), "Echoes should stay loud but bounded");
+
+        // Los ecos por debajo del umbral del limitador no cambian
+        let mut delay = Delay::new(10.0, 0.5, 1.0, 0.0, 1000);
+        let mut impulse = vec![0.0; 31];
+        impulse[0] = 0.6;
+        delay.process_buffer(&mut impulse);
+        assert_eq!(impulse[10], 0.6);
+        assert_eq!(impulse[20], 0.3);
+        assert_eq!(impulse[30], 0.15);
+
+        delay.set_feedback(-1.0);
+        assert_eq!(delay.feedback(), 0.0);
+    }
 }
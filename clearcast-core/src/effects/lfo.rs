@@ -0,0 +1,179 @@
+//! Oscilador de baja frecuencia (LFO) para efectos de modulación
+//!
+//! Trémolo, chorus, flanger y demás efectos de modulación necesitan una señal
+//! de control periódica y lenta. Este módulo la proporciona con varias formas
+//! de onda para que cada efecto no tenga que reimplementarla.
+
+use std::f32::consts::PI;
+
+/// Forma de onda del LFO
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Waveform {
+    /// Senoide que empieza en 0 y sube
+    #[default]
+    Sine,
+    /// Triángulo que empieza en 0 y sube, en fase con la senoide
+    Triangle,
+    /// Diente de sierra que sube de -1 a 1 en cada ciclo
+    Saw,
+    /// Cuadrada: 1 en la primera mitad del ciclo y -1 en la segunda
+    Square,
+}
+
+/// Oscilador de baja frecuencia con salida en [-1, 1]
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{Lfo, Waveform};
+///
+/// let mut lfo = Lfo::new(1.0, 4.0);
+/// lfo.set_waveform(Waveform::Square);
+/// let samples: Vec<f32> = (0..4).map(|_| lfo.next()).collect();
+/// assert_eq!(samples, vec![1.0, 1.0, -1.0, -1.0]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Lfo {
+    waveform: Waveform,
+    /// Fase actual en ciclos, en [0, 1)
+    phase: f32,
+    /// Incremento de fase por muestra, en ciclos
+    phase_increment: f32,
+    sample_rate: f32,
+}
+
+impl Lfo {
+    /// Crea un LFO senoidal
+    ///
+    /// # Argumentos
+    /// * `rate_hz` - Frecuencia de oscilación en Hz
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(rate_hz: f32, sample_rate: f32) -> Self {
+        Self {
+            waveform: Waveform::default(),
+            phase: 0.0,
+            phase_increment: rate_hz.max(0.0) / sample_rate,
+            sample_rate,
+        }
+    }
+
+    /// Cambia la forma de onda sin alterar la fase
+    pub fn set_waveform(&mut self, waveform: Waveform) {
+        self.waveform = waveform;
+    }
+
+    /// Devuelve la forma de onda actual
+    pub fn waveform(&self) -> Waveform {
+        self.waveform
+    }
+
+    /// Cambia la frecuencia de oscilación sin alterar la fase
+    pub fn set_rate(&mut self, rate_hz: f32) {
+        self.phase_increment = rate_hz.max(0.0) / self.sample_rate;
+    }
+
+    /// Devuelve la frecuencia de oscilación en Hz
+    pub fn rate(&self) -> f32 {
+        self.phase_increment * self.sample_rate
+    }
+
+    /// Coloca el oscilador en una fase dada, en ciclos (0.25 = un cuarto de ciclo)
+    pub fn set_phase(&mut self, phase: f32) {
+        self.phase = phase.rem_euclid(1.0);
+    }
+
+    /// Vuelve a la fase 0
+    pub fn reset(&mut self) {
+        self.phase = 0.0;
+    }
+
+    /// Devuelve la siguiente muestra del oscilador y avanza la fase
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> f32 {
+        let p = self.phase;
+        let value = match self.waveform {
+            Waveform::Sine => (2.0 * PI * p).sin(),
+            Waveform::Triangle => {
+                if p < 0.25 {
+                    4.0 * p
+                } else if p < 0.75 {
+                    2.0 - 4.0 * p
+                } else {
+                    4.0 * p - 4.0
+                }
+            }
+            Waveform::Saw => 2.0 * p - 1.0,
+            Waveform::Square => {
+                if p < 0.5 { 1.0 } else { -1.0 }
+            }
+        };
+
+        self.phase += self.phase_increment;
+        if self.phase >= 1.0 {
+            self.phase -= 1.0;
+        }
+
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_1_SQRT_2;
+
+    const ALL: [Waveform; 4] = [Waveform::Sine, Waveform::Triangle, Waveform::Saw, Waveform::Square];
+
+    fn render(waveform: Waveform, rate_hz: f32, sample_rate: f32, len: usize) -> Vec<f32> {
+        let mut lfo = Lfo::new(rate_hz, sample_rate);
+        lfo.set_waveform(waveform);
+        (0..len).map(|_| lfo.next()).collect()
+    }
+
+    #[test]
+    fn test_lfo_waveform_shapes() {
+        // 1 Hz a 8 Hz de muestreo: una muestra cada octavo de ciclo
+        let expected: [(Waveform, [f32; 8]); 4] = [
+            (Waveform::Sine, [0.0, FRAC_1_SQRT_2, 1.0, FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2, -1.0, -FRAC_1_SQRT_2]),
+            (Waveform::Triangle, [0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -1.0, -0.5]),
+            (Waveform::Saw, [-1.0, -0.75, -0.5, -0.25, 0.0, 0.25, 0.5, 0.75]),
+            (Waveform::Square, [1.0, 1.0, 1.0, 1.0, -1.0, -1.0, -1.0, -1.0]),
+        ];
+
+        for (waveform, shape) in expected {
+            let output = render(waveform, 1.0, 8.0, 16);
+            for (i, &value) in output.iter().enumerate() {
+                assert!(
+                    (value - shape[i % 8]).abs() < 1e-3,
+                    "{:?} sample {}: expected {}, got {}",
+                    waveform,
+                    i,
+                    shape[i % 8],
+                    value
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_lfo_frequency() {
+        let sample_rate = 1000.0;
+        for waveform in ALL {
+            for rate in [0.5, 5.0, 12.0] {
+                // Dos segundos de señal: 2 * rate ciclos completos
+                let output = render(waveform, rate, sample_rate, 2000);
+                assert!(output.iter().all(|x| (-1.0..=1.0).contains(x)));
+
+                let falling = output.windows(2).filter(|w| w[0] > 0.0 && w[1] <= 0.0).count();
+                let expected = 2.0 * rate;
+                assert!(
+                    (falling as f32 - expected).abs() <= 1.0,
+                    "{:?} at {} Hz: {} cycles, expected {}",
+                    waveform,
+                    rate,
+                    falling,
+                    expected
+                );
+            }
+        }
+    }
+}
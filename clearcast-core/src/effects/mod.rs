@@ -26,14 +26,95 @@
 //! let thread_safe_delay = delay.boxed();
 //! ```
 
-mod delay;
 mod soft_limiter;
+pub use soft_limiter::{soft_limit_buffer, KneeShape, SoftLimiter};
 
+// El resto de efectos usa búferes dinámicos o funciones de `std`; solo el
+// limitador suave forma parte del núcleo `no_std`
+#[cfg(feature = "std")]
+mod delay;
+#[cfg(feature = "std")]
 pub use delay::Delay;
-pub use soft_limiter::SoftLimiter;
 
+#[cfg(feature = "std")]
 mod normalize;
-pub use normalize::normalize_rms;
+#[cfg(feature = "std")]
+pub use normalize::{normalize_rms, normalize_rms_windowed, RmsNormalizer};
+
+#[cfg(feature = "std")]
+mod vocal_rider;
+#[cfg(feature = "std")]
+pub use vocal_rider::VocalRider;
+
+#[cfg(feature = "std")]
+mod varispeed;
+#[cfg(feature = "std")]
+pub use varispeed::varispeed;
+
+#[cfg(feature = "std")]
+mod brickwall;
+#[cfg(feature = "std")]
+pub use brickwall::BrickWallLimiter;
+
+#[cfg(feature = "std")]
+mod bitcrusher;
+#[cfg(feature = "std")]
+pub use bitcrusher::BitCrusher;
+
+#[cfg(feature = "std")]
+mod saturation;
+#[cfg(feature = "std")]
+pub use saturation::Saturation;
+
+#[cfg(feature = "std")]
+mod stereo;
+#[cfg(feature = "std")]
+pub use stereo::{from_mid_side, to_mid_side, StereoWidener};
+
+#[cfg(feature = "std")]
+mod lfo;
+#[cfg(feature = "std")]
+pub use lfo::{Lfo, Waveform};
+
+#[cfg(feature = "std")]
+mod tremolo;
+#[cfg(feature = "std")]
+pub use tremolo::Tremolo;
+
+#[cfg(feature = "std")]
+mod phaser;
+#[cfg(feature = "std")]
+pub use phaser::Phaser;
+
+#[cfg(feature = "std")]
+mod deesser;
+#[cfg(feature = "std")]
+pub use deesser::DeEsser;
+
+#[cfg(feature = "std")]
+mod transient;
+#[cfg(feature = "std")]
+pub use transient::TransientShaper;
+
+#[cfg(feature = "std")]
+mod haas;
+#[cfg(feature = "std")]
+pub use haas::Haas;
+
+#[cfg(feature = "std")]
+mod vibrato;
+#[cfg(feature = "std")]
+pub use vibrato::Vibrato;
+
+#[cfg(feature = "std")]
+mod pitch;
+#[cfg(feature = "std")]
+pub use pitch::PitchShifter;
+
+#[cfg(feature = "std")]
+mod autopan;
+#[cfg(feature = "std")]
+pub use autopan::AutoPan;
 
 /// Interfaz base para todos los efectos de audio
 ///
@@ -78,6 +159,34 @@ pub trait AudioEffect: Send + Sync {
         }
     }
     
+    /// Procesa un par de canales estéreo en su lugar
+    ///
+    /// Por defecto aplica `process_buffer` primero al canal izquierdo y luego
+    /// al derecho con la misma instancia, lo que solo es correcto para efectos
+    /// sin estado: un delay, por ejemplo, pasaría el eco del izquierdo al
+    /// derecho. Los efectos mono con estado implementan
+    /// [`AudioEffect::clone_box`] para que `AudioEngine` procese el canal
+    /// derecho con su propia instancia. Los efectos estéreo, como
+    /// `StereoWidener` o `AutoPan`, lo sobrescriben para trabajar con ambos
+    /// canales a la vez.
+    fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        self.process_buffer(left);
+        self.process_buffer(right);
+    }
+
+    /// Devuelve una copia independiente del efecto, si tiene estado que copiar
+    ///
+    /// `AudioEngine::process_stereo` procesa el canal derecho con una copia
+    /// (reiniciada) de cada efecto que la devuelva, de modo que el estado de
+    /// un canal nunca pasa al otro. Por defecto devuelve `None`: el efecto no
+    /// tiene estado o trata los dos canales en [`AudioEffect::process_stereo`].
+    ///
+    /// Solo disponible con la característica `std`.
+    #[cfg(feature = "std")]
+    fn clone_box(&self) -> Option<std::boxed::Box<dyn AudioEffect>> {
+        None
+    }
+
     /// Reinicia el estado interno del efecto
     fn reset(&mut self);
     
@@ -85,10 +194,304 @@ pub trait AudioEffect: Send + Sync {
     fn name(&self) -> &'static str;
     
     /// Crea una nueva instancia en un Arc<Mutex<Self>> para uso seguro en hilos
+    ///
+    /// Solo disponible con la característica `std`.
+    #[cfg(feature = "std")]
     fn boxed(self) -> std::sync::Arc<std::sync::Mutex<Self>> 
     where 
         Self: Sized + 'static 
     {
         std::sync::Arc::new(std::sync::Mutex::new(self))
     }
+
+    /// Envuelve un iterador de muestras aplicando el efecto de forma perezosa
+    ///
+    /// Permite encadenar efectos sin búferes intermedios.
+    ///
+    /// # Ejemplo
+    /// ```rust
+    /// use clearcast_core::effects::{AudioEffect, Delay, SoftLimiter};
+    ///
+    /// let input = vec![0.5, 1.5, -0.2, 0.0];
+    /// let output: Vec<f32> = SoftLimiter::new(0.8, 0.1)
+    ///     .process_iter(Delay::new(1.0, 0.0, 0.5, 0.5, 1000).process_iter(input.into_iter()))
+    ///     .collect();
+    /// assert_eq!(output.len(), 4);
+    /// ```
+    fn process_iter<I>(self, iter: I) -> EffectIter<Self, I>
+    where
+        Self: Sized,
+        I: Iterator<Item = f32>,
+    {
+        EffectIter { effect: self, iter }
+    }
+
+    /// Describe los parámetros ajustables del efecto
+    ///
+    /// Permite construir controles genéricos para cualquier efecto. Por
+    /// defecto el efecto no expone ningún parámetro.
+    ///
+    /// # Ejemplo
+    /// ```rust
+    /// use clearcast_core::effects::{AudioEffect, Delay};
+    ///
+    /// let mut delay = Delay::new(300.0, 0.5, 0.3, 0.7, 44100);
+    /// for param in delay.parameters() {
+    ///     println!("{}: {} ({} a {})", param.name, param.value, param.min, param.max);
+    /// }
+    /// delay.set_parameter("feedback", 0.25).unwrap();
+    /// assert!(delay.set_parameter("feedback", 2.0).is_err());
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn parameters(&self) -> alloc::vec::Vec<ParamDescriptor> {
+        alloc::vec::Vec::new()
+    }
+
+    /// Cambia un parámetro por su nombre
+    ///
+    /// Devuelve un error si el efecto no tiene ese parámetro o si el valor
+    /// queda fuera de su rango. Por defecto todos los nombres son desconocidos.
+    #[cfg(feature = "alloc")]
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), alloc::string::String> {
+        let _ = value;
+        Err(alloc::format!("{} has no parameter named '{}'", self.name(), name))
+    }
+}
+
+/// Descripción de un parámetro ajustable de un efecto
+///
+/// Se obtiene con [`AudioEffect::parameters`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParamDescriptor {
+    /// Nombre con el que se cambia en [`AudioEffect::set_parameter`]
+    pub name: &'static str,
+    /// Valor mínimo admitido
+    pub min: f32,
+    /// Valor máximo admitido
+    pub max: f32,
+    /// Valor actual
+    pub value: f32,
+}
+
+/// Comprueba que `name` está entre los parámetros y que `value` cae en su rango
+#[cfg(feature = "alloc")]
+fn check_parameter(params: &[ParamDescriptor], name: &str, value: f32) -> Result<(), alloc::string::String> {
+    let param = params
+        .iter()
+        .find(|param| param.name == name)
+        .ok_or_else(|| alloc::format!("Unknown parameter '{}'", name))?;
+    if !(param.min..=param.max).contains(&value) {
+        return Err(alloc::format!(
+            "Value {} for '{}' is outside [{}, {}]",
+            value, name, param.min, param.max
+        ));
+    }
+    Ok(())
+}
+
+/// Efecto que invierte la polaridad de la señal
+///
+/// Niega cada muestra. Sirve para corregir cancelaciones de fase entre
+/// micrófonos que captan la misma fuente con polaridad opuesta.
+///
+/// # Ejemplo
+/// ```rust
+/// use clearcast_core::effects::{AudioEffect, PolarityInvert};
+///
+/// let mut buffer = vec![0.5, -0.25];
+/// PolarityInvert.process_buffer(&mut buffer);
+/// assert_eq!(buffer, vec![-0.5, 0.25]);
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PolarityInvert;
+
+impl AudioEffect for PolarityInvert {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        -sample
+    }
+
+    fn reset(&mut self) {
+        // Efecto sin estado
+    }
+
+    fn name(&self) -> &'static str {
+        "PolarityInvert"
+    }
+}
+
+/// Ganancia mínima de [`Gain`] en dB
+const GAIN_MIN_DB: f32 = -60.0;
+/// Ganancia máxima de [`Gain`] en dB
+const GAIN_MAX_DB: f32 = 24.0;
+
+/// Efecto de ganancia fija en dB
+///
+/// Multiplica cada muestra por el factor lineal correspondiente a la ganancia,
+/// limitada al rango de -60 dB a +24 dB.
+///
+/// # Ejemplo
+/// ```rust
+/// use clearcast_core::effects::{AudioEffect, Gain};
+///
+/// let mut gain = Gain::new(-6.0);
+/// assert!((gain.process_sample(1.0) - 0.501).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Gain {
+    gain_db: f32,
+    /// Factor lineal equivalente a `gain_db`
+    factor: f32,
+}
+
+impl Gain {
+    /// Crea un efecto de ganancia con el valor indicado en dB
+    pub fn new(gain_db: f32) -> Self {
+        let mut gain = Self { gain_db: 0.0, factor: 1.0 };
+        gain.set_gain_db(gain_db);
+        gain
+    }
+
+    /// Cambia la ganancia en dB
+    pub fn set_gain_db(&mut self, gain_db: f32) {
+        self.gain_db = gain_db.clamp(GAIN_MIN_DB, GAIN_MAX_DB);
+        self.factor = crate::utils::db_to_linear(self.gain_db);
+    }
+
+    /// Ganancia actual en dB
+    pub fn gain_db(&self) -> f32 {
+        self.gain_db
+    }
+}
+
+impl AudioEffect for Gain {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        sample * self.factor
+    }
+
+    fn reset(&mut self) {
+        // Efecto sin estado
+    }
+
+    fn name(&self) -> &'static str {
+        "Gain"
+    }
+
+    #[cfg(feature = "alloc")]
+    fn parameters(&self) -> alloc::vec::Vec<ParamDescriptor> {
+        alloc::vec![ParamDescriptor { name: "gain_db", min: GAIN_MIN_DB, max: GAIN_MAX_DB, value: self.gain_db }]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), alloc::string::String> {
+        check_parameter(&self.parameters(), name, value)?;
+        self.set_gain_db(value);
+        Ok(())
+    }
+}
+
+/// Iterador que aplica un efecto a cada muestra de otro iterador
+///
+/// Se crea con [`AudioEffect::process_iter`].
+pub struct EffectIter<E, I> {
+    effect: E,
+    iter: I,
+}
+
+impl<E, I> EffectIter<E, I> {
+    /// Devuelve el efecto envuelto, conservando su estado
+    pub fn into_effect(self) -> E {
+        self.effect
+    }
+}
+
+impl<E, I> Iterator for EffectIter<E, I>
+where
+    E: AudioEffect,
+    I: Iterator<Item = f32>,
+{
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        self.iter.next().map(|sample| self.effect.process_sample(sample))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_process_iter_matches_process_buffer() {
+        let input: Vec<f32> = (0..500).map(|i| (i as f32 * 0.1).sin()).collect();
+
+        let mut expected = input.clone();
+        Delay::new(10.0, 0.4, 0.5, 0.5, 1000).process_buffer(&mut expected);
+
+        let collected: Vec<f32> = Delay::new(10.0, 0.4, 0.5, 0.5, 1000)
+            .process_iter(input.into_iter())
+            .collect();
+
+        assert_eq!(collected, expected);
+    }
+
+    #[test]
+    fn test_process_iter_chains_effects() {
+        let input = vec![0.5, 1.5, -1.8, 0.3];
+
+        let mut expected = input.clone();
+        SoftLimiter::new(0.8, 0.1).process_buffer(&mut expected);
+        SoftLimiter::new(0.6, 0.1).process_buffer(&mut expected);
+
+        let chained: Vec<f32> = SoftLimiter::new(0.6, 0.1)
+            .process_iter(SoftLimiter::new(0.8, 0.1).process_iter(input.into_iter()))
+            .collect();
+
+        assert_eq!(chained, expected);
+    }
+
+    #[test]
+    fn test_polarity_invert() {
+        let input: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin() - 0.1).collect();
+        let mut effect = PolarityInvert;
+        assert_eq!(effect.name(), "PolarityInvert");
+
+        let mut output = input.clone();
+        effect.process_buffer(&mut output);
+        assert!(output.iter().zip(&input).all(|(&y, &x)| y == -x));
+
+        effect.process_buffer(&mut output);
+        assert_eq!(output, input);
+    }
+
+    #[test]
+    fn test_gain_and_limiter_parameters() {
+        let mut gain = Gain::new(-6.0);
+        assert_eq!(
+            gain.parameters(),
+            vec![ParamDescriptor { name: "gain_db", min: -60.0, max: 24.0, value: -6.0 }]
+        );
+        gain.set_parameter("gain_db", 6.0).unwrap();
+        assert!((gain.process_sample(0.25) - 0.499).abs() < 1e-3);
+
+        // Nombres desconocidos y valores fuera de rango no cambian nada
+        assert!(gain.set_parameter("volume", 0.0).is_err());
+        assert!(gain.set_parameter("gain_db", 40.0).is_err());
+        assert!(gain.set_parameter("gain_db", f32::NAN).is_err());
+        assert_eq!(gain.gain_db(), 6.0);
+
+        let mut limiter = SoftLimiter::new(0.8, 0.1);
+        let names: Vec<&str> = limiter.parameters().iter().map(|p| p.name).collect();
+        assert_eq!(names, vec!["threshold", "knee"]);
+        limiter.set_parameter("threshold", 0.5).unwrap();
+        limiter.set_parameter("knee", 0.0).unwrap();
+        assert_eq!(limiter.process_sample(0.9), 0.5);
+
+        // Los efectos sin parámetros usan la implementación por defecto
+        assert!(PolarityInvert.parameters().is_empty());
+        assert!(PolarityInvert.set_parameter("gain_db", 0.0).is_err());
+    }
 }
@@ -4,11 +4,25 @@
 //! el valor RMS (Root Mean Square) como referencia. La normalización ajusta la
 //! ganancia de la señal para que su nivel RMS coincida con un valor objetivo
 //! especificado en dBFS (decibelios relativos a la escala completa).
+//!
+//! Las funciones miden el búfer completo antes de aplicar la ganancia; para
+//! procesar un flujo bloque a bloque está [`RmsNormalizer`], que sigue el nivel
+//! con una media móvil.
 
 use std::f32::consts::SQRT_2;
 
+use super::AudioEffect;
+use crate::utils::db_to_linear;
+
+/// Nivel por debajo del cual [`RmsNormalizer`] congela la ganancia (dBFS)
+const SILENCE_FLOOR_DB: f32 = -60.0;
+/// Máxima corrección de [`RmsNormalizer`], hacia arriba o hacia abajo (dB)
+const MAX_CORRECTION_DB: f32 = 24.0;
+
 /// Normaliza un búfer de audio al nivel RMS objetivo especificado en dBFS.
 ///
+/// Las muestras no finitas (NaN, ±infinito) no cuentan para el cálculo del RMS.
+///
 /// # Argumentos
 ///
 /// * `buffer` - Búfer de audio a normalizar (modificado in-place)
@@ -25,9 +39,15 @@ pub fn normalize_rms(buffer: &mut [f32], target_dbfs: f32) {
         return;
     }
 
-    // Calcular el valor RMS actual
-    let sum_squares: f32 = buffer.iter().map(|&x| x * x).sum();
-    let rms = (sum_squares / buffer.len() as f32).sqrt();
+    // Calcular el valor RMS actual sobre las muestras finitas
+    let (sum_squares, count) = buffer
+        .iter()
+        .filter(|x| x.is_finite())
+        .fold((0.0f32, 0usize), |(sum, count), &x| (sum + x * x, count + 1));
+    if count == 0 {
+        return;
+    }
+    let rms = (sum_squares / count as f32).sqrt();
     
     // Evitar división por cero si el audio es silencio
     if rms <= f32::MIN_POSITIVE {
@@ -35,7 +55,7 @@ pub fn normalize_rms(buffer: &mut [f32], target_dbfs: f32) {
     }
     
     // Convertir el objetivo de dBFS a amplitud lineal
-    let target_linear = 10.0f32.powf(target_dbfs / 20.0);
+    let target_linear = db_to_linear(target_dbfs);
     
     // Calcular el factor de escala necesario
     let scale_factor = target_linear / rms;
@@ -46,6 +66,155 @@ pub fn normalize_rms(buffer: &mut [f32], target_dbfs: f32) {
     }
 }
 
+/// Normaliza un búfer para que su ventana más fuerte quede en el nivel RMS objetivo.
+///
+/// El RMS se mide en una ventana deslizante de `window_ms` que avanza muestra a
+/// muestra, y la ganancia se calcula con el nivel de la ventana más fuerte. Así
+/// los pasajes en silencio o muy suaves de un clip largo no rebajan el nivel de
+/// referencia, como ocurre con [`normalize_rms`], que promedia todo el búfer.
+/// Si la ventana es más larga que el búfer, se mide el búfer completo. Las
+/// muestras no finitas no cuentan para el cálculo del RMS.
+///
+/// # Argumentos
+///
+/// * `buffer` - Búfer de audio a normalizar (modificado in-place)
+/// * `target_dbfs` - Nivel objetivo en dBFS de la ventana más fuerte
+/// * `window_ms` - Duración de la ventana de medida en milisegundos (p. ej. 400.0)
+/// * `sample_rate` - Frecuencia de muestreo en Hz
+///
+/// # Ejemplo
+///
+/// ```
+/// use clearcast_core::effects::normalize_rms_windowed;
+///
+/// // Medio segundo de tono seguido de medio segundo de silencio
+/// let mut audio: Vec<f32> = (0..8000).map(|i| if i < 4000 { 0.1 * (i as f32 * 0.1).sin() } else { 0.0 }).collect();
+/// normalize_rms_windowed(&mut audio, -12.0, 100.0, 8000.0);
+/// ```
+pub fn normalize_rms_windowed(buffer: &mut [f32], target_dbfs: f32, window_ms: f32, sample_rate: f32) {
+    let window = ((window_ms * 0.001 * sample_rate).round().max(1.0) as usize).min(buffer.len());
+    if window == 0 {
+        return;
+    }
+
+    // Suma de cuadrados y número de muestras finitas de la ventana actual
+    let finite_square = |x: f32| if x.is_finite() { (x as f64 * x as f64, 1usize) } else { (0.0, 0) };
+    let (mut sum, mut count) = buffer[..window]
+        .iter()
+        .map(|&x| finite_square(x))
+        .fold((0.0f64, 0usize), |(sum, count), (sq, n)| (sum + sq, count + n));
+
+    let mut loudest = 0.0f64;
+    for i in window..=buffer.len() {
+        if count > 0 {
+            loudest = loudest.max(sum / count as f64);
+        }
+        if i == buffer.len() {
+            break;
+        }
+        let (added, added_count) = finite_square(buffer[i]);
+        let (removed, removed_count) = finite_square(buffer[i - window]);
+        sum = (sum + added - removed).max(0.0);
+        count = count + added_count - removed_count;
+    }
+
+    let rms = loudest.sqrt() as f32;
+    if rms <= f32::MIN_POSITIVE {
+        return;
+    }
+
+    let scale_factor = db_to_linear(target_dbfs) / rms;
+    for sample in buffer.iter_mut() {
+        *sample *= scale_factor;
+    }
+}
+
+/// Normalizador RMS para flujos de audio
+///
+/// Versión en tiempo real de [`normalize_rms`]: sigue el nivel RMS con una
+/// media móvil exponencial de `window_ms` y lleva la ganancia hacia la que
+/// dejaría ese nivel en el objetivo, suavizada con la misma constante de tiempo
+/// para no producir bombeo. Mientras la ventana aún no se ha llenado, la media
+/// se corrige para no partir de cero y no sobreamplificar el comienzo. En
+/// silencio (por debajo de -60 dBFS) la ganancia se congela, y la corrección
+/// nunca supera ±24 dB.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, RmsNormalizer};
+///
+/// let mut normalizer = RmsNormalizer::new(-18.0, 400.0, 44100.0);
+/// let mut block = vec![0.01f32; 512];
+/// normalizer.process_buffer(&mut block);
+/// assert!(normalizer.current_gain_db() > 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct RmsNormalizer {
+    target_dbfs: f32,
+    coeff: f32,
+    mean_square: f32,
+    /// Peso acumulado de la media móvil; tiende a 1.0 cuando la ventana se llena
+    weight: f32,
+    gain_db: f32,
+}
+
+impl RmsNormalizer {
+    /// Crea un nuevo normalizador RMS
+    ///
+    /// # Argumentos
+    /// * `target_dbfs` - Nivel RMS objetivo en dBFS (p. ej. -18.0)
+    /// * `window_ms` - Constante de tiempo de la medida y de la ganancia en milisegundos
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(target_dbfs: f32, window_ms: f32, sample_rate: f32) -> Self {
+        let window_samples = (window_ms.max(1.0) * 0.001 * sample_rate).max(1.0);
+        Self {
+            target_dbfs,
+            coeff: (-1.0 / window_samples).exp(),
+            mean_square: 0.0,
+            weight: 0.0,
+            gain_db: 0.0,
+        }
+    }
+
+    /// Devuelve la ganancia aplicada actualmente en dB
+    pub fn current_gain_db(&self) -> f32 {
+        self.gain_db
+    }
+}
+
+impl AudioEffect for RmsNormalizer {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        if !sample.is_finite() {
+            return 0.0;
+        }
+
+        self.mean_square = self.coeff * self.mean_square + (1.0 - self.coeff) * sample * sample;
+        self.weight = self.coeff * self.weight + (1.0 - self.coeff);
+        let level_db = 10.0 * (self.mean_square / self.weight).max(1e-12).log10();
+
+        if level_db > SILENCE_FLOOR_DB {
+            let desired = (self.target_dbfs - level_db).clamp(-MAX_CORRECTION_DB, MAX_CORRECTION_DB);
+            self.gain_db = (1.0 - self.coeff) * desired + self.coeff * self.gain_db;
+        }
+
+        sample * db_to_linear(self.gain_db)
+    }
+
+    fn reset(&mut self) {
+        self.mean_square = 0.0;
+        self.weight = 0.0;
+        self.gain_db = 0.0;
+    }
+
+    fn name(&self) -> &'static str {
+        "RmsNormalizer"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,6 +249,83 @@ mod tests {
         assert_relative_eq!(signal[0], signal[2], epsilon = 1e-6);
     }
     
+    #[test]
+    fn test_normalize_rms_windowed_targets_loud_section() {
+        let sample_rate = 8000.0;
+        let rms = |s: &[f32]| (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).sqrt();
+
+        // Mitad con un tono y mitad en silencio
+        let clip: Vec<f32> = (0..16000)
+            .map(|i| if i < 8000 { 0.3 * (i as f32 * 0.2).sin() } else { 0.0 })
+            .collect();
+        let target_dbfs = -12.0;
+        let target_linear = db_to_linear(target_dbfs);
+
+        // Con el RMS de todo el búfer el silencio baja la media y la parte
+        // fuerte acaba 3 dB por encima del objetivo
+        let mut whole = clip.clone();
+        normalize_rms(&mut whole, target_dbfs);
+        assert_relative_eq!(rms(&whole[..8000]), target_linear * SQRT_2, epsilon = 1e-3);
+
+        // Con ventanas la parte fuerte queda en el objetivo
+        let mut windowed = clip.clone();
+        normalize_rms_windowed(&mut windowed, target_dbfs, 400.0, sample_rate);
+        assert_relative_eq!(rms(&windowed[..8000]), target_linear, epsilon = 1e-3);
+        assert!(windowed[8000..].iter().all(|&x| x == 0.0));
+
+        // Una ventana más larga que el clip equivale a la versión de todo el búfer
+        let mut long_window = clip.clone();
+        normalize_rms_windowed(&mut long_window, target_dbfs, 10_000.0, sample_rate);
+        assert_relative_eq!(long_window.as_slice(), whole.as_slice(), epsilon = 1e-5);
+
+        let mut silent = vec![0.0; 100];
+        normalize_rms_windowed(&mut silent, target_dbfs, 400.0, sample_rate);
+        assert_eq!(silent, vec![0.0; 100]);
+        normalize_rms_windowed(&mut [], target_dbfs, 400.0, sample_rate);
+    }
+
+    #[test]
+    fn test_rms_normalizer_converges_in_blocks() {
+        let sample_rate = 44100.0;
+        let rms_db = |s: &[f32]| 10.0 * (s.iter().map(|x| x * x).sum::<f32>() / s.len() as f32).log10();
+
+        // Tres segundos a -30 dBFS y tres a -6 dBFS
+        let signal: Vec<f32> = (0..6 * 44100)
+            .map(|i| {
+                let amplitude = if i < 3 * 44100 { 0.0447 } else { 0.707 };
+                amplitude * (2.0 * std::f32::consts::PI * 200.0 * i as f32 / sample_rate).sin()
+            })
+            .collect();
+
+        let mut normalizer = RmsNormalizer::new(-18.0, 400.0, sample_rate);
+        let mut output = signal.clone();
+        let mut gains = Vec::new();
+        for block in output.chunks_mut(512) {
+            normalizer.process_buffer(block);
+            gains.push(normalizer.current_gain_db());
+        }
+
+        // Al final de cada tramo el nivel de salida ha alcanzado el objetivo
+        let tail = 44100 / 2;
+        for end in [3 * 44100, 6 * 44100] {
+            let level = rms_db(&output[end - tail..end]);
+            assert!((level + 18.0).abs() < 0.5, "Level {:.2} dBFS before sample {}", level, end);
+        }
+
+        // La ganancia cambia de forma gradual entre bloques, incluso en el salto de nivel
+        let max_jump = gains.windows(2).map(|g| (g[1] - g[0]).abs()).fold(0.0f32, f32::max);
+        assert!(max_jump < 1.0, "Gain jumped {:.2} dB between blocks", max_jump);
+
+        // Procesar por bloques da lo mismo que procesar todo de una vez
+        let mut whole = signal.clone();
+        RmsNormalizer::new(-18.0, 400.0, sample_rate).process_buffer(&mut whole);
+        assert_eq!(whole, output);
+
+        // Tras reset vuelve a empezar sin ganancia
+        normalizer.reset();
+        assert_eq!(normalizer.current_gain_db(), 0.0);
+    }
+
     #[test]
     fn test_normalize_rms_already_at_target() {
         // Crear una señal con RMS de -12 dBFS
@@ -0,0 +1,206 @@
+//! Implementación de un efecto phaser
+//!
+//! El phaser suma la señal con una copia que atraviesa una cascada de filtros
+//! pasa-todo de primer orden. Allí donde la copia queda desfasada 180° ambas se
+//! cancelan y aparece una muesca en el espectro; un LFO desplaza la frecuencia
+//! de los filtros para que las muescas barran el espectro.
+
+use super::{AudioEffect, Lfo};
+use crate::filters::AllpassFilter;
+
+/// Extremos del barrido de los filtros pasa-todo en Hz
+const MIN_SWEEP_HZ: f32 = 200.0;
+const MAX_SWEEP_HZ: f32 = 4000.0;
+
+/// Realimentación máxima en valor absoluto, para mantener el lazo estable
+const MAX_FEEDBACK: f32 = 0.95;
+
+/// Phaser con barrido senoidal
+///
+/// Cada pareja de etapas produce una muesca, así que `stages` = 4 da dos
+/// muescas. La realimentación hace más pronunciados los picos entre muescas.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, Phaser};
+///
+/// let mut phaser = Phaser::new(0.5, 0.8, 4, 0.5, 0.5, 44100.0);
+/// let mut buffer: Vec<f32> = (0..4410).map(|i| (i as f32 * 0.05).sin()).collect();
+/// phaser.process_buffer(&mut buffer);
+/// assert!(buffer.iter().all(|x| x.is_finite()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Phaser {
+    depth: f32,
+    feedback: f32,
+    mix: f32,
+    sample_rate: f32,
+    stages: Vec<AllpassFilter>,
+    lfo: Lfo,
+    /// Última salida de la cascada, que se realimenta a la entrada
+    last_wet: f32,
+}
+
+impl Phaser {
+    /// Crea un nuevo phaser
+    ///
+    /// # Argumentos
+    /// * `rate_hz` - Frecuencia del barrido en Hz (0.0 = muescas fijas)
+    /// * `depth` - Amplitud del barrido (0.0 = fijo en el centro, 1.0 = de 200 Hz a 4 kHz)
+    /// * `stages` - Número de filtros pasa-todo en cascada (al menos 1)
+    /// * `feedback` - Realimentación de la cascada (-0.95 a 0.95)
+    /// * `mix` - Proporción de señal procesada (0.0 = seca, 1.0 = muescas completas)
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(
+        rate_hz: f32,
+        depth: f32,
+        stages: usize,
+        feedback: f32,
+        mix: f32,
+        sample_rate: f32,
+    ) -> Self {
+        Self {
+            depth: depth.clamp(0.0, 1.0),
+            feedback: feedback.clamp(-MAX_FEEDBACK, MAX_FEEDBACK),
+            mix: mix.clamp(0.0, 1.0),
+            sample_rate,
+            stages: vec![AllpassFilter::new(1, 0.0); stages.max(1)],
+            lfo: Lfo::new(rate_hz, sample_rate),
+            last_wet: 0.0,
+        }
+    }
+
+    /// Coeficiente de un pasa-todo de primer orden cuyo desfase es de 90° en `freq_hz`
+    fn allpass_coeff(&self, freq_hz: f32) -> f32 {
+        let t = (std::f32::consts::PI * freq_hz / self.sample_rate).tan();
+        (1.0 - t) / (1.0 + t)
+    }
+}
+
+impl AudioEffect for Phaser {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        // Barrido logarítmico alrededor de la media geométrica de los extremos
+        let position = 0.5 + 0.5 * self.depth * self.lfo.next();
+        let freq = MIN_SWEEP_HZ * (MAX_SWEEP_HZ / MIN_SWEEP_HZ).powf(position);
+        let coeff = self.allpass_coeff(freq.min(0.49 * self.sample_rate));
+
+        let input = sample + self.feedback * self.last_wet;
+        let mut wet = input;
+        for stage in self.stages.iter_mut() {
+            stage.set_feedback(coeff);
+            wet = stage.process(wet);
+        }
+        self.last_wet = wet;
+
+        // La suma se hace con la entrada realimentada para que las muescas
+        // sigan siendo completas con cualquier realimentación
+        let phased = 0.5 * (input + wet);
+        (1.0 - self.mix) * sample + self.mix * phased
+    }
+
+    fn reset(&mut self) {
+        self.stages.iter_mut().for_each(AllpassFilter::reset);
+        self.lfo.reset();
+        self.last_wet = 0.0;
+    }
+
+    fn name(&self) -> &'static str {
+        "Phaser"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::window::hann;
+    use rand::{Rng, SeedableRng};
+    use realfft::RealFftPlanner;
+
+    /// Respuesta en magnitud estimada a partir de la entrada y la salida,
+    /// promediando la potencia de tramas con ventana de Hann
+    fn magnitude_response(input: &[f32], output: &[f32], frame: usize) -> Vec<f32> {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(frame);
+        let window = hann(frame);
+
+        let power = |signal: &[f32]| {
+            let mut total = vec![0.0f32; frame / 2 + 1];
+            for chunk in signal.chunks_exact(frame) {
+                let mut windowed: Vec<f32> = chunk.iter().zip(&window).map(|(x, w)| x * w).collect();
+                let mut spectrum = fft.make_output_vec();
+                fft.process(&mut windowed, &mut spectrum).unwrap();
+                for (t, bin) in total.iter_mut().zip(&spectrum) {
+                    *t += bin.norm_sqr();
+                }
+            }
+            total
+        };
+
+        power(output)
+            .iter()
+            .zip(power(input))
+            .map(|(y, x)| (y / x).sqrt())
+            .collect()
+    }
+
+    #[test]
+    fn test_static_phaser_notches_white_noise() {
+        let sample_rate = 44100.0;
+        let frame = 2048;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(311);
+        let noise: Vec<f32> = (0..frame * 64).map(|_| rng.gen_range(-0.5..0.5)).collect();
+
+        for stages in [2, 4, 6] {
+            let feedback = 0.6;
+            let mut phaser = Phaser::new(0.0, 0.0, stages, feedback, 1.0, sample_rate);
+            let mut output = noise.clone();
+            phaser.process_buffer(&mut output);
+
+            // La realimentación no puede llevar la ganancia más allá de 1 / (1 - feedback)
+            let bound = 0.5 / (1.0 - feedback);
+            assert!(output.iter().all(|x| x.abs() <= bound), "Output not bounded");
+
+            // Se ignoran los bins por debajo de 40 Hz, de resolución muy pobre
+            let response = magnitude_response(&noise, &output, frame);
+            let response = &response[2..];
+
+            // Cada pareja de etapas produce una muesca de al menos 20 dB
+            let mut notches = 0;
+            let mut in_notch = false;
+            for &gain in response {
+                if gain < 0.1 && !in_notch {
+                    notches += 1;
+                }
+                in_notch = gain < 0.1;
+            }
+            assert_eq!(notches, stages / 2, "{} stages", stages);
+
+            // Entre muescas la realimentación realza la señal
+            let peak = response.iter().fold(0.0f32, |m, &g| m.max(g));
+            assert!(peak > 1.0, "Peak gain {} with {} stages", peak, stages);
+        }
+    }
+
+    #[test]
+    fn test_phaser_reset_and_dry_mix() {
+        let input: Vec<f32> = (0..1000).map(|i| (i as f32 * 0.07).sin()).collect();
+
+        let mut phaser = Phaser::new(1.0, 1.0, 6, 0.7, 0.5, 8000.0);
+        let mut first = input.clone();
+        phaser.process_buffer(&mut first);
+
+        phaser.reset();
+        let mut second = input.clone();
+        phaser.process_buffer(&mut second);
+        assert_eq!(first, second);
+
+        let mut dry = Phaser::new(1.0, 1.0, 6, 0.7, 0.0, 8000.0);
+        let mut output = input.clone();
+        dry.process_buffer(&mut output);
+        assert_eq!(output, input);
+    }
+}
@@ -0,0 +1,161 @@
+//! Implementación de una etapa de saturación armónica suave
+//!
+//! La saturación pasa la señal por una curva tanh que redondea los picos de
+//! forma progresiva. Al ser una curva simétrica añade sobre todo armónicos
+//! impares, lo que da "calidez" a la voz sin el recorte duro de un limitador.
+
+use super::AudioEffect;
+
+/// Por debajo de este drive la curva es prácticamente lineal y se omite
+const MIN_DRIVE: f32 = 1e-3;
+
+/// Saturación suave basada en tangente hiperbólica
+///
+/// Cada muestra se transforma con `tanh(drive * x) / tanh(drive)`, de modo que
+/// una entrada de amplitud 1.0 sigue valiendo 1.0 sea cual sea el drive, y se
+/// mezcla con la señal original según `mix`. La entrada se recorta antes a
+/// ±1.0, porque con drive bajo la curva apenas comprime y dejaría pasar
+/// cualquier pico; así la salida nunca supera ±1.0.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, Saturation};
+///
+/// let mut saturation = Saturation::new(3.0, 1.0);
+/// assert!((saturation.process_sample(1.0) - 1.0).abs() < 1e-6);
+/// assert!(saturation.process_sample(0.5) > 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Saturation {
+    drive: f32,
+    mix: f32,
+    /// `1 / tanh(drive)`, precalculado
+    normalization: f32,
+}
+
+impl Saturation {
+    /// Crea una nueva etapa de saturación
+    ///
+    /// # Argumentos
+    /// * `drive` - Intensidad de la saturación (cerca de 0 = lineal, valores
+    ///   mayores = más armónicos)
+    /// * `mix` - Proporción de señal saturada (0.0 = seca, 1.0 = solo saturada)
+    pub fn new(drive: f32, mix: f32) -> Self {
+        let drive = drive.max(0.0);
+
+        Self {
+            drive,
+            mix: mix.clamp(0.0, 1.0),
+            normalization: if drive > MIN_DRIVE { 1.0 / drive.tanh() } else { 1.0 },
+        }
+    }
+
+    /// Devuelve la intensidad de la saturación
+    pub fn drive(&self) -> f32 {
+        self.drive
+    }
+
+    /// Devuelve la proporción de señal saturada
+    pub fn mix(&self) -> f32 {
+        self.mix
+    }
+}
+
+impl AudioEffect for Saturation {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let sample = sample.clamp(-1.0, 1.0);
+        if self.drive <= MIN_DRIVE {
+            return sample;
+        }
+
+        let saturated = (self.drive * sample).tanh() * self.normalization;
+        sample + self.mix * (saturated - sample)
+    }
+
+    fn reset(&mut self) {
+        // Efecto sin estado
+    }
+
+    fn name(&self) -> &'static str {
+        "Saturation"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use realfft::RealFftPlanner;
+
+    /// Energía de los armónicos impares 3, 5 y 7 relativa a la fundamental
+    fn odd_harmonic_ratio(signal: &[f32], fundamental_bin: usize) -> f32 {
+        let mut planner = RealFftPlanner::<f32>::new();
+        let fft = planner.plan_fft_forward(signal.len());
+        let mut input = signal.to_vec();
+        let mut spectrum = fft.make_output_vec();
+        fft.process(&mut input, &mut spectrum).unwrap();
+
+        let power = |harmonic: usize| spectrum[harmonic * fundamental_bin].norm_sqr();
+        (power(3) + power(5) + power(7)) / power(1)
+    }
+
+    #[test]
+    fn test_saturation_adds_odd_harmonics() {
+        // 1 kHz a 48 kHz con 4800 muestras: la fundamental cae en el bin 100
+        let sample_rate = 48000.0;
+        let input: Vec<f32> = (0..4800)
+            .map(|i| 0.9 * (2.0 * std::f32::consts::PI * 1000.0 * i as f32 / sample_rate).sin())
+            .collect();
+
+        let mut previous_ratio = odd_harmonic_ratio(&input, 100);
+        for drive in [1.0, 3.0, 8.0] {
+            let mut saturation = Saturation::new(drive, 1.0);
+            let mut output = input.clone();
+            saturation.process_buffer(&mut output);
+
+            assert!(output.iter().all(|x| (-1.0..=1.0).contains(x)));
+            let ratio = odd_harmonic_ratio(&output, 100);
+            assert!(
+                ratio > previous_ratio,
+                "Drive {} should add odd harmonics ({} <= {})",
+                drive,
+                ratio,
+                previous_ratio
+            );
+            previous_ratio = ratio;
+        }
+    }
+
+    #[test]
+    fn test_saturation_mix_and_zero_drive() {
+        let mut dry = Saturation::new(5.0, 0.0);
+        assert_eq!(dry.process_sample(0.3), 0.3);
+
+        let mut linear = Saturation::new(0.0, 1.0);
+        assert_eq!(linear.process_sample(0.3), 0.3);
+
+        // Con drive pequeño la curva normalizada se acerca a la identidad
+        let mut gentle = Saturation::new(0.01, 1.0);
+        assert!((gentle.process_sample(0.3) - 0.3).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_saturation_output_stays_within_full_scale() {
+        // Con drive bajo la curva es casi lineal: los picos por encima de 1.0 se recortan
+        for drive in [0.0, 1e-4, 0.01, 0.5, 3.0] {
+            for mix in [0.0, 0.5, 1.0] {
+                let mut saturation = Saturation::new(drive, mix);
+                for input in [-10.0, -1.5, -1.0, 1.0, 1.2, 4.0] {
+                    let output = saturation.process_sample(input);
+                    assert!(
+                        (-1.0..=1.0).contains(&output),
+                        "drive {} mix {}: {} -> {}",
+                        drive,
+                        mix,
+                        input,
+                        output
+                    );
+                }
+            }
+        }
+    }
+}
@@ -6,7 +6,43 @@
 //! lo que resulta en una distorsión menos perceptible.
 
 use crate::effects::AudioEffect;
-use std::f32::consts::{E, PI};
+#[cfg(feature = "alloc")]
+use crate::effects::{check_parameter, ParamDescriptor};
+
+/// Forma de la curva de la rodilla por encima del umbral
+///
+/// Todas las curvas salen del umbral con pendiente 1 (sin esquina) y se
+/// acercan al techo `threshold + knee` sin sobrepasarlo nunca. Cambia el
+/// carácter armónico de la saturación.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KneeShape {
+    /// Tangente hiperbólica: se acerca al techo de forma asintótica
+    #[default]
+    Tanh,
+    /// Parábola que alcanza el techo cuando la entrada supera el umbral en `2 * knee`
+    Quadratic,
+    /// Cúbica que alcanza el techo cuando la entrada supera el umbral en `3 * knee`,
+    /// con la transición más suave de las tres
+    Cubic,
+}
+
+impl KneeShape {
+    /// Salida por encima del umbral para un exceso `over` y un margen `headroom` hasta el techo
+    fn curve(self, over: f32, headroom: f32) -> f32 {
+        match self {
+            Self::Tanh => headroom * libm::tanhf(over / headroom),
+            Self::Quadratic => {
+                let u = (over / (2.0 * headroom)).min(1.0);
+                headroom * u * (2.0 - u)
+            }
+            Self::Cubic => {
+                let u = (over / (3.0 * headroom)).min(1.0);
+                let rest = 1.0 - u;
+                headroom * (1.0 - rest * rest * rest)
+            }
+        }
+    }
+}
 
 /// Un limitador suave que aplica una función de transferencia basada en tanh
 ///
@@ -19,6 +55,8 @@ pub struct SoftLimiter {
     threshold: f32,
     /// Factor de suavizado (controla la transición a la región de limitación)
     knee: f32,
+    /// Forma de la curva de la rodilla
+    knee_shape: KneeShape,
     /// Si es true, el limitador está activado
     is_active: bool,
 }
@@ -28,7 +66,8 @@ impl SoftLimiter {
     ///
     /// # Argumentos
     /// * `threshold` - Nivel de umbral (0.0 a 1.0) donde comienza la limitación
-    /// * `knee` - Ancho de la rodilla (0.0 a 1.0) que controla la suavidad de la transición
+    /// * `knee` - Ancho de la rodilla (0.0 a 1.0) que controla la suavidad de la transición.
+    ///   La salida nunca supera `threshold + knee`, ni 1.0
     ///
     /// # Ejemplo
     /// ```
@@ -40,6 +79,7 @@ impl SoftLimiter {
         Self {
             threshold: threshold.clamp(0.01, 1.0),
             knee: knee.clamp(0.0, 1.0),
+            knee_shape: KneeShape::default(),
             is_active: true,
         }
     }
@@ -54,6 +94,11 @@ impl SoftLimiter {
         self.knee = knee.clamp(0.0, 1.0);
     }
 
+    /// Establece la forma de la curva de la rodilla
+    pub fn set_knee_shape(&mut self, knee_shape: KneeShape) {
+        self.knee_shape = knee_shape;
+    }
+
     /// Habilita o deshabilita el limitador
     pub fn set_active(&mut self, active: bool) {
         self.is_active = active;
@@ -65,29 +110,23 @@ impl SoftLimiter {
             return sample;
         }
 
-        // Aplicar la función de transferencia basada en tanh
-        let sign = sample.signum();
         let abs_sample = sample.abs();
         
         // Si la muestra está por debajo del umbral, devolver sin cambios
         if abs_sample <= self.threshold {
             return sample;
         }
-        
-        // Calcular la cantidad que excede el umbral
+
+        // Margen entre el umbral y el techo; sin margen es un recorte duro
+        let headroom = (self.threshold + self.knee).min(1.0) - self.threshold;
+        if headroom <= 0.0 {
+            return sample.signum() * self.threshold;
+        }
+
+        // La curva parte del umbral con pendiente 1 y se aplana hacia el techo,
+        // así que la salida es monótona y nunca lo supera
         let over = abs_sample - self.threshold;
-        
-        // Aplicar una función de transferencia suave basada en tanh
-        // La función es aproximadamente lineal cerca de cero y se aplana suavemente
-        let soft_limit = self.threshold + (self.knee * (over / self.knee).tanh());
-        
-        // Asegurarse de que no exceda 1.0
-        let limited = sign * soft_limit.min(1.0);
-        
-        // Mezclar entre la señal original y la limitada para una transición más suave
-        // Usar una mezcla basada en cuánto excede el umbral
-        let mix = ((abs_sample - self.threshold) / (1.0 - self.threshold)).min(1.0);
-        limited * mix + sample * (1.0 - mix)
+        sample.signum() * (self.threshold + self.knee_shape.curve(over, headroom)).min(1.0)
     }
 }
 
@@ -113,23 +152,49 @@ impl AudioEffect for SoftLimiter {
     fn name(&self) -> &'static str {
         "SoftLimiter"
     }
+
+    #[cfg(feature = "alloc")]
+    fn parameters(&self) -> alloc::vec::Vec<ParamDescriptor> {
+        alloc::vec![
+            ParamDescriptor { name: "threshold", min: 0.01, max: 1.0, value: self.threshold },
+            ParamDescriptor { name: "knee", min: 0.0, max: 1.0, value: self.knee },
+        ]
+    }
+
+    #[cfg(feature = "alloc")]
+    fn set_parameter(&mut self, name: &str, value: f32) -> Result<(), alloc::string::String> {
+        check_parameter(&self.parameters(), name, value)?;
+        match name {
+            "threshold" => self.set_threshold(value),
+            "knee" => self.set_knee(value),
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
 }
 
 /// Función de conveniencia para aplicar un limitador suave a un slice de audio
 ///
 /// Esta función es útil para procesar audio sin necesidad de crear una instancia del limitador.
 ///
+/// No necesita `std` ni asignador, así que también está disponible en el
+/// núcleo `no_std`.
+///
 /// # Argumentos
 /// * `input` - Slice de muestras de audio de entrada
+/// * `output` - Slice donde se escriben las muestras limitadas (al menos tan largo como `input`)
 /// * `threshold` - Nivel de umbral (0.0 a 1.0) donde comienza la limitación
 /// * `knee` - Ancho de la rodilla (0.0 a 1.0) que controla la suavidad de la transición
 ///
 /// # Ejemplo
 /// ```
-/// use clearcast_core::effects::soft_limiter::soft_limit_buffer;
+/// use clearcast_core::effects::soft_limit_buffer;
 ///
-/// let mut audio = vec![0.5, 1.5, -1.8, 0.3];
-/// soft_limit_buffer(&audio, &mut audio, 0.8, 0.05);
+/// let audio = [0.5, 1.5, -1.8, 0.3];
+/// let mut limited = [0.0; 4];
+/// soft_limit_buffer(&audio, &mut limited, 0.8, 0.05);
+/// assert_eq!(limited[0], 0.5);
+/// assert!(limited[1] < 1.5 && limited[2] > -1.8);
 /// ```
 pub fn soft_limit_buffer(input: &[f32], output: &mut [f32], threshold: f32, knee: f32) {
     let mut limiter = SoftLimiter::new(threshold, knee);
@@ -184,6 +249,28 @@ mod tests {
         assert_eq!(output[3], 0.3);  // Por debajo del umbral
     }
 
+    #[test]
+    fn test_knee_shapes_are_monotonic_and_bounded() {
+        let shapes = [KneeShape::Tanh, KneeShape::Quadratic, KneeShape::Cubic];
+        for shape in shapes {
+            for (threshold, knee) in [(0.8, 0.1), (0.5, 0.3), (0.95, 0.5), (0.7, 0.0), (1.0, 0.2)] {
+                let mut limiter = SoftLimiter::new(threshold, knee);
+                limiter.set_knee_shape(shape);
+                let ceiling = (threshold + knee).min(1.0);
+
+                let mut previous = 0.0;
+                for i in 0..=2000 {
+                    let x = i as f32 * 0.001;
+                    let y = limiter.apply_limiter(x);
+                    assert!(y >= previous, "{:?} ({}, {}): not monotonic at {}", shape, threshold, knee, x);
+                    assert!(y <= ceiling && y <= 1.0, "{:?} ({}, {}): {} -> {}", shape, threshold, knee, x, y);
+                    assert_eq!(limiter.apply_limiter(-x), -y);
+                    previous = y;
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_soft_limit_buffer_function() {
         let input = [0.5, 1.5, -1.8, 0.3];
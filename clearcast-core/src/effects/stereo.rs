@@ -0,0 +1,153 @@
+//! Herramientas estéreo basadas en la codificación mid/side
+//!
+//! Una señal estéreo puede representarse como la parte común a ambos canales
+//! (mid) y la diferencia entre ellos (side). Escalar la componente side cambia
+//! la anchura de la imagen estéreo: eliminarla produce mono y aumentarla la
+//! ensancha, sin alterar lo que está en el centro.
+
+use super::AudioEffect;
+
+/// Convierte un par de canales izquierdo/derecho a mid/side
+///
+/// `mid = (L + R) / 2` y `side = (L - R) / 2`. Si los canales tienen distinta
+/// longitud se usa la del más corto.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{from_mid_side, to_mid_side};
+///
+/// let (mid, side) = to_mid_side(&[1.0, 0.5], &[0.0, 0.5]);
+/// assert_eq!(mid, vec![0.5, 0.5]);
+/// assert_eq!(side, vec![0.5, 0.0]);
+///
+/// let (left, right) = from_mid_side(&mid, &side);
+/// assert_eq!(left, vec![1.0, 0.5]);
+/// assert_eq!(right, vec![0.0, 0.5]);
+/// ```
+pub fn to_mid_side(left: &[f32], right: &[f32]) -> (Vec<f32>, Vec<f32>) {
+    left.iter()
+        .zip(right)
+        .map(|(&l, &r)| ((l + r) * 0.5, (l - r) * 0.5))
+        .unzip()
+}
+
+/// Convierte un par mid/side de vuelta a canales izquierdo/derecho
+///
+/// Es la inversa de [`to_mid_side`]: `L = mid + side` y `R = mid - side`.
+pub fn from_mid_side(mid: &[f32], side: &[f32]) -> (Vec<f32>, Vec<f32>) {
+    mid.iter()
+        .zip(side)
+        .map(|(&m, &s)| (m + s, m - s))
+        .unzip()
+}
+
+/// Ensanchador estéreo que escala la componente side de la señal
+///
+/// Como [`AudioEffect`] solo actúa sobre pares de canales: una señal mono no
+/// tiene componente side, así que `process_sample` la deja pasar sin cambios.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::StereoWidener;
+///
+/// let mut widener = StereoWidener::new(0.0);
+/// let mut left = vec![1.0, 0.2];
+/// let mut right = vec![0.0, 0.4];
+/// widener.process_stereo(&mut left, &mut right);
+/// assert_eq!(left, right);
+/// ```
+#[derive(Debug, Clone)]
+pub struct StereoWidener {
+    width: f32,
+}
+
+impl StereoWidener {
+    /// Crea un nuevo ensanchador estéreo
+    ///
+    /// # Argumentos
+    /// * `width` - Factor aplicado a la componente side (0.0 = mono,
+    ///   1.0 = sin cambios, mayor que 1.0 = más ancho)
+    pub fn new(width: f32) -> Self {
+        Self { width: width.max(0.0) }
+    }
+
+    /// Cambia la anchura estéreo
+    pub fn set_width(&mut self, width: f32) {
+        self.width = width.max(0.0);
+    }
+
+    /// Devuelve la anchura estéreo actual
+    pub fn width(&self) -> f32 {
+        self.width
+    }
+
+    /// Procesa un par de canales en su lugar
+    ///
+    /// Si los canales tienen distinta longitud, las muestras sobrantes del más
+    /// largo no se modifican.
+    pub fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            let mid = (*l + *r) * 0.5;
+            let side = (*l - *r) * 0.5 * self.width;
+            *l = mid + side;
+            *r = mid - side;
+        }
+    }
+}
+
+impl AudioEffect for StereoWidener {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        sample
+    }
+
+    fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        StereoWidener::process_stereo(self, left, right);
+    }
+
+    fn reset(&mut self) {
+        // Efecto sin estado
+    }
+
+    fn name(&self) -> &'static str {
+        "StereoWidener"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_channels() -> (Vec<f32>, Vec<f32>) {
+        let left = (0..256).map(|i| (i as f32 * 0.1).sin()).collect();
+        let right = (0..256).map(|i| 0.5 * (i as f32 * 0.37).cos()).collect();
+        (left, right)
+    }
+
+    #[test]
+    fn test_stereo_widener_mono_and_identity() {
+        let (input_left, input_right) = test_channels();
+
+        // Anchura 0: ambos canales quedan iguales (mono)
+        let (mut left, mut right) = (input_left.clone(), input_right.clone());
+        StereoWidener::new(0.0).process_stereo(&mut left, &mut right);
+        assert_eq!(left, right);
+
+        // Anchura 1: la conversión de ida y vuelta conserva la entrada
+        let (mut left, mut right) = (input_left.clone(), input_right.clone());
+        StereoWidener::new(1.0).process_stereo(&mut left, &mut right);
+        for (a, b) in left.iter().zip(&input_left).chain(right.iter().zip(&input_right)) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn test_mid_side_round_trip() {
+        let (left, right) = test_channels();
+        let (mid, side) = to_mid_side(&left, &right);
+        let (round_left, round_right) = from_mid_side(&mid, &side);
+
+        for (a, b) in round_left.iter().zip(&left).chain(round_right.iter().zip(&right)) {
+            assert!((a - b).abs() < 1e-6);
+        }
+    }
+}
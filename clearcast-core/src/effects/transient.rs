@@ -0,0 +1,142 @@
+//! Implementación de un modelador de transitorios (transient shaper)
+//!
+//! A diferencia de un compresor, no depende del nivel absoluto de la señal sino
+//! de cómo cambia: compara un seguidor de envolvente rápido con uno lento. Al
+//! comienzo de un golpe el rápido sube antes que el lento (ataque) y durante la
+//! cola el lento se queda por encima del rápido (sostenido), de modo que cada
+//! parte recibe su propia ganancia.
+
+use super::AudioEffect;
+use crate::filters::{EnvelopeFollower, EnvelopeMode};
+use crate::utils::{db_to_linear, linear_to_db};
+
+/// Tiempos del seguidor rápido en milisegundos
+const FAST_ATTACK_MS: f32 = 1.0;
+const FAST_RELEASE_MS: f32 = 50.0;
+/// Tiempos del seguidor lento en milisegundos
+const SLOW_ATTACK_MS: f32 = 25.0;
+const SLOW_RELEASE_MS: f32 = 250.0;
+/// Diferencia entre envolventes (dB) a partir de la cual se aplica la ganancia completa
+const FULL_SCALE_DIFF_DB: f32 = 6.0;
+
+/// Modelador de transitorios con ganancias independientes para ataque y sostenido
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, TransientShaper};
+///
+/// // Más pegada y menos cola
+/// let mut shaper = TransientShaper::new(6.0, -3.0, 44100.0);
+/// let output = shaper.process_sample(0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransientShaper {
+    attack_gain: f32,
+    sustain_gain: f32,
+    fast: EnvelopeFollower,
+    slow: EnvelopeFollower,
+}
+
+impl TransientShaper {
+    /// Crea un nuevo modelador de transitorios
+    ///
+    /// # Argumentos
+    /// * `attack_gain` - Ganancia en dB aplicada a los ataques (positiva = más pegada)
+    /// * `sustain_gain` - Ganancia en dB aplicada a las colas (negativa = sonido más seco)
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(attack_gain: f32, sustain_gain: f32, sample_rate: f32) -> Self {
+        Self {
+            attack_gain,
+            sustain_gain,
+            fast: EnvelopeFollower::new(FAST_ATTACK_MS, FAST_RELEASE_MS, sample_rate, EnvelopeMode::Peak),
+            slow: EnvelopeFollower::new(SLOW_ATTACK_MS, SLOW_RELEASE_MS, sample_rate, EnvelopeMode::Peak),
+        }
+    }
+}
+
+impl AudioEffect for TransientShaper {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let fast_db = linear_to_db(self.fast.process(sample));
+        let slow_db = linear_to_db(self.slow.process(sample));
+
+        // Positiva durante los ataques, negativa durante las colas
+        let diff = (fast_db - slow_db) / FULL_SCALE_DIFF_DB;
+        let gain_db = self.attack_gain * diff.clamp(0.0, 1.0) + self.sustain_gain * (-diff).clamp(0.0, 1.0);
+
+        sample * db_to_linear(gain_db)
+    }
+
+    fn reset(&mut self) {
+        self.fast.reset();
+        self.slow.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "TransientShaper"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    /// Silencio antes del golpe en segundos
+    const HIT_ONSET: f32 = 0.01;
+
+    /// Golpe de tambor: tono de 150 Hz con caída exponencial, tras 10 ms de silencio
+    fn drum_hit(sample_rate: f32) -> Vec<f32> {
+        (0..(0.5 * sample_rate) as usize)
+            .map(|i| {
+                let t = i as f32 / sample_rate - HIT_ONSET;
+                if t < 0.0 {
+                    0.0
+                } else {
+                    0.8 * (-t / 0.1).exp() * (2.0 * PI * 150.0 * t).sin()
+                }
+            })
+            .collect()
+    }
+
+    /// Relación entre el pico de los primeros 15 ms del golpe y el pico de la cola
+    fn punch(signal: &[f32], sample_rate: f32) -> f32 {
+        let peak = |range: std::ops::Range<f32>| {
+            signal[(range.start * sample_rate) as usize..(range.end * sample_rate) as usize]
+                .iter()
+                .fold(0.0f32, |max, &x| max.max(x.abs()))
+        };
+        peak(HIT_ONSET..HIT_ONSET + 0.015) / peak(0.2..0.3)
+    }
+
+    #[test]
+    fn test_attack_gain_emphasizes_initial_peak() {
+        let sample_rate = 44100.0;
+        let input = drum_hit(sample_rate);
+
+        let mut shaper = TransientShaper::new(6.0, 0.0, sample_rate);
+        let mut output = input.clone();
+        shaper.process_buffer(&mut output);
+        assert!(
+            punch(&output, sample_rate) > 1.3 * punch(&input, sample_rate),
+            "Punch {} vs {}",
+            punch(&output, sample_rate),
+            punch(&input, sample_rate)
+        );
+
+        // Recortar el sostenido también resalta el ataque, bajando la cola
+        let mut dry = TransientShaper::new(0.0, -6.0, sample_rate);
+        let mut output = input.clone();
+        dry.process_buffer(&mut output);
+        assert!(punch(&output, sample_rate) > 1.3 * punch(&input, sample_rate));
+
+        // Con ganancias nulas la señal no cambia
+        let mut neutral = TransientShaper::new(0.0, 0.0, sample_rate);
+        let mut output = input.clone();
+        neutral.process_buffer(&mut output);
+        assert_eq!(output, input);
+    }
+}
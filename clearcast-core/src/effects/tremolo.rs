@@ -0,0 +1,94 @@
+//! Implementación de un efecto de trémolo (modulación de amplitud)
+//!
+//! El trémolo multiplica la señal por una ganancia que oscila periódicamente,
+//! produciendo variaciones rítmicas de volumen.
+
+use super::{AudioEffect, Lfo};
+
+/// Trémolo con oscilador senoidal
+///
+/// La ganancia es `1 - depth * (0.5 - 0.5 * cos(fase))`: empieza en 1.0 y baja
+/// hasta `1 - depth` a mitad de cada ciclo.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, Tremolo};
+///
+/// let mut tremolo = Tremolo::new(5.0, 0.5, 44100.0);
+/// let mut buffer = vec![1.0; 44100];
+/// tremolo.process_buffer(&mut buffer);
+/// assert!(buffer.iter().all(|&x| (0.5..=1.0).contains(&x)));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Tremolo {
+    depth: f32,
+    lfo: Lfo,
+}
+
+impl Tremolo {
+    /// Crea un nuevo trémolo
+    ///
+    /// # Argumentos
+    /// * `rate_hz` - Frecuencia de la modulación en Hz
+    /// * `depth` - Cantidad de modulación (0.0 = ninguna, 1.0 = silencio en el valle)
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(rate_hz: f32, depth: f32, sample_rate: f32) -> Self {
+        let mut tremolo = Self {
+            depth: depth.clamp(0.0, 1.0),
+            lfo: Lfo::new(rate_hz, sample_rate),
+        };
+        tremolo.reset();
+        tremolo
+    }
+}
+
+impl AudioEffect for Tremolo {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let gain = 1.0 - self.depth * (0.5 - 0.5 * self.lfo.next());
+        sample * gain
+    }
+
+    fn reset(&mut self) {
+        // La senoide del LFO adelantada un cuarto de ciclo es el coseno
+        self.lfo.reset();
+        self.lfo.set_phase(0.25);
+    }
+
+    fn name(&self) -> &'static str {
+        "Tremolo"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tremolo_oscillates_at_rate() {
+        let sample_rate = 1000.0;
+        let rate = 4.0;
+        let depth = 0.6;
+        let mut tremolo = Tremolo::new(rate, depth, sample_rate);
+
+        // Un segundo de DC: 4 ciclos de 250 muestras
+        let mut output = vec![1.0; 1000];
+        tremolo.process_buffer(&mut output);
+
+        for cycle in output.chunks(250) {
+            let min = cycle.iter().fold(f32::INFINITY, |m, &x| m.min(x));
+            let max = cycle.iter().fold(f32::NEG_INFINITY, |m, &x| m.max(x));
+            assert!((min - (1.0 - depth)).abs() < 1e-3, "Cycle minimum {}", min);
+            assert!((max - 1.0).abs() < 1e-3, "Cycle maximum {}", max);
+
+            // El valle cae a mitad de ciclo
+            assert!((cycle[125] - (1.0 - depth)).abs() < 1e-3);
+        }
+
+        tremolo.reset();
+        assert_eq!(tremolo.process_sample(1.0), 1.0);
+    }
+}
@@ -0,0 +1,159 @@
+//! Efecto de velocidad variable (varispeed) basado en remuestreo
+//!
+//! Como en un magnetófono, cambiar la velocidad de reproducción altera a la vez
+//! la duración y el tono: al doble de velocidad el audio dura la mitad y suena
+//! una octava más agudo. La señal se remuestrea con interpolación sinc
+//! enventanada, que al acelerar actúa también como filtro antialiasing.
+
+use std::f32::consts::PI;
+
+use crate::AudioProcessingError;
+
+/// Número de muestras de entrada a cada lado del punto interpolado (a velocidad 1.0)
+const HALF_TAPS: usize = 16;
+/// Margen del filtro antialiasing respecto a la nueva frecuencia de Nyquist
+const ROLLOFF: f32 = 0.95;
+/// Velocidad mínima admitida: por debajo, la salida crecería sin límite
+/// (a 0.01 ya dura cien veces más que la entrada)
+const MIN_SPEED: f32 = 0.01;
+
+/// Cambia la velocidad de reproducción de un búfer, alterando duración y tono
+///
+/// # Argumentos
+/// * `input` - Búfer de audio de entrada
+/// * `speed` - Factor de velocidad (2.0 = doble de rápido y una octava más agudo,
+///   0.5 = la mitad de rápido y una octava más grave)
+/// * `sample_rate` - Frecuencia de muestreo en Hz
+///
+/// # Retorno
+/// Nuevo búfer de `input.len() / speed` muestras. Con un búfer vacío o una
+/// frecuencia de muestreo no positiva se devuelve una copia de la entrada.
+///
+/// # Errores
+/// [`AudioProcessingError::ProcessingError`] si `speed` no es finita o es
+/// menor que 0.01.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::varispeed;
+///
+/// let input = vec![0.0; 1000];
+/// let output = varispeed(&input, 2.0, 44100.0).unwrap();
+/// assert_eq!(output.len(), 500);
+/// assert!(varispeed(&input, 0.0, 44100.0).is_err());
+/// ```
+pub fn varispeed(input: &[f32], speed: f32, sample_rate: f32) -> Result<Vec<f32>, AudioProcessingError> {
+    // NaN no supera la comparación, así que también se rechaza aquí
+    if !(speed.is_finite() && speed >= MIN_SPEED) {
+        return Err(AudioProcessingError::ProcessingError(format!(
+            "Varispeed speed must be a finite value of at least {}, got {}",
+            MIN_SPEED, speed
+        )));
+    }
+    if input.is_empty() || sample_rate <= 0.0 {
+        return Ok(input.to_vec());
+    }
+
+    let output_len = (input.len() as f64 / speed as f64).round() as usize;
+
+    // Al acelerar, el contenido por encima de la nueva Nyquist se plegaría:
+    // se baja el corte del filtro de interpolación en proporción a la velocidad
+    let nyquist = sample_rate * 0.5;
+    let cutoff_hz = nyquist * (1.0 / speed).min(1.0) * ROLLOFF;
+    let cutoff = cutoff_hz / sample_rate; // ciclos por muestra de entrada
+    let half_width = (HALF_TAPS as f32 * speed.max(1.0)).ceil() as isize;
+
+    let len = input.len() as isize;
+    let output = (0..output_len)
+        .map(|n| {
+            let position = n as f64 * speed as f64;
+            let center = position.floor() as isize;
+            let frac = (position - center as f64) as f32;
+
+            let mut acc = 0.0f32;
+            for k in (center - half_width + 1)..=(center + half_width) {
+                if !(0..len).contains(&k) {
+                    continue;
+                }
+                let x = (k - center) as f32 - frac;
+                acc += input[k as usize] * windowed_sinc(x, cutoff, half_width as f32);
+            }
+            acc
+        })
+        .collect();
+
+    Ok(output)
+}
+
+/// Núcleo sinc paso bajo con ventana de Hann
+fn windowed_sinc(x: f32, cutoff: f32, half_width: f32) -> f32 {
+    if x.abs() >= half_width {
+        return 0.0;
+    }
+    let arg = 2.0 * PI * cutoff * x;
+    let sinc = if arg.abs() < 1e-6 { 1.0 } else { arg.sin() / arg };
+    let window = 0.5 * (1.0 + (PI * x / half_width).cos());
+    2.0 * cutoff * sinc * window
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, sample_rate: f32, num_samples: usize) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| 0.5 * (2.0 * PI * freq * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    /// Estima la frecuencia contando cruces por cero ascendentes
+    fn estimate_frequency(signal: &[f32], sample_rate: f32) -> f32 {
+        let crossings = signal.windows(2).filter(|w| w[0] <= 0.0 && w[1] > 0.0).count();
+        crossings as f32 * sample_rate / signal.len() as f32
+    }
+
+    #[test]
+    fn test_varispeed_double_speed() {
+        let sample_rate = 44100.0;
+        let input = sine(440.0, sample_rate, sample_rate as usize);
+
+        let output = varispeed(&input, 2.0, sample_rate).unwrap();
+        assert_eq!(output.len(), input.len() / 2);
+
+        let freq = estimate_frequency(&output, sample_rate);
+        assert!((freq - 880.0).abs() < 5.0, "Expected ~880 Hz, got {} Hz", freq);
+
+        // La amplitud se conserva lejos de los bordes
+        let peak = output[1000..output.len() - 1000].iter().fold(0.0f32, |m, &x| m.max(x.abs()));
+        assert!((peak - 0.5).abs() < 0.02, "Unexpected amplitude {}", peak);
+    }
+
+    #[test]
+    fn test_varispeed_half_speed_and_identity() {
+        let sample_rate = 44100.0;
+        let input = sine(440.0, sample_rate, 22050);
+
+        let slow = varispeed(&input, 0.5, sample_rate).unwrap();
+        assert_eq!(slow.len(), input.len() * 2);
+        let freq = estimate_frequency(&slow, sample_rate);
+        assert!((freq - 220.0).abs() < 5.0, "Expected ~220 Hz, got {} Hz", freq);
+
+        // A velocidad 1.0 la interpolación cae sobre las muestras originales
+        let same = varispeed(&input, 1.0, sample_rate).unwrap();
+        for (a, b) in input[100..input.len() - 100].iter().zip(&same[100..same.len() - 100]) {
+            assert!((a - b).abs() < 0.02);
+        }
+    }
+
+    #[test]
+    fn test_varispeed_rejects_speed_below_minimum() {
+        let input = vec![0.1; 64];
+        for speed in [0.0, -1.0, 1e-6, MIN_SPEED * 0.5, f32::NAN, f32::INFINITY] {
+            assert!(varispeed(&input, speed, 44100.0).is_err(), "speed {} was accepted", speed);
+        }
+
+        // El mínimo en sí es válido y acota la longitud de la salida
+        let slowest = varispeed(&input, MIN_SPEED, 44100.0).unwrap();
+        assert_eq!(slowest.len(), 6400);
+    }
+}
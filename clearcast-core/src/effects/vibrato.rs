@@ -0,0 +1,117 @@
+//! Implementación de un efecto de vibrato (modulación de tono)
+//!
+//! El vibrato lee la señal a través de un retardo cuya duración oscila con un
+//! LFO. Mientras el retardo crece la lectura avanza más despacio que la
+//! escritura y el tono baja; mientras decrece, el tono sube. A diferencia del
+//! chorus no se mezcla la señal seca: la salida es solo la copia modulada.
+
+use std::collections::VecDeque;
+
+use super::delay::read_interpolated;
+use super::{AudioEffect, Lfo};
+
+/// Vibrato con oscilador senoidal
+///
+/// El retardo oscila entre 0 y `2 * depth_ms` alrededor de `depth_ms`, así que
+/// la salida va `depth_ms` por detrás de la entrada en promedio. La posición de
+/// lectura es fraccionaria y se interpola linealmente entre muestras.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::effects::{AudioEffect, Vibrato};
+///
+/// let mut vibrato = Vibrato::new(5.0, 2.0, 44100.0);
+/// let mut buffer: Vec<f32> = (0..4410).map(|i| (i as f32 * 0.05).sin()).collect();
+/// vibrato.process_buffer(&mut buffer);
+/// assert!(buffer.iter().all(|x| x.abs() <= 1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Vibrato {
+    /// Desviación máxima del retardo respecto al centro, en muestras
+    depth_samples: f32,
+    /// Historial de la entrada, la muestra más reciente al final
+    buffer: VecDeque<f32>,
+    lfo: Lfo,
+}
+
+impl Vibrato {
+    /// Crea un nuevo vibrato
+    ///
+    /// # Argumentos
+    /// * `rate_hz` - Frecuencia de la modulación en Hz (normalmente 4 a 8)
+    /// * `depth_ms` - Desviación máxima del retardo en milisegundos; con
+    ///   `rate_hz` determina cuánto sube y baja el tono
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    pub fn new(rate_hz: f32, depth_ms: f32, sample_rate: f32) -> Self {
+        let depth_samples = depth_ms.max(0.0) * sample_rate / 1000.0;
+        // Retardo máximo más una muestra para interpolar
+        let len = (2.0 * depth_samples).ceil() as usize + 2;
+        Self {
+            depth_samples,
+            buffer: VecDeque::from(vec![0.0; len]),
+            lfo: Lfo::new(rate_hz, sample_rate),
+        }
+    }
+}
+
+impl AudioEffect for Vibrato {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        self.buffer.push_back(sample);
+        self.buffer.pop_front();
+
+        let delay = self.depth_samples * (1.0 + self.lfo.next());
+        read_interpolated(&self.buffer, delay)
+    }
+
+    fn reset(&mut self) {
+        self.buffer.iter_mut().for_each(|x| *x = 0.0);
+        self.lfo.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "Vibrato"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_vibrato_modulates_frequency_around_input() {
+        let sample_rate = 44100.0;
+        let freq = 1000.0;
+        let input: Vec<f32> = (0..44100).map(|i| (2.0 * PI * freq * i as f32 / sample_rate).sin()).collect();
+
+        // 2 ms a 5 Hz: el retardo varía hasta 2π·5·88.2/44100 ≈ 6.3% por muestra
+        let mut vibrato = Vibrato::new(5.0, 2.0, sample_rate);
+        let mut output = input.clone();
+        vibrato.process_buffer(&mut output);
+
+        // Cruces por cero ascendentes, interpolados entre muestras
+        let crossings: Vec<f32> = output
+            .windows(2)
+            .enumerate()
+            .skip(1000)
+            .filter(|(_, w)| w[0] < 0.0 && w[1] >= 0.0)
+            .map(|(i, w)| i as f32 + w[0] / (w[0] - w[1]))
+            .collect();
+        let frequencies: Vec<f32> = crossings.windows(2).map(|c| sample_rate / (c[1] - c[0])).collect();
+
+        let min = frequencies.iter().fold(f32::INFINITY, |m, &f| m.min(f));
+        let max = frequencies.iter().fold(f32::NEG_INFINITY, |m, &f| m.max(f));
+        let mean = frequencies.iter().sum::<f32>() / frequencies.len() as f32;
+        assert!(min < 0.96 * freq && min > 0.9 * freq, "Minimum frequency {}", min);
+        assert!(max > 1.04 * freq && max < 1.1 * freq, "Maximum frequency {}", max);
+        assert!((mean - freq).abs() < 0.01 * freq, "Mean frequency {}", mean);
+
+        // Tras reset no queda rastro de la señal anterior
+        vibrato.reset();
+        assert_eq!(vibrato.process_sample(0.0), 0.0);
+    }
+}
@@ -0,0 +1,152 @@
+//! Implementación de un "vocal rider" (automatización de ganancia para voz)
+//!
+//! Un vocal rider mueve la ganancia de forma suave y continua para mantener la
+//! voz en un nivel constante, subiendo las frases débiles y bajando las fuertes.
+//! A diferencia de un compresor, actúa sobre el nivel promedio (RMS) con tiempos
+//! de respuesta lentos y dentro de un rango de ganancia limitado, por lo que no
+//! altera los transitorios.
+
+use super::AudioEffect;
+use crate::utils::db_to_linear;
+
+/// Nivel por debajo del cual se considera silencio y se congela la ganancia (dBFS)
+const SILENCE_FLOOR_DB: f32 = -60.0;
+
+/// Automatización de ganancia que mantiene la voz cerca de un nivel objetivo
+#[derive(Debug, Clone)]
+pub struct VocalRider {
+    target_db: f32,
+    range_db: f32,
+    coeff: f32,
+    envelope: f32,
+    gain_db: f32,
+}
+
+impl VocalRider {
+    /// Crea un nuevo vocal rider
+    ///
+    /// # Argumentos
+    /// * `target_db` - Nivel RMS objetivo en dBFS (p. ej. -18.0)
+    /// * `response_ms` - Tiempo de respuesta del detector y de la ganancia en milisegundos
+    /// * `range_db` - Máxima corrección permitida en dB, hacia arriba o hacia abajo
+    /// * `sample_rate` - Frecuencia de muestreo en Hz
+    ///
+    /// # Ejemplo
+    /// ```
+    /// use clearcast_core::effects::{AudioEffect, VocalRider};
+    ///
+    /// let mut rider = VocalRider::new(-18.0, 200.0, 6.0, 44100.0);
+    /// let output = rider.process_sample(0.1);
+    /// ```
+    pub fn new(target_db: f32, response_ms: f32, range_db: f32, sample_rate: f32) -> Self {
+        let response_samples = (response_ms.max(1.0) * 0.001 * sample_rate).max(1.0);
+        Self {
+            target_db,
+            range_db: range_db.abs(),
+            coeff: (-1.0 / response_samples).exp(),
+            envelope: 0.0,
+            gain_db: 0.0,
+        }
+    }
+
+    /// Devuelve la ganancia aplicada actualmente en dB
+    pub fn current_gain_db(&self) -> f32 {
+        self.gain_db
+    }
+}
+
+impl AudioEffect for VocalRider {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        // Detector RMS con el tiempo de respuesta configurado
+        self.envelope = (1.0 - self.coeff) * sample * sample + self.coeff * self.envelope;
+        let level_db = 10.0 * self.envelope.max(1e-12).log10();
+
+        // En silencio se mantiene la ganancia para no amplificar el ruido de fondo
+        if level_db > SILENCE_FLOOR_DB {
+            let desired = (self.target_db - level_db).clamp(-self.range_db, self.range_db);
+            self.gain_db = (1.0 - self.coeff) * desired + self.coeff * self.gain_db;
+        }
+
+        sample * db_to_linear(self.gain_db)
+    }
+
+    fn reset(&mut self) {
+        self.envelope = 0.0;
+        self.gain_db = 0.0;
+    }
+
+    fn name(&self) -> &'static str {
+        "VocalRider"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tone(amplitude: f32, num_samples: usize, sample_rate: f32) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| amplitude * (2.0 * std::f32::consts::PI * 200.0 * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    fn rms_db(signal: &[f32]) -> f32 {
+        let mean_sq = signal.iter().map(|&x| x * x).sum::<f32>() / signal.len() as f32;
+        10.0 * mean_sq.log10()
+    }
+
+    #[test]
+    fn test_vocal_rider_rides_levels_within_range() {
+        let sample_rate = 44100.0;
+        let range_db = 6.0;
+        let mut rider = VocalRider::new(-18.0, 50.0, range_db, sample_rate);
+
+        // Frase débil (~-33 dBFS RMS) seguida de una fuerte (~-9 dBFS RMS)
+        let quiet = tone(0.03, sample_rate as usize, sample_rate);
+        let loud = tone(0.5, sample_rate as usize, sample_rate);
+
+        let mut quiet_out = quiet.clone();
+        rider.process_buffer(&mut quiet_out);
+        let mut loud_out = loud.clone();
+        rider.process_buffer(&mut loud_out);
+
+        // Analizar la segunda mitad de cada frase, una vez asentada la ganancia
+        let half = quiet.len() / 2;
+        let quiet_change = rms_db(&quiet_out[half..]) - rms_db(&quiet[half..]);
+        let loud_change = rms_db(&loud_out[half..]) - rms_db(&loud[half..]);
+
+        assert!(quiet_change > range_db - 0.5, "Quiet phrase should be raised, got {} dB", quiet_change);
+        assert!(quiet_change <= range_db + 0.01, "Boost must stay within range, got {} dB", quiet_change);
+        assert!(loud_change < -(range_db - 0.5), "Loud phrase should be lowered, got {} dB", loud_change);
+        assert!(loud_change >= -(range_db + 0.01), "Cut must stay within range, got {} dB", loud_change);
+    }
+
+    #[test]
+    fn test_vocal_rider_reaches_target_inside_range() {
+        let sample_rate = 44100.0;
+        let mut rider = VocalRider::new(-18.0, 50.0, 12.0, sample_rate);
+
+        // ~-23 dBFS RMS: necesita +5 dB, dentro del rango
+        let input = tone(0.1, sample_rate as usize, sample_rate);
+        let mut output = input.clone();
+        rider.process_buffer(&mut output);
+
+        let level = rms_db(&output[output.len() / 2..]);
+        assert!((level + 18.0).abs() < 1.0, "Expected ~-18 dBFS, got {}", level);
+    }
+
+    #[test]
+    fn test_vocal_rider_reset() {
+        let mut rider = VocalRider::new(-18.0, 50.0, 6.0, 44100.0);
+        let mut signal = tone(0.5, 4410, 44100.0);
+        rider.process_buffer(&mut signal);
+        assert!(rider.current_gain_db() < 0.0);
+
+        rider.reset();
+        assert_eq!(rider.current_gain_db(), 0.0);
+    }
+}
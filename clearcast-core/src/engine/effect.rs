@@ -0,0 +1,157 @@
+//! Motor completo usado como efecto
+//!
+//! `EngineEffect` envuelve un `AudioEngine` para que se pueda anidar en la
+//! cadena de efectos de otro motor o usar desde un host externo que solo conoce
+//! la interfaz `AudioEffect`. La normalización final se omite: depende del pico
+//! de todo el búfer y un efecto debe poder procesar bloques arbitrarios.
+
+use super::AudioEngine;
+use crate::effects::AudioEffect;
+
+/// [`AudioEffect`] wrapper that runs an [`AudioEngine`] without normalization
+///
+/// `process_buffer` applies the noise gate, the engine's effects, its limiter
+/// and its safety ceiling, if any. `process_sample` follows
+/// [`AudioEngine::process_sample`], which skips the gate.
+///
+/// Non-finite samples are handled according to the engine's `non_finite`:
+/// [`NonFiniteHandling::Zero`](super::NonFiniteHandling::Zero) silences them,
+/// and with [`NonFiniteHandling::Error`](super::NonFiniteHandling::Error) the error is logged and the whole block (or
+/// sample) is silenced, since an effect has no way to return it. Failing
+/// effects of the nested engine are logged too.
+///
+/// The gate threshold is relative to the peak of each block, so with small
+/// blocks it follows the level block by block and the gate pumps; give the
+/// nested engine a threshold of 0.0 when the host's blocks are short.
+///
+/// # Example
+/// ```rust
+/// use clearcast_core::{AudioEffect, AudioEngine, EngineEffect};
+///
+/// let mut outer = AudioEngine::new();
+/// outer.add_effect(EngineEffect(AudioEngine::new()).boxed());
+/// let output = outer.process(vec![0.1, -0.5, 0.3]).unwrap();
+/// assert_eq!(output.len(), 3);
+/// ```
+pub struct EngineEffect(pub AudioEngine);
+
+impl AudioEffect for EngineEffect {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let mut sample = sample;
+        sanitize(&self.0, std::slice::from_mut(&mut sample));
+        self.0.process_sample(sample)
+    }
+
+    fn process_buffer(&mut self, buffer: &mut [f32]) {
+        let engine = &self.0;
+        sanitize(engine, buffer);
+
+        engine.noise_gate(buffer);
+        if let Err(e) = engine.apply_effects(buffer) {
+            log::warn!("Effects processing failed in nested engine: {}", e);
+        }
+        engine.apply_soft_limiter(buffer);
+        engine.apply_safety_ceiling(buffer);
+    }
+
+    fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) {
+        let engine = &self.0;
+        sanitize(engine, left);
+        sanitize(engine, right);
+
+        // Same stages as `process_buffer`, with the gate linked like in
+        // `AudioEngine::process_stereo` and dual mono effects
+        let threshold = engine.noise_gate_threshold(left).max(engine.noise_gate_threshold(right));
+        engine.apply_noise_gate(left, threshold);
+        engine.apply_noise_gate(right, threshold);
+        if let Err(e) = engine.apply_effects_stereo(left, right) {
+            log::warn!("Effects processing failed in nested engine: {}", e);
+        }
+        for channel in [left, right] {
+            engine.apply_soft_limiter(channel);
+            engine.apply_safety_ceiling(channel);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.0.reset();
+    }
+
+    fn name(&self) -> &'static str {
+        "EngineEffect"
+    }
+}
+
+/// Apply the engine's `non_finite` handling, silencing a rejected block
+fn sanitize(engine: &AudioEngine, samples: &mut [f32]) {
+    if let Err(e) = engine.sanitize_input(samples) {
+        log::warn!("Nested engine rejected its input: {}", e);
+        samples.fill(0.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::Delay;
+    use crate::engine::{LimiterConfig, LimiterMode, NonFiniteHandling};
+
+    #[test]
+    fn test_nested_engine_applies_its_limiter() {
+        // Motor interior que recorta a 0.5, con un eco para comprobar el reset
+        let limiter = LimiterConfig { threshold: 0.5, mode: LimiterMode::HardClip, ..Default::default() };
+        let mut inner = AudioEngine::with_limiter(0.0, 0.95, limiter).unwrap();
+        inner.add_effect(Delay::new(1.0, 0.0, 0.5, 1.0, 1000).boxed());
+
+        let mut outer = AudioEngine::new();
+        outer.add_effect(EngineEffect(inner).boxed());
+
+        let input: Vec<f32> = (0..1000).map(|i| 0.9 * (i as f32 * 0.05).sin()).collect();
+        let mut output = input.clone();
+        outer.apply_effects(&mut output).unwrap();
+
+        // Sin la normalización del motor interior, la salida queda en el techo
+        // de su limitador, y las partes suaves no se tocan
+        let peak = output.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+        assert_eq!(peak, 0.5);
+        assert!(output[1..].iter().zip(input.windows(2)).all(|(y, w)| {
+            let dry_plus_echo = w[1] + 0.5 * w[0];
+            dry_plus_echo.abs() > 0.5 || *y == dry_plus_echo
+        }));
+
+        // La versión muestra a muestra también limita, y reset limpia el eco
+        let mut effect = EngineEffect(AudioEngine::with_limiter(0.0, 0.95, limiter).unwrap());
+        assert_eq!(effect.process_sample(2.0), 0.5);
+        assert_eq!(effect.process_sample(f32::NAN), 0.0);
+        outer.reset();
+        let mut silence = vec![0.0; 4];
+        outer.apply_effects(&mut silence).unwrap();
+        assert_eq!(silence, vec![0.0; 4]);
+    }
+
+    #[test]
+    fn test_nested_engine_follows_non_finite_handling() {
+        let input = [0.1, f32::NAN, -0.2, f32::INFINITY];
+
+        // Con Zero solo se silencian las muestras no finitas
+        let mut effect = EngineEffect(AudioEngine::with_settings(0.0, 0.95).unwrap());
+        let mut block = input;
+        effect.process_buffer(&mut block);
+        assert_eq!(block, [0.1, 0.0, -0.2, 0.0]);
+
+        // Con Error se rechaza el bloque entero, que queda en silencio
+        let mut engine = AudioEngine::with_settings(0.0, 0.95).unwrap();
+        engine.non_finite = NonFiniteHandling::Error;
+        let mut effect = EngineEffect(engine);
+        let mut block = input;
+        effect.process_buffer(&mut block);
+        assert_eq!(block, [0.0; 4]);
+        assert_eq!(effect.process_sample(f32::NAN), 0.0);
+        assert_eq!(effect.process_sample(0.1), 0.1);
+
+        let (mut left, mut right) = ([0.1, 0.2], [f32::NAN, 0.3]);
+        effect.process_stereo(&mut left, &mut right);
+        assert_eq!(left, [0.1, 0.2]);
+        assert_eq!(right, [0.0, 0.0]);
+    }
+}
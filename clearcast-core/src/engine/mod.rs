@@ -37,42 +37,436 @@
 use ndarray::Array1;
 
 // Sincronización entre hilos
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 
-// Manejo de errores
-use thiserror::Error;
+// Ventana deslizante del detector de picos del limitador
+use std::collections::VecDeque;
+
+// Rutas de los ficheros de `process_files`
+#[cfg(feature = "wav")]
+use std::path::PathBuf;
+
+// Tipo de error compartido con los filtros
+pub use crate::error::AudioProcessingError;
 
 // Interfaz de efectos de audio
-use crate::effects::AudioEffect;
+use crate::effects::{AudioEffect, DeEsser, SoftLimiter};
+
+// Efectos del preset de voz
+use crate::filters::{ButterworthFilter, Compressor};
+
+// Medición de pico real (true peak)
+use crate::utils::meter::rms;
+use crate::utils::{db_to_linear, linear_to_db, true_peak, true_peak_dbtp};
+
+// Bucles de ganancia desenrollados
+use crate::utils::simd;
+
+// Procesamiento por bloques con estado persistente
+mod streaming;
+pub use streaming::StreamingEngine;
+
+// Motor completo como efecto de otra cadena
+mod effect;
+pub use effect::EngineEffect;
 
 // Processing will be done on the full array without chunking
 
-/// Tipos de error para operaciones de procesamiento de audio
+/// Output ceiling used by [`AudioEngine::with_safety`] (-1 dBFS)
+pub const SAFETY_CEILING: f32 = 0.891;
+
+/// Cutoff of the rumble filter of [`AudioEngine::voice_preset`] (in Hz)
+const VOICE_HIGHPASS_HZ: f32 = 80.0;
+/// Level [`AudioEngine::voice_preset`] normalizes to (in dBFS RMS)
+const VOICE_TARGET_RMS_DB: f32 = -16.0;
+/// Width of the soft knee below the target peak that rounds off the samples a
+/// normalization gain pushes over it, as a fraction of the target peak
+const OVERSHOOT_KNEE: f32 = 0.1;
+
+/// Peak gain reduction accepted by [`AudioEngine::optimal_normalization_gain`] (in dB)
+const MAX_PREDICTED_GAIN_REDUCTION_DB: f32 = 3.0;
+/// Highest level searched by [`AudioEngine::optimal_normalization_gain`]
+const MAX_PREDICTED_LEVEL: f32 = 1000.0;
+
+/// Frame length in samples used by [`AudioEngine::suggest_settings`]
+const SUGGESTION_FRAME: usize = 512;
+/// Fraction of the quietest frames taken as the noise floor
+const NOISE_FLOOR_FRACTION: f32 = 0.1;
+/// Margin of the suggested noise gate over the estimated noise floor (+6 dB)
+const NOISE_GATE_MARGIN: f32 = 2.0;
+/// Points per quarter period of the reference sine used by `auto_makeup`
+const AUTO_MAKEUP_STEPS: usize = 64;
+
+/// Shape of the limiter's gain curve
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LimiterMode {
+    /// Knee and ratio curve that rounds peaks off; peaks can end up slightly
+    /// above the threshold
+    #[default]
+    Soft,
+    /// Brickwall: clamp every sample to exactly the threshold, at the cost of
+    /// distortion on the clipped peaks. Knee, ratio and detection window are ignored
+    HardClip,
+}
+
+/// How the limiter's gain recovers once a peak has passed
 ///
-/// Este enum define los posibles errores que pueden ocurrir durante el
-/// procesamiento de audio, permitiendo un manejo de errores detallado.
+/// Both shapes start from the gain reduction of the last peak and reach unity
+/// gain exactly `release_ms` later; they differ in the path in between.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReleaseShape {
+    /// Constant recovery rate
+    #[default]
+    Linear,
+    /// Recover most of the gain quickly, then ease into unity gain
+    Logarithmic,
+}
+
+impl ReleaseShape {
+    /// Fraction of the gain reduction recovered after `progress` of the release time (0.0 to 1.0)
+    fn recovery(self, progress: f32) -> f32 {
+        match self {
+            Self::Linear => progress,
+            Self::Logarithmic => (1.0 + 9.0 * progress).log10(),
+        }
+    }
+}
+
+/// Configuration for the soft limiter
+///
+/// New settings may be added in later versions, so the struct can't be built
+/// with a literal outside this crate: start from [`LimiterConfig::default`]
+/// and chain the `with_*` setters, or assign the fields of a mutable copy.
 ///
-/// # Ejemplos
+/// # Example
 /// ```rust
-/// use clearcast_core::{AudioEngine, AudioProcessingError};
+/// use clearcast_core::engine::{AudioEngine, LimiterConfig, LimiterMode};
 ///
-/// let engine = AudioEngine::new();
-/// match engine.process(vec![]) {
-///     Err(AudioProcessingError::EmptyBuffer) => {
-///         println!("Error: Se proporcionó un búfer vacío");
-///     }
-///     Err(e) => println!("Error inesperado: {}", e),
-///     Ok(_) => println!("Procesamiento exitoso"),
-/// }
+/// let limiter = LimiterConfig::default()
+///     .with_threshold(0.5)
+///     .with_mode(LimiterMode::HardClip)
+///     .with_detection_window(5.0, 48000.0);
+/// let engine = AudioEngine::with_limiter(0.0, 0.95, limiter).unwrap();
+/// assert_eq!(engine.limiter.threshold, 0.5);
 /// ```
-#[derive(Error, Debug)]
-pub enum AudioProcessingError {
-    /// Error that occurs when an empty buffer is provided
-    #[error("Empty audio buffer provided")]
-    EmptyBuffer,
-    /// Error that occurs during audio processing
-    #[error("Audio processing error: {0}")]
-    ProcessingError(String),
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct LimiterConfig {
+    /// Threshold above which the limiter starts to take effect (0.0 to 1.0)
+    pub threshold: f32,
+    /// Knee width for smooth transition into limiting (0.0 for hard knee, 0.1 for soft knee)
+    pub knee_width: f32,
+    /// Make-up gain applied after limiting (in dB)
+    pub make_up_gain: f32,
+    /// Derive the make-up gain from the curve (threshold, knee and ratio)
+    /// instead of using `make_up_gain`: the gain that gives a full-scale sine
+    /// the same RMS after limiting as before, so limited material keeps
+    /// roughly its loudness
+    pub auto_makeup: bool,
+    /// Ratio of compression (e.g., 4.0 means 4:1 compression)
+    pub ratio: f32,
+    /// Length of the peak detection window in milliseconds. With 0.0 the
+    /// limiter reacts to each sample on its own; otherwise the gain reduction
+    /// follows the highest peak of the last `detection_window_ms`
+    pub detection_window_ms: f32,
+    /// Sample rate in Hz used to convert `detection_window_ms` and
+    /// `release_ms` to samples. The engine doesn't know the rate of the audio
+    /// it is given, so this has to match it when either time is set
+    pub sample_rate: f32,
+    /// Soft curve or brickwall clipping
+    pub mode: LimiterMode,
+    /// Time in milliseconds the gain takes to return to unity after a peak.
+    /// With 0.0 the gain follows the input level instantly. Deeper reduction
+    /// always applies at once; only the recovery is slowed down
+    pub release_ms: f32,
+    /// Path the gain follows during the release
+    pub release_shape: ReleaseShape,
+}
+
+impl Default for LimiterConfig {
+    fn default() -> Self {
+        Self {
+            threshold: 0.9,  // Start limiting at 90% of full scale
+            knee_width: 0.1,  // 10% knee width for smooth transition
+            make_up_gain: 0.0,  // No make-up gain by default
+            auto_makeup: false,
+            ratio: 8.0,  // 8:1 ratio for limiting
+            detection_window_ms: 0.0,  // Per-sample detection
+            sample_rate: 44100.0,
+            mode: LimiterMode::Soft,
+            release_ms: 0.0,  // Instant release
+            release_shape: ReleaseShape::Linear,
+        }
+    }
+}
+
+impl LimiterConfig {
+    /// Sets the threshold above which the limiter starts to take effect (0.0 to 1.0)
+    pub fn with_threshold(mut self, threshold: f32) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Sets the knee width (0.0 for a hard knee)
+    pub fn with_knee_width(mut self, knee_width: f32) -> Self {
+        self.knee_width = knee_width;
+        self
+    }
+
+    /// Sets the compression ratio above the threshold
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Sets the make-up gain applied after limiting, in dB
+    pub fn with_make_up_gain(mut self, make_up_gain: f32) -> Self {
+        self.make_up_gain = make_up_gain;
+        self
+    }
+
+    /// Derives the make-up gain from the curve instead of `make_up_gain`
+    pub fn with_auto_makeup(mut self, auto_makeup: bool) -> Self {
+        self.auto_makeup = auto_makeup;
+        self
+    }
+
+    /// Sets the soft curve or brickwall clipping
+    pub fn with_mode(mut self, mode: LimiterMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Sets the sample rate the detection window and release times refer to
+    pub fn with_sample_rate(mut self, sample_rate: f32) -> Self {
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Sets the peak detection window and the sample rate it is measured at
+    pub fn with_detection_window(mut self, detection_window_ms: f32, sample_rate: f32) -> Self {
+        self.detection_window_ms = detection_window_ms;
+        self.sample_rate = sample_rate;
+        self
+    }
+
+    /// Sets the release time and the path the gain follows during it
+    pub fn with_release(mut self, release_ms: f32, release_shape: ReleaseShape) -> Self {
+        self.release_ms = release_ms;
+        self.release_shape = release_shape;
+        self
+    }
+
+    /// Output level of the limiter's static curve for an input level, before make-up gain
+    fn limited_level(&self, level: f32) -> f32 {
+        let threshold = self.threshold;
+        let ratio = self.ratio;
+
+        // Calculate knee parameters
+        let lower_threshold = threshold * (1.0 - self.knee_width);
+        let upper_threshold = threshold * (1.0 + self.knee_width);
+
+        if level <= lower_threshold {
+            // Below knee, no limiting
+            level
+        } else if level < upper_threshold {
+            // In knee region, apply soft knee
+            let knee = upper_threshold - lower_threshold;
+            let over = level - lower_threshold;
+            let compression = over / knee;
+            let target_gain = 1.0 + (ratio - 1.0) * compression * compression;
+
+            lower_threshold + (level - lower_threshold) / target_gain
+        } else {
+            // Above knee, apply full limiting
+            let over = level - threshold;
+            threshold + over / ratio
+        }
+    }
+
+    /// Linear make-up gain, either configured or derived with `auto_makeup`
+    fn make_up_factor(&self) -> f32 {
+        if !self.auto_makeup {
+            return db_to_linear(self.make_up_gain);
+        }
+
+        // RMS of a full-scale sine before and after the static curve, over
+        // one period (the quarter period repeats with the same magnitudes)
+        let (mut input_power, mut output_power) = (0.0, 0.0);
+        for i in 0..AUTO_MAKEUP_STEPS {
+            let level = (std::f32::consts::FRAC_PI_2 * (i as f32 + 0.5) / AUTO_MAKEUP_STEPS as f32).sin();
+            let limited = match self.mode {
+                LimiterMode::HardClip => level.min(self.threshold),
+                LimiterMode::Soft => self.limited_level(level),
+            };
+            input_power += level * level;
+            output_power += limited * limited;
+        }
+
+        if output_power > 0.0 { (input_power / output_power).sqrt() } else { 1.0 }
+    }
+
+    /// Highest level the limiter outputs, make-up gain included
+    ///
+    /// This is the level a full-scale sample comes out at (never more than
+    /// full scale); louder samples are clamped to it. It depends only on the
+    /// limiter settings, not on the engine's `target_peak`.
+    pub fn ceiling(&self) -> f32 {
+        self.ceiling_with(self.make_up_factor())
+    }
+
+    /// [`LimiterConfig::ceiling`] for an already computed make-up factor
+    fn ceiling_with(&self, make_up: f32) -> f32 {
+        let full_scale = match self.mode {
+            LimiterMode::HardClip => self.threshold.min(1.0),
+            LimiterMode::Soft => self.limited_level(1.0),
+        };
+        (full_scale * make_up).min(1.0)
+    }
+
+    /// Static curve, make-up gain and ceiling applied to a single sample
+    ///
+    /// `make_up` is [`LimiterConfig::make_up_factor`], which callers working
+    /// sample by sample compute once rather than for every sample.
+    fn limit(&self, sample: f32, make_up: f32) -> f32 {
+        let limited = match self.mode {
+            LimiterMode::HardClip => sample.clamp(-self.threshold, self.threshold),
+            LimiterMode::Soft if sample.abs() > self.threshold * (1.0 - self.knee_width) => {
+                sample.signum() * self.limited_level(sample.abs())
+            }
+            LimiterMode::Soft => sample,
+        };
+
+        let ceiling = self.ceiling_with(make_up);
+        (limited * make_up).clamp(-ceiling, ceiling)
+    }
+
+    /// Length of the peak detection window in samples
+    fn detection_window_samples(&self) -> usize {
+        (self.detection_window_ms * 0.001 * self.sample_rate).round().max(0.0) as usize
+    }
+
+    /// Length of the release in samples
+    fn release_samples(&self) -> usize {
+        (self.release_ms * 0.001 * self.sample_rate).round().max(0.0) as usize
+    }
+}
+
+/// Gain recovery state of the limiter's release
+#[derive(Debug, Clone, Copy)]
+struct ReleaseEnvelope {
+    /// Gain when the current release started
+    start_gain: f32,
+    /// Samples since the current release started
+    elapsed: usize,
+}
+
+impl Default for ReleaseEnvelope {
+    fn default() -> Self {
+        Self { start_gain: 1.0, elapsed: 0 }
+    }
+}
+
+impl ReleaseEnvelope {
+    /// Gain to apply given the static curve's gain for the current sample
+    fn next(&mut self, target: f32, release_samples: usize, shape: ReleaseShape) -> f32 {
+        let progress = self.elapsed as f32 / release_samples as f32;
+        let released = if progress >= 1.0 {
+            1.0
+        } else {
+            self.start_gain + (1.0 - self.start_gain) * shape.recovery(progress)
+        };
+
+        if target <= released {
+            // A new peak: reduce at once and restart the release from here
+            self.start_gain = target;
+            self.elapsed = 1;
+            target
+        } else {
+            self.elapsed += 1;
+            released
+        }
+    }
+}
+
+/// How the noise gate treats samples below its threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseReductionMode {
+    /// Zero every sample below the threshold
+    #[default]
+    Gate,
+    /// Scale samples below the threshold by `(|x| / threshold)^2`, which fades
+    /// low-level content out smoothly instead of chopping it
+    SoftGate,
+}
+
+impl NoiseReductionMode {
+    /// Apply the gate curve to one sample
+    fn apply(self, x: f32, threshold: f32) -> f32 {
+        match self {
+            // A small epsilon keeps values at the threshold despite floating point imprecision
+            Self::Gate if x.abs() < threshold - 1e-6 => 0.0,
+            Self::SoftGate if x.abs() < threshold => {
+                let ratio = x.abs() / threshold;
+                x * ratio * ratio
+            }
+            _ => x,
+        }
+    }
+}
+
+/// Which level of the buffer normalization brings to the target peak
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum NormalizeMode {
+    /// The loudest sample (or the true peak with `true_peak_normalization`)
+    #[default]
+    Peak,
+    /// The given percentile of the sample magnitudes (0.0 to 100.0, e.g. 99.0),
+    /// so a few isolated clicks don't make the whole buffer quiet. The
+    /// samples above it go through a soft knee that starts 10% below the
+    /// target peak, which rounds the clicks off under the target instead of
+    /// flattening them at it; the loudest part of the bulk is bent slightly too
+    Percentile(f32),
+    /// The RMS level, brought to the given level in dBFS (e.g. -16.0) instead
+    /// of the target peak. A rough stand-in for loudness normalization, with
+    /// no K-weighting or gating. The peaks the gain pushes toward the target
+    /// peak go through the same soft knee as with `Percentile`
+    Rms(f32),
+}
+
+/// What [`AudioEngine::process`] does with NaN and infinite input samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NonFiniteHandling {
+    /// Replace them with silence (0.0) and process the rest of the buffer
+    #[default]
+    Zero,
+    /// Reject the whole buffer with a `ProcessingError`
+    Error,
+}
+
+/// Measurements taken on the output of [`AudioEngine::process_with_report`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProcessReport {
+    /// Highest absolute sample value of the output (linear)
+    pub peak: f32,
+    /// Oversampled true peak of the output in dBTP
+    pub true_peak_dbtp: f32,
+    /// Distance in dB between the output true peak and the engine's ceiling:
+    /// `safety_ceiling` when set, otherwise [`LimiterConfig::ceiling`]
+    /// (infinite for silent output, negative when the output exceeds it)
+    pub headroom_db: f32,
+}
+
+/// Engine settings recommended by [`AudioEngine::suggest_settings`]
+///
+/// The values use the same scale as [`AudioEngine::with_settings`], so they can
+/// be passed to it directly.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EngineConfig {
+    /// Noise gate threshold relative to the buffer peak (0.0 to 1.0)
+    pub noise_reduction_threshold: f32,
+    /// Target peak amplitude for normalization (0.0 to 1.0)
+    pub target_peak: f32,
 }
 
 /// Motor principal para el procesamiento de audio
@@ -108,40 +502,48 @@ pub enum AudioProcessingError {
 /// let audio = vec![0.1, -0.2, 0.3];
 /// let processed = engine.process(audio).unwrap();
 /// ```
-/// Configuration for the soft limiter
-#[derive(Debug, Clone, Copy)]
-pub struct LimiterConfig {
-    /// Threshold above which the limiter starts to take effect (0.0 to 1.0)
-    pub threshold: f32,
-    /// Knee width for smooth transition into limiting (0.0 for hard knee, 0.1 for soft knee)
-    pub knee_width: f32,
-    /// Make-up gain applied after limiting (in dB)
-    pub make_up_gain: f32,
-    /// Ratio of compression (e.g., 4.0 means 4:1 compression)
-    pub ratio: f32,
-}
-
-impl Default for LimiterConfig {
-    fn default() -> Self {
-        Self {
-            threshold: 0.9,  // Start limiting at 90% of full scale
-            knee_width: 0.1,  // 10% knee width for smooth transition
-            make_up_gain: 0.0,  // No make-up gain by default
-            ratio: 8.0,  // 8:1 ratio for limiting
-        }
-    }
-}
-
-/// Main audio processing engine
 pub struct AudioEngine {
     /// Threshold for noise reduction (0.0 to 1.0, higher means more aggressive noise reduction)
-    pub noise_reduction_threshold: f32,
+    noise_reduction_threshold: f32,
+    /// Whether the noise gate zeroes or smoothly attenuates sub-threshold samples
+    pub noise_reduction_mode: NoiseReductionMode,
     /// Target peak amplitude for normalization (0.0 to 1.0)
-    pub target_peak: f32,
+    target_peak: f32,
+    /// Whether normalization follows the peak or a percentile of the buffer
+    pub normalize_mode: NormalizeMode,
+    /// Normalize against the oversampled true peak instead of the sample peak,
+    /// so inter-sample peaks don't exceed `target_peak` after normalization
+    pub true_peak_normalization: bool,
+    /// Also normalize right after the effects, before the limiter. Effects
+    /// that add gain (a resonant EQ, for instance) then reach the limiter
+    /// already scaled to the target instead of being squashed by it, and the
+    /// final normalization only makes up for what the limiter shaved off
+    pub normalize_before_limiter: bool,
     /// Configuration for the soft limiter
     pub limiter: LimiterConfig,
+    /// How non-finite input samples are handled before any processing
+    pub non_finite: NonFiniteHandling,
+    /// Hard output ceiling enforced as the very last stage of the chain.
+    /// When set, no output sample exceeds it in absolute value, whatever the
+    /// other settings, effects or input (non-finite samples become silence).
+    /// The whole-buffer paths also bring the estimated true peak under it;
+    /// `process_sample`, [`StreamingEngine`] and [`EngineEffect`] only see
+    /// part of the signal and clamp the samples alone
+    pub safety_ceiling: Option<f32>,
     /// List of audio effects to apply
     pub effects: Vec<Arc<Mutex<dyn AudioEffect + Send + 'static>>>,
+    /// Right-channel copies of the stateful mono effects, for `process_stereo`
+    stereo_twins: Mutex<Vec<StereoTwin>>,
+    /// Limiter settings and the make-up factor last derived from them, so
+    /// `process_sample` doesn't integrate the auto make-up for every sample
+    make_up_cache: Option<(LimiterConfig, f32)>,
+}
+
+/// Effect of the chain paired with the copy that processes the right channel
+/// in [`AudioEngine::apply_effects_stereo`] (`None` if it doesn't provide one)
+struct StereoTwin {
+    effect: Arc<Mutex<dyn AudioEffect + Send + 'static>>,
+    right: Option<Box<dyn AudioEffect>>,
 }
 
 impl Default for AudioEngine {
@@ -155,12 +557,87 @@ impl AudioEngine {
     pub fn new() -> Self {
         Self {
             noise_reduction_threshold: 0.05, // Default 5% threshold
+            noise_reduction_mode: NoiseReductionMode::default(),
             target_peak: 0.95,              // Target 95% of maximum amplitude
+            normalize_mode: NormalizeMode::default(),
+            true_peak_normalization: false,
+            normalize_before_limiter: false,
             limiter: LimiterConfig::default(),
+            non_finite: NonFiniteHandling::default(),
+            safety_ceiling: None,
             effects: Vec::new(),
+            stereo_twins: Mutex::new(Vec::new()),
+            make_up_cache: None,
         }
     }
 
+    /// Create an AudioEngine that is guaranteed never to clip
+    ///
+    /// Normalizes against the true peak to a conservative ceiling of
+    /// [`SAFETY_CEILING`] and enforces that ceiling as a final hard stage, so
+    /// `process` never returns a sample above it even if the settings are
+    /// changed afterwards or the effects misbehave. Inter-sample peaks are
+    /// kept under it too: if clamping leaves the 4x oversampled true peak
+    /// above the ceiling, the whole buffer is turned down to fit.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::engine::{AudioEngine, SAFETY_CEILING};
+    ///
+    /// let engine = AudioEngine::with_safety();
+    /// let output = engine.process(vec![0.0, 4.0, -12.0, f32::NAN]).unwrap();
+    /// assert!(output.iter().all(|x| x.abs() <= SAFETY_CEILING));
+    /// ```
+    pub fn with_safety() -> Self {
+        Self {
+            target_peak: SAFETY_CEILING,
+            true_peak_normalization: true,
+            safety_ceiling: Some(SAFETY_CEILING),
+            ..Self::new()
+        }
+    }
+
+
+    /// Create an AudioEngine set up for spoken voice
+    ///
+    /// The effect chain is a 2nd-order Butterworth high-pass at 80 Hz to
+    /// remove rumble, gentle RMS compression (3:1 above -20 dBFS) and a
+    /// de-esser around 6.5 kHz. The noise gate uses
+    /// [`NoiseReductionMode::SoftGate`], so quiet syllables fade out instead
+    /// of being chopped. The output is normalized to -16 dBFS RMS with
+    /// [`NormalizeMode::Rms`], before and after the limiter. The peaks the
+    /// level boost raises are rounded off by the soft knee of the RMS
+    /// normalization and then by the limiter; neither hard-clips them at the
+    /// target peak. At sample rates too low for the high-pass it is left out
+    /// of the chain.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::AudioEngine;
+    ///
+    /// let engine = AudioEngine::voice_preset(44100.0);
+    /// assert_eq!(engine.effects.len(), 3);
+    /// let voice: Vec<f32> = (0..44100).map(|i| 0.1 * (i as f32 * 0.03).sin()).collect();
+    /// let output = engine.process(voice).unwrap();
+    /// assert!(output.iter().all(|x| x.abs() <= engine.target_peak()));
+    /// ```
+    pub fn voice_preset(sample_rate: f32) -> Self {
+        let mut engine = Self {
+            noise_reduction_mode: NoiseReductionMode::SoftGate,
+            normalize_mode: NormalizeMode::Rms(VOICE_TARGET_RMS_DB),
+            normalize_before_limiter: true,
+            limiter: LimiterConfig::default().with_sample_rate(sample_rate),
+            ..Self::new()
+        };
+
+        match ButterworthFilter::highpass(VOICE_HIGHPASS_HZ, 2, sample_rate) {
+            Ok(highpass) => engine.add_effect(highpass.boxed()),
+            Err(e) => log::warn!("voice_preset: leaving out the high-pass: {}", e),
+        }
+        engine.add_effect(Compressor::new(-20.0, 3.0, 10.0, 150.0, sample_rate).boxed());
+        engine.add_effect(DeEsser::new(6500.0, -30.0, 4.0, sample_rate).boxed());
+        engine
+    }
 
     /// Create a new AudioEngine with custom settings
     pub fn with_settings(
@@ -180,47 +657,397 @@ impl AudioEngine {
            !(0.0..=1.0).contains(&target_peak) ||
            !(0.0..=1.0).contains(&limiter.threshold) ||
            !(0.0..=1.0).contains(&limiter.knee_width) ||
-           limiter.ratio < 1.0 {
+           limiter.ratio < 1.0 ||
+           limiter.detection_window_ms < 0.0 ||
+           limiter.release_ms.is_nan() || limiter.release_ms < 0.0 ||
+           limiter.sample_rate <= 0.0 {
             return Err(AudioProcessingError::ProcessingError(
-                "Invalid settings: thresholds must be between 0.0 and 1.0, and ratio must be >= 1.0".to_string(),
+                "Invalid settings: thresholds must be between 0.0 and 1.0, ratio must be >= 1.0, \
+                 the detection window and release must not be negative and the sample rate must be positive".to_string(),
             ));
         }
         
         Ok(Self {
             noise_reduction_threshold: noise_reduction_threshold.clamp(0.0, 1.0),
+            noise_reduction_mode: NoiseReductionMode::default(),
             target_peak: target_peak.clamp(0.0, 1.0),
+            normalize_mode: NormalizeMode::default(),
+            true_peak_normalization: false,
+            normalize_before_limiter: false,
             limiter,
+            non_finite: NonFiniteHandling::default(),
+            safety_ceiling: None,
             effects: Vec::new(),
+            stereo_twins: Mutex::new(Vec::new()),
+            make_up_cache: None,
         })
     }
 
 
+    /// Threshold for noise reduction (0.0 to 1.0)
+    pub fn noise_reduction_threshold(&self) -> f32 {
+        self.noise_reduction_threshold
+    }
+
+    /// Set the noise reduction threshold
+    ///
+    /// Values outside 0.0 to 1.0 are rejected and leave the current setting unchanged.
+    pub fn set_noise_reduction_threshold(&mut self, threshold: f32) -> Result<(), AudioProcessingError> {
+        if !(0.0..=1.0).contains(&threshold) {
+            return Err(AudioProcessingError::ProcessingError(format!(
+                "Invalid noise reduction threshold {}: must be between 0.0 and 1.0",
+                threshold
+            )));
+        }
+        self.noise_reduction_threshold = threshold;
+        Ok(())
+    }
+
+    /// Target peak amplitude for normalization (0.0 to 1.0)
+    pub fn target_peak(&self) -> f32 {
+        self.target_peak
+    }
+
+    /// Set the target peak amplitude for normalization
+    ///
+    /// Values outside 0.0 to 1.0 are rejected and leave the current setting unchanged.
+    pub fn set_target_peak(&mut self, target_peak: f32) -> Result<(), AudioProcessingError> {
+        if !(0.0..=1.0).contains(&target_peak) {
+            return Err(AudioProcessingError::ProcessingError(format!(
+                "Invalid target peak {}: must be between 0.0 and 1.0",
+                target_peak
+            )));
+        }
+        self.target_peak = target_peak;
+        Ok(())
+    }
+
     /// Process audio data with noise reduction, normalization and effects
+    ///
+    /// Allocating wrapper around [`AudioEngine::process_in_place`].
     pub fn process(&self, input: Vec<f32>) -> Result<Vec<f32>, AudioProcessingError> {
-        if input.is_empty() {
+        let mut output = input;
+        self.process_in_place(&mut output)?;
+        Ok(output)
+    }
+
+    /// Process audio data and report how much headroom the output has left
+    ///
+    /// Useful to decide whether loudness can be pushed further without
+    /// reaching the ceiling. The headroom is measured against the safety
+    /// ceiling if there is one, and against the limiter's ceiling otherwise.
+    pub fn process_with_report(
+        &self,
+        input: Vec<f32>,
+    ) -> Result<(Vec<f32>, ProcessReport), AudioProcessingError> {
+        let output = self.process(input)?;
+
+        let peak = simd::peak_abs(&output);
+        let true_peak_dbtp = true_peak_dbtp(&output);
+        let report = ProcessReport {
+            peak,
+            true_peak_dbtp,
+            headroom_db: linear_to_db(self.output_ceiling()) - true_peak_dbtp,
+        };
+
+        Ok((output, report))
+    }
+
+    /// Process audio data in place with noise reduction, normalization and effects
+    ///
+    /// Runs the same chain as [`AudioEngine::process`] without allocating an
+    /// output buffer, which suits real-time callbacks and WASM callers.
+    ///
+    /// With the default settings the chain itself does not allocate either.
+    /// A few settings need a temporary buffer as long as `buffer`:
+    /// - a limiter [`LimiterConfig::detection_window_ms`] or
+    ///   [`LimiterConfig::release_ms`] above zero, for the detection levels
+    /// - [`NormalizeMode::Percentile`], for the sample magnitudes
+    ///
+    /// Effects in the chain may allocate on their own; see each effect.
+    ///
+    /// The stages run in this order:
+    /// 1. Non-finite samples are handled according to `non_finite`
+    /// 2. Noise gate
+    /// 3. Effects
+    /// 4. Normalization to `target_peak`, only with `normalize_before_limiter`
+    /// 5. Limiter, which shapes the peaks and caps them at
+    ///    [`LimiterConfig::ceiling`]. It knows nothing about `target_peak`
+    /// 6. Normalization to `target_peak`, which scales the limited signal as a
+    ///    whole, so the limiter's curve is kept whatever the target
+    /// 7. Safety ceiling, if any, on the samples and then on the true peak
+    pub fn process_in_place(&self, buffer: &mut [f32]) -> Result<(), AudioProcessingError> {
+        if buffer.is_empty() {
             return Err(AudioProcessingError::EmptyBuffer);
         }
 
-        // Convert to Array1 for processing
-        let mut audio = Array1::from_vec(input);
-        
+        // NaN or infinite samples would poison every peak measurement below
+        self.sanitize_input(buffer)?;
+
         // Apply noise reduction
-        self.apply_noise_reduction(&mut audio)?;
-        
+        self.noise_gate(buffer);
+
         // Apply audio effects
-        self.apply_effects(audio.as_slice_mut().ok_or_else(|| 
-            AudioProcessingError::ProcessingError("Failed to get mutable slice".to_string())
-        )?)?;
-        
-        // Apply soft limiter before normalization to prevent clipping
-        self.apply_soft_limiter(audio.as_slice_mut().unwrap());
-        
+        self.apply_effects(buffer)?;
+
+        // Measure the post-effects peak and pre-scale, so the limiter only
+        // catches what is left above the target
+        if self.normalize_before_limiter {
+            self.normalize_samples(buffer);
+        }
+
+        // Shape the peaks; the limiter's ceiling is independent of the target
+        self.apply_soft_limiter(buffer);
+
         // Normalize audio (this will ensure the peak is at target_peak)
-        self.normalize_audio(&mut audio)?;
-        
-        Ok(audio.into_raw_vec())
+        self.normalize_samples(buffer);
+
+        // Enforce the hard ceiling, if any, as the very last step
+        self.apply_safety_ceiling(buffer);
+        self.apply_true_peak_ceiling(&mut [buffer]);
+
+        Ok(())
+    }
+
+    /// Process a pair of stereo channels in place
+    ///
+    /// Runs the same stages as [`AudioEngine::process_in_place`] with the two
+    /// channels linked: the noise gate threshold and the normalization gain
+    /// come from both channels together, so the stereo balance is kept. The
+    /// effects get both channels through [`AudioEffect::process_stereo`], so
+    /// stereo effects see them together; mono effects with state process the
+    /// right channel with their own copy (see [`AudioEngine::apply_effects_stereo`]).
+    /// The limiter runs on each channel separately.
+    ///
+    /// # Errors
+    /// [`AudioProcessingError::EmptyBuffer`] for empty channels, and
+    /// [`AudioProcessingError::ProcessingError`] for channels of different
+    /// lengths or for non-finite input with [`NonFiniteHandling::Error`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::AudioEngine;
+    /// use clearcast_core::effects::{AudioEffect, StereoWidener};
+    ///
+    /// let mut engine = AudioEngine::new();
+    /// engine.add_effect(StereoWidener::new(0.0).boxed());
+    /// let mut left = vec![0.5, 0.1, -0.3];
+    /// let mut right = vec![0.1, 0.4, 0.2];
+    /// engine.process_stereo(&mut left, &mut right).unwrap();
+    /// assert_eq!(left, right);
+    /// ```
+    pub fn process_stereo(&self, left: &mut [f32], right: &mut [f32]) -> Result<(), AudioProcessingError> {
+        if left.is_empty() || right.is_empty() {
+            return Err(AudioProcessingError::EmptyBuffer);
+        }
+        if left.len() != right.len() {
+            return Err(AudioProcessingError::ProcessingError(format!(
+                "Stereo channels differ in length: {} and {}",
+                left.len(),
+                right.len()
+            )));
+        }
+
+        self.sanitize_input(left)?;
+        self.sanitize_input(right)?;
+
+        let gate_threshold = self.noise_gate_threshold(left).max(self.noise_gate_threshold(right));
+        self.apply_noise_gate(left, gate_threshold);
+        self.apply_noise_gate(right, gate_threshold);
+
+        self.apply_effects_stereo(left, right)?;
+
+        if self.normalize_before_limiter {
+            if let Some(gain) = self.normalization_gain(&[&*left, &*right].concat()) {
+                self.apply_normalization_gain(left, gain);
+                self.apply_normalization_gain(right, gain);
+            }
+        }
+
+        self.apply_soft_limiter(left);
+        self.apply_soft_limiter(right);
+
+        if let Some(gain) = self.normalization_gain(&[&*left, &*right].concat()) {
+            self.apply_normalization_gain(left, gain);
+            self.apply_normalization_gain(right, gain);
+        }
+
+        self.apply_safety_ceiling(left);
+        self.apply_safety_ceiling(right);
+        self.apply_true_peak_ceiling(&mut [left, right]);
+
+        Ok(())
+    }
+
+    /// Process a long buffer in fixed-size chunks with the same result as [`AudioEngine::process`]
+    ///
+    /// The stages run one chunk at a time, so the limiter's scratch buffer
+    /// only grows with `chunk_size` and its detection window. The returned
+    /// `Vec` is still a copy of the whole input, so peak memory stays O(input
+    /// length); the chunking bounds the working buffers, not the output. The levels that
+    /// depend on the whole buffer (the noise gate threshold and the
+    /// normalization gain) are measured in a separate pass over all the
+    /// chunks first, and the resulting gain is applied to every chunk, so the
+    /// output level is consistent from the first chunk to the last. The
+    /// limiter's detection window also looks back into the previous chunk.
+    /// Percentile normalization still sorts the magnitudes of the whole buffer.
+    ///
+    /// # Errors
+    /// [`AudioProcessingError::EmptyBuffer`] for an empty input, and
+    /// [`AudioProcessingError::ProcessingError`] for a zero `chunk_size` or
+    /// for non-finite input with [`NonFiniteHandling::Error`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::AudioEngine;
+    ///
+    /// let engine = AudioEngine::new();
+    /// let input: Vec<f32> = (0..10_000).map(|i| 0.3 * (i as f32 * 0.01).sin()).collect();
+    /// let chunked = engine.process_chunked(&input, 1024).unwrap();
+    /// assert_eq!(chunked, engine.process(input).unwrap());
+    /// ```
+    pub fn process_chunked(&self, input: &[f32], chunk_size: usize) -> Result<Vec<f32>, AudioProcessingError> {
+        if input.is_empty() {
+            return Err(AudioProcessingError::EmptyBuffer);
+        }
+        if chunk_size == 0 {
+            return Err(AudioProcessingError::ProcessingError(
+                "Chunk size must be greater than zero".to_string()
+            ));
+        }
+
+        let mut output = input.to_vec();
+        self.sanitize_input(&mut output)?;
+
+        // Gate and effects, with the threshold taken from the peak of the whole input
+        let gate_threshold = self.noise_gate_threshold(&output);
+        for chunk in output.chunks_mut(chunk_size) {
+            self.apply_noise_gate(chunk, gate_threshold);
+            self.apply_effects(chunk)?;
+        }
+
+        if self.normalize_before_limiter {
+            if let Some(gain) = self.normalization_gain(&output) {
+                for chunk in output.chunks_mut(chunk_size) {
+                    self.apply_normalization_gain(chunk, gain);
+                }
+            }
+        }
+
+        // The detection window needs the unlimited tail of the previous chunk.
+        // One scratch buffer holds that tail followed by an unlimited copy of
+        // the current chunk, so its capacity never grows past the first chunk
+        let lookback = self.limiter.detection_window_samples().saturating_sub(1);
+        let mut unlimited: Vec<f32> = Vec::with_capacity(lookback + chunk_size);
+        let mut release = ReleaseEnvelope::default();
+        for chunk in output.chunks_mut(chunk_size) {
+            let history_len = unlimited.len();
+            unlimited.extend_from_slice(chunk);
+            self.limit_with_history(chunk, &unlimited[..history_len], &mut release);
+            unlimited.drain(..unlimited.len().saturating_sub(lookback));
+        }
+
+        let gain = self.normalization_gain(&output);
+        for chunk in output.chunks_mut(chunk_size) {
+            if let Some(gain) = gain {
+                self.apply_normalization_gain(chunk, gain);
+            }
+            self.apply_safety_ceiling(chunk);
+        }
+        self.apply_true_peak_ceiling(&mut [&mut output]);
+
+        Ok(output)
+    }
+
+    /// Read and process a batch of WAV files (requires the `wav` feature)
+    ///
+    /// Each file is read as mono with [`crate::io::read_wav`] and run through
+    /// [`AudioEngine::process`]. The effects are reset before every file, so
+    /// no delay tail or envelope carries over from one file to the next. The
+    /// files' sample rates are not checked against the engine's settings.
+    ///
+    /// # Returns
+    /// The processed samples of each file, in the order of `paths`
+    ///
+    /// # Errors
+    /// [`AudioProcessingError::ProcessingError`] naming the file if it cannot
+    /// be read, and whatever `process` returns for its samples (e.g.
+    /// [`AudioProcessingError::EmptyBuffer`] for a file without samples).
+    /// Processing stops at the first error.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use std::path::PathBuf;
+    /// use clearcast_core::AudioEngine;
+    ///
+    /// let engine = AudioEngine::voice_preset(44100.0);
+    /// let outputs = engine.process_files(&[PathBuf::from("intro.wav"), PathBuf::from("episode.wav")])?;
+    /// # Ok::<(), clearcast_core::AudioProcessingError>(())
+    /// ```
+    #[cfg(feature = "wav")]
+    pub fn process_files(&self, paths: &[PathBuf]) -> Result<Vec<Vec<f32>>, AudioProcessingError> {
+        paths
+            .iter()
+            .map(|path| {
+                let (samples, _) = crate::io::read_wav(path).map_err(|e| {
+                    AudioProcessingError::ProcessingError(format!("Cannot read {}: {}", path.display(), e))
+                })?;
+                for effect in &self.effects {
+                    lock_effect(effect)?.reset();
+                }
+                self.process(samples)
+            })
+            .collect()
     }
     
+    /// Process a single sample through the effects and the limiter
+    ///
+    /// Meant for real-time callbacks that push one sample at a time without
+    /// buffering. The stages that need the whole buffer are skipped: the noise
+    /// gate (its threshold is relative to the buffer peak) and normalization.
+    /// The limiter reacts to each sample on its own, ignoring
+    /// `detection_window_ms` and `release_ms`. With no normalization stage to
+    /// bring the level down, the output is capped at `target_peak` (or at
+    /// [`LimiterConfig::ceiling`], if that is lower).
+    /// Non-finite input becomes silence, and the safety ceiling, if any, still
+    /// applies.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::AudioEngine;
+    ///
+    /// let mut engine = AudioEngine::new();
+    /// let output: Vec<f32> = [0.1, 2.0, -0.3].iter().map(|&x| engine.process_sample(x)).collect();
+    /// assert!(output.iter().all(|x| x.abs() <= engine.target_peak()));
+    /// ```
+    pub fn process_sample(&mut self, x: f32) -> f32 {
+        let mut sample = if x.is_finite() { x } else { 0.0 };
+
+        // A poisoned effect is bypassed: there is no error to return here
+        for effect in &self.effects {
+            if let Ok(mut effect) = effect.lock() {
+                sample = effect.process_sample(sample);
+            }
+        }
+
+        let make_up = self.cached_make_up_factor();
+        sample = self.limit_sample(sample, make_up);
+        self.apply_safety_ceiling(std::slice::from_mut(&mut sample));
+        sample
+    }
+
+    /// The limiter's make-up factor, recomputed only when its settings change
+    fn cached_make_up_factor(&mut self) -> f32 {
+        match self.make_up_cache {
+            Some((limiter, make_up)) if limiter == self.limiter => make_up,
+            _ => {
+                let make_up = self.limiter.make_up_factor();
+                self.make_up_cache = Some((self.limiter, make_up));
+                make_up
+            }
+        }
+    }
+
     /// Add an audio effect to the processing chain
     pub fn add_effect(&mut self, effect: Arc<Mutex<dyn AudioEffect + Send + 'static>>) {
         self.effects.push(effect);
@@ -230,130 +1057,476 @@ impl AudioEngine {
     pub fn clear_effects(&mut self) {
         self.effects.clear();
     }
+
+    /// Reset the internal state of every effect in the chain
+    ///
+    /// Stateful effects such as `Delay` keep their buffers between calls to
+    /// `process`. Call this before processing an unrelated clip with the same
+    /// engine so the tail of the previous one doesn't leak into it.
+    pub fn reset(&mut self) {
+        for effect in &self.effects {
+            if let Ok(mut effect) = effect.lock() {
+                effect.reset();
+            }
+        }
+        // The right-channel copies are recreated from the reset effects
+        if let Ok(twins) = self.stereo_twins.get_mut() {
+            twins.clear();
+        }
+    }
     
     /// Apply all registered audio effects to the buffer
+    ///
+    /// # Errors
+    /// [`AudioProcessingError::ProcessingError`] if an effect's mutex was
+    /// poisoned by a thread that panicked while holding it.
     pub fn apply_effects(&self, buffer: &mut [f32]) -> Result<(), AudioProcessingError> {
         if self.effects.is_empty() {
             return Ok(());
         }
         
-        // Crear una copia temporal para procesar
-        let mut temp_buffer = buffer.to_vec();
-        
-        // Procesar cada efecto en la cadena
+        // Procesar cada efecto en la cadena directamente sobre el búfer
         for effect in &self.effects {
-            let mut effect = effect.lock().unwrap();
-            effect.process_buffer(&mut temp_buffer);
+            lock_effect(effect)?.process_buffer(buffer);
         }
         
-        // Copiar el resultado de vuelta al buffer de entrada
-        buffer.copy_from_slice(&temp_buffer);
-        
+        Ok(())
+    }
+
+    /// Apply all registered audio effects to a pair of stereo channels
+    ///
+    /// Effects that return a copy from [`AudioEffect::clone_box`] are dual
+    /// mono: the effect processes the left channel and the copy, kept by the
+    /// engine from call to call, the right one, so no state crosses between
+    /// channels. The other effects get both channels through
+    /// [`AudioEffect::process_stereo`]. Fails like
+    /// [`AudioEngine::apply_effects`] on a poisoned effect.
+    pub fn apply_effects_stereo(&self, left: &mut [f32], right: &mut [f32]) -> Result<(), AudioProcessingError> {
+        let mut twins = self.stereo_twins.lock().map_err(|_| {
+            AudioProcessingError::ProcessingError("effect lock poisoned".to_string())
+        })?;
+
+        // Pair each effect with its copy, creating the missing ones and
+        // dropping those of effects no longer in the chain
+        let mut paired = Vec::with_capacity(self.effects.len());
+        for effect in &self.effects {
+            let mut effect_guard = lock_effect(effect)?;
+            let mut twin = match twins.iter().position(|twin| Arc::ptr_eq(&twin.effect, effect)) {
+                Some(index) => twins.swap_remove(index),
+                None => StereoTwin {
+                    effect: Arc::clone(effect),
+                    right: effect_guard.clone_box().map(|mut copy| {
+                        copy.reset();
+                        copy
+                    }),
+                },
+            };
+            match twin.right.as_mut() {
+                Some(copy) => {
+                    effect_guard.process_buffer(left);
+                    copy.process_buffer(right);
+                }
+                None => effect_guard.process_stereo(left, right),
+            }
+            paired.push(twin);
+        }
+        *twins = paired;
+
         Ok(())
     }
 
     /// Apply noise reduction to the audio data
+    ///
+    /// Samples below `noise_reduction_threshold` times the buffer peak are
+    /// zeroed or attenuated depending on `noise_reduction_mode`.
     pub fn apply_noise_reduction(&self, audio: &mut Array1<f32>) -> Result<(), AudioProcessingError> {
         if audio.is_empty() {
             return Err(AudioProcessingError::EmptyBuffer);
         }
 
-        // Calculate the noise threshold based on the maximum amplitude
-        let max_amplitude = audio.iter()
-            .fold(0.0f32, |a, &b| a.max(b.abs()));
-            
-        let threshold = max_amplitude * self.noise_reduction_threshold;
+        self.noise_gate(Self::samples_mut(audio)?);
+        Ok(())
+    }
+
+    /// Noise gate relative to the buffer's maximum amplitude
+    fn noise_gate(&self, samples: &mut [f32]) {
+        let threshold = self.noise_gate_threshold(samples);
+        self.apply_noise_gate(samples, threshold);
+    }
+
+    /// Gate threshold for a buffer, relative to its maximum amplitude
+    fn noise_gate_threshold(&self, samples: &[f32]) -> f32 {
+        simd::peak_abs(samples) * self.noise_reduction_threshold
+    }
 
-        // Apply noise gate - only values strictly below threshold are zeroed out
+    /// Gate the samples against an absolute threshold
+    fn apply_noise_gate(&self, samples: &mut [f32], threshold: f32) {
+        // Apply noise gate - only values strictly below threshold are affected
         // Values at or above threshold are preserved
-        // We use a small epsilon to handle floating point imprecision
-        let epsilon = 1e-6;
-        for x in audio.iter_mut() {
-            if x.abs() < threshold - epsilon && x.abs() > 0.0 {
-                *x = 0.0;
-            }
+        let mode = self.noise_reduction_mode;
+        for x in samples.iter_mut() {
+            *x = mode.apply(*x, threshold);
         }
-
-        Ok(())
     }
 
 
     /// Apply soft limiting to audio samples
+    ///
+    /// With [`LimiterMode::HardClip`] samples are clamped to the threshold
+    /// instead, so no sample exceeds it before make-up gain. The output is
+    /// capped at [`LimiterConfig::ceiling`], not at the target peak; bringing
+    /// the level to the target is left to normalization. The release starts
+    /// at unity gain on every call.
     pub fn apply_soft_limiter(&self, samples: &mut [f32]) {
+        self.limit_with_history(samples, &[], &mut ReleaseEnvelope::default());
+    }
+
+    /// Soft limiting whose detection window also covers `history`
+    ///
+    /// `history` holds the unlimited samples that came right before `samples`,
+    /// and `release` the state of the release envelope after them.
+    fn limit_with_history(&self, samples: &mut [f32], history: &[f32], release: &mut ReleaseEnvelope) {
         let limiter = self.limiter;
-        let threshold = limiter.threshold;
-        let knee_width = limiter.knee_width;
-        let make_up_gain = 10.0f32.powf(limiter.make_up_gain / 20.0);
-        let ratio = limiter.ratio;
-        let _ratio_recip = 1.0 / ratio; // Not currently used, but kept for future use
-        
-        // Calculate knee parameters
-        let lower_threshold = threshold * (1.0 - knee_width);
-        let upper_threshold = threshold * (1.0 + knee_width);
-        
-        for sample in samples.iter_mut() {
-            let abs_sample = sample.abs();
-            
-            if abs_sample <= lower_threshold {
-                // Below knee, no limiting
-                *sample *= make_up_gain;
-            } else if abs_sample < upper_threshold {
-                // In knee region, apply soft knee
-                let knee = upper_threshold - lower_threshold;
-                let over = abs_sample - lower_threshold;
-                let compression = over / knee;
-                let target_gain = 1.0 + (ratio - 1.0) * compression * compression;
-                
-                *sample = sample.signum() * (lower_threshold + (abs_sample - lower_threshold) / target_gain) * make_up_gain;
-            } else {
-                // Above knee, apply full limiting
-                let over = abs_sample - threshold;
-                let limited = threshold + over / ratio;
-                *sample = sample.signum() * limited * make_up_gain;
+        let make_up_gain = limiter.make_up_factor();
+        let window = limiter.detection_window_samples();
+        let release_samples = limiter.release_samples();
+
+        if limiter.mode == LimiterMode::HardClip {
+            simd::clamp_in_place(samples, limiter.threshold);
+        } else if release_samples > 0 {
+            // The gain follows the static curve on the way down and the
+            // release envelope on the way back up
+            let levels = Self::detection_levels(samples, history, window);
+            for (sample, level) in samples.iter_mut().zip(levels) {
+                let target = if level > 0.0 { limiter.limited_level(level) / level } else { 1.0 };
+                *sample *= release.next(target.min(1.0), release_samples, limiter.release_shape);
             }
-            
-            // Ensure we don't exceed the target peak
-            if *sample > self.target_peak {
-                *sample = self.target_peak;
-            } else if *sample < -self.target_peak {
-                *sample = -self.target_peak;
+        } else if window > 1 {
+            // Gain reduction follows the recent peak, so a brief dip inside a
+            // loud passage keeps the same gain instead of releasing the limiter
+            let levels = Self::detection_levels(samples, history, window);
+            for (sample, level) in samples.iter_mut().zip(levels) {
+                if level > 0.0 {
+                    *sample *= limiter.limited_level(level) / level;
+                }
+            }
+        } else {
+            let lower_threshold = limiter.threshold * (1.0 - limiter.knee_width);
+            for sample in samples.iter_mut() {
+                let abs_sample = sample.abs();
+                if abs_sample > lower_threshold {
+                    *sample = sample.signum() * limiter.limited_level(abs_sample);
+                }
             }
         }
+
+        // Make-up gain and the ceiling are branch-free, so they run as
+        // separate vectorizable passes
+        simd::scale_in_place(samples, make_up_gain);
+        simd::clamp_in_place(samples, limiter.ceiling_with(make_up_gain));
     }
     
+    /// Level the limiter reacts to for each sample: its magnitude, or the
+    /// peak of the detection window extended back into `history`
+    fn detection_levels(samples: &[f32], history: &[f32], window: usize) -> Vec<f32> {
+        if window <= 1 {
+            samples.iter().map(|x| x.abs()).collect()
+        } else {
+            windowed_peak(samples, history, window)
+        }
+    }
+
+    /// Per-sample version of [`AudioEngine::apply_soft_limiter`] without the detection window or release
+    ///
+    /// Also capped at `target_peak`, as nothing normalizes the output afterwards.
+    fn limit_sample(&self, sample: f32, make_up: f32) -> f32 {
+        self.limiter.limit(sample, make_up).clamp(-self.target_peak, self.target_peak)
+    }
+
+    /// Predict the input gain that maximizes loudness without excessive limiting
+    ///
+    /// Normalizing to the target peak leaves the limiter idle, so the loudest
+    /// sample decides the level of the whole buffer. Gaining the input up
+    /// first lets the limiter shave the peaks and the final normalization
+    /// raise everything else. This returns the largest gain for which the
+    /// limiter reduces the input's peak by at most 3 dB, using the engine's
+    /// own limiter curve. Effects are not taken into account.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::AudioEngine;
+    ///
+    /// let engine = AudioEngine::with_settings(0.0, 0.95).unwrap();
+    /// let input = vec![0.1, -0.3, 0.05, 0.2];
+    /// let gain = engine.optimal_normalization_gain(&input);
+    /// let gained: Vec<f32> = input.iter().map(|x| x * gain).collect();
+    /// let output = engine.process(gained).unwrap();
+    /// ```
+    pub fn optimal_normalization_gain(&self, input: &[f32]) -> f32 {
+        let peak = simd::peak_abs(input);
+        if peak < f32::EPSILON {
+            return 1.0;
+        }
+
+        let make_up = self.limiter.make_up_factor();
+        if self.limiter_gain_reduction_db(MAX_PREDICTED_LEVEL, make_up) <= MAX_PREDICTED_GAIN_REDUCTION_DB {
+            return MAX_PREDICTED_LEVEL / peak;
+        }
+
+        // Gain reduction grows with level, so bisect (in the log domain) for
+        // the highest peak level that stays within the accepted reduction
+        let mut low = 1e-6f32;
+        let mut high = MAX_PREDICTED_LEVEL;
+        for _ in 0..48 {
+            let mid = (low * high).sqrt();
+            if self.limiter_gain_reduction_db(mid, make_up) <= MAX_PREDICTED_GAIN_REDUCTION_DB {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+
+        low / peak
+    }
+
+    /// Recommend a noise gate threshold and target peak for material like `reference`
+    ///
+    /// The noise floor is estimated as the mean RMS level of the quietest 10%
+    /// of frames, and the gate threshold is set 6 dB above it. The target peak
+    /// leaves room for the inter-sample peaks of the reference, so its true
+    /// peak lands at the default target of 0.95 after normalization. Empty or
+    /// silent references return the default settings.
+    ///
+    /// # Example
+    /// ```rust
+    /// use clearcast_core::engine::AudioEngine;
+    ///
+    /// let reference: Vec<f32> = (0..44100).map(|i| 0.5 * (i as f32 * 0.01).sin()).collect();
+    /// let config = AudioEngine::suggest_settings(&reference);
+    /// let engine = AudioEngine::with_settings(config.noise_reduction_threshold, config.target_peak).unwrap();
+    /// ```
+    pub fn suggest_settings(reference: &[f32]) -> EngineConfig {
+        let default = Self::new();
+        let mut config = EngineConfig {
+            noise_reduction_threshold: default.noise_reduction_threshold,
+            target_peak: default.target_peak,
+        };
+
+        let peak = simd::peak_abs(reference);
+        if peak <= 0.0 {
+            return config;
+        }
+
+        let mut levels: Vec<f32> = reference.chunks(SUGGESTION_FRAME).map(rms).collect();
+        levels.sort_by(f32::total_cmp);
+        let quietest = ((levels.len() as f32 * NOISE_FLOOR_FRACTION).ceil() as usize).max(1);
+        let noise_floor = levels[..quietest].iter().sum::<f32>() / quietest as f32;
+
+        config.noise_reduction_threshold = (NOISE_GATE_MARGIN * noise_floor / peak).clamp(0.0, 1.0);
+        config.target_peak = default.target_peak * peak / true_peak(reference).max(peak);
+        config
+    }
+
+    /// Gain reduction (in dB) the soft limiter applies to a sample of the given level
+    ///
+    /// `make_up` is the limiter's make-up factor, computed once by the caller.
+    fn limiter_gain_reduction_db(&self, level: f32, make_up: f32) -> f32 {
+        let limited = self.limiter.limit(level, make_up);
+        linear_to_db(level * make_up / limited.abs().max(f32::MIN_POSITIVE))
+    }
+
     /// Normalize audio to the target peak amplitude
     pub fn normalize_audio(&self, audio: &mut Array1<f32>) -> Result<(), AudioProcessingError> {
         if audio.is_empty() {
             return Err(AudioProcessingError::EmptyBuffer);
         }
 
-        // Find the current peak amplitude
-        let current_peak = audio.iter()
-            .fold(0.0f32, |max, &x| max.max(x.abs()));
-            
-        if current_peak < f32::EPSILON {
-            return Ok(());
+        self.normalize_samples(Self::samples_mut(audio)?);
+        Ok(())
+    }
+
+    /// Scale samples so their peak (or percentile) lands on the target peak
+    fn normalize_samples(&self, samples: &mut [f32]) {
+        if let Some(gain) = self.normalization_gain(samples) {
+            self.apply_normalization_gain(samples, gain);
+        }
+    }
+
+    /// Gain that brings the buffer's peak (or percentile) to the target peak
+    ///
+    /// Returns `None` for silent buffers, which are left untouched.
+    fn normalization_gain(&self, samples: &[f32]) -> Option<f32> {
+        // Find the current peak amplitude
+        let current_peak = match self.normalize_mode {
+            NormalizeMode::Peak if self.true_peak_normalization => true_peak(samples),
+            NormalizeMode::Peak => simd::peak_abs(samples),
+            NormalizeMode::Percentile(percentile) => magnitude_percentile(samples, percentile),
+            NormalizeMode::Rms(target_db) => {
+                let level = rms(samples);
+                if level < f32::EPSILON {
+                    return None;
+                }
+                return Some(db_to_linear(target_db) / level);
+            }
+        };
+
+        if current_peak < f32::EPSILON {
+            return None;
+        }
+
+        Some(self.target_peak / current_peak)
+    }
+
+    /// Apply a normalization gain, keeping what ends up above the target under it
+    fn apply_normalization_gain(&self, samples: &mut [f32], gain: f32) {
+        simd::scale_in_place(samples, gain);
+
+        match self.normalize_mode {
+            NormalizeMode::Peak => {}
+            // The outliers above the percentile (or the peaks of a loud RMS
+            // target) now exceed the target
+            NormalizeMode::Percentile(_) | NormalizeMode::Rms(_) => self.round_off_overshoot(samples),
+        }
+        
+        // Note: We're not applying soft limiting here as it can affect the peak level
+        // Soft limiting should be applied separately if needed
+    }
+
+    /// Bend the samples near and above the target peak under it with a soft knee
+    ///
+    /// The knee is memoryless, so chunked and whole-buffer processing agree;
+    /// the final clamp only catches what the knee's float rounding lets through.
+    fn round_off_overshoot(&self, samples: &mut [f32]) {
+        let knee = self.target_peak * OVERSHOOT_KNEE;
+        SoftLimiter::new(self.target_peak - knee, knee).process_buffer(samples);
+        simd::clamp_in_place(samples, self.target_peak);
+    }
+
+    /// Zero out or reject non-finite samples, depending on `non_finite`
+    fn sanitize_input(&self, samples: &mut [f32]) -> Result<(), AudioProcessingError> {
+        match self.non_finite {
+            NonFiniteHandling::Zero => {
+                for x in samples.iter_mut().filter(|x| !x.is_finite()) {
+                    *x = 0.0;
+                }
+                Ok(())
+            }
+            NonFiniteHandling::Error => match samples.iter().position(|x| !x.is_finite()) {
+                Some(index) => Err(AudioProcessingError::ProcessingError(format!(
+                    "Non-finite sample {} at index {}",
+                    samples[index], index
+                ))),
+                None => Ok(()),
+            },
+        }
+    }
+
+    /// The safety ceiling as enforced, if any
+    ///
+    /// A nonsensical ceiling falls back to full scale rather than disabling the guarantee.
+    fn effective_safety_ceiling(&self) -> Option<f32> {
+        self.safety_ceiling
+            .map(|ceiling| if ceiling.is_finite() { ceiling.abs().min(1.0) } else { 1.0 })
+    }
+
+    /// Level the output headroom is measured against: the safety ceiling, or the limiter's
+    fn output_ceiling(&self) -> f32 {
+        self.effective_safety_ceiling().unwrap_or_else(|| self.limiter.ceiling())
+    }
+
+    /// Clamp samples to the safety ceiling and silence non-finite values
+    fn apply_safety_ceiling(&self, samples: &mut [f32]) {
+        let Some(ceiling) = self.effective_safety_ceiling() else {
+            return;
+        };
+
+        for x in samples.iter_mut() {
+            *x = if x.is_finite() { x.clamp(-ceiling, ceiling) } else { 0.0 };
+        }
+    }
+
+    /// Bring the true peak of already clamped channels under the safety ceiling
+    ///
+    /// Clamping the samples can leave inter-sample peaks above the ceiling.
+    /// When the highest true peak of the channels exceeds it, all of them are
+    /// scaled by the same gain, which keeps the waveform and the stereo
+    /// balance; the final clamp only catches rounding.
+    fn apply_true_peak_ceiling(&self, channels: &mut [&mut [f32]]) {
+        let Some(ceiling) = self.effective_safety_ceiling() else {
+            return;
+        };
+
+        let peak = channels.iter().map(|channel| true_peak(channel)).fold(0.0f32, f32::max);
+        if peak > ceiling {
+            for channel in channels.iter_mut() {
+                simd::scale_in_place(channel, ceiling / peak);
+                simd::clamp_in_place(channel, ceiling);
+            }
+        }
+    }
+
+    /// Borrow an array's samples as a contiguous mutable slice
+    fn samples_mut(audio: &mut Array1<f32>) -> Result<&mut [f32], AudioProcessingError> {
+        audio.as_slice_mut().ok_or_else(||
+            AudioProcessingError::ProcessingError("Failed to get mutable slice".to_string())
+        )
+    }
+}
+
+/// Lock an effect of the chain, or fail if a panic poisoned its mutex
+fn lock_effect<'a>(
+    effect: &'a Mutex<dyn AudioEffect + Send + 'static>,
+) -> Result<MutexGuard<'a, dyn AudioEffect + Send + 'static>, AudioProcessingError> {
+    effect.lock().map_err(|_| {
+        AudioProcessingError::ProcessingError("effect lock poisoned".to_string())
+    })
+}
+
+/// Magnitude below which `percentile` percent of the finite samples fall
+///
+/// `percentile` is clamped to 0.0 to 100.0; NaN is treated as 100.0 (the peak).
+fn magnitude_percentile(samples: &[f32], percentile: f32) -> f32 {
+    let mut magnitudes: Vec<f32> = samples.iter().filter(|x| x.is_finite()).map(|x| x.abs()).collect();
+    if magnitudes.is_empty() {
+        return 0.0;
+    }
+
+    let percentile = if percentile.is_nan() { 100.0 } else { percentile.clamp(0.0, 100.0) };
+    let index = ((magnitudes.len() - 1) as f32 * percentile / 100.0).round() as usize;
+    *magnitudes.select_nth_unstable_by(index, f32::total_cmp).1
+}
+
+/// Highest absolute value over the last `window` samples (including the current one)
+///
+/// The window reaches back into `history`, the samples right before
+/// `samples`, without copying both into one buffer.
+fn windowed_peak(samples: &[f32], history: &[f32], window: usize) -> Vec<f32> {
+    let mut peaks = Vec::with_capacity(samples.len());
+    // Indices and magnitudes of candidate maxima, with decreasing magnitudes
+    let mut candidates: VecDeque<(usize, f32)> = VecDeque::with_capacity(window);
+
+    for (i, &x) in history.iter().chain(samples).enumerate() {
+        let magnitude = x.abs();
+        while candidates.back().is_some_and(|&(_, m)| m <= magnitude) {
+            candidates.pop_back();
         }
-        
-        // Calculate gain to normalize to target peak
-        let gain = self.target_peak / current_peak;
-        
-        // Apply gain
-        for x in audio.iter_mut() {
-            *x *= gain;
+        candidates.push_back((i, magnitude));
+        if candidates.front().is_some_and(|&(j, _)| j + window <= i) {
+            candidates.pop_front();
+        }
+        if i >= history.len() {
+            peaks.push(candidates[0].1);
         }
-        
-        // Note: We're not applying soft limiting here as it can affect the peak level
-        // Soft limiting should be applied separately if needed
-        
-        Ok(())
     }
+
+    peaks
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use approx::assert_relative_eq;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_audio_engine_creation() {
@@ -369,6 +1542,7 @@ mod tests {
             knee_width: 0.1,
             make_up_gain: 2.0,
             ratio: 10.0,
+            ..Default::default()
         };
         let _engine = AudioEngine::with_limiter(0.1, 0.9, limiter).unwrap();
         
@@ -391,6 +1565,45 @@ mod tests {
         };
         assert!(AudioEngine::with_limiter(0.1, 0.9, invalid_limiter).is_err());
     }
+
+    #[test]
+    fn test_reset_clears_effect_tails() {
+        use crate::effects::Delay;
+
+        // 10-sample echo: the impulse's echo falls after the end of the first clip
+        let mut engine = AudioEngine::with_settings(0.0, 0.9).unwrap();
+        engine.add_effect(Delay::new(10.0, 0.5, 1.0, 1.0, 1000).boxed());
+
+        let mut impulse = vec![0.0; 8];
+        impulse[0] = 1.0;
+        engine.process(impulse.clone()).unwrap();
+        let leaked = engine.process(vec![0.0; 32]).unwrap();
+        assert!(leaked.iter().any(|&x| x != 0.0), "The delay tail should carry over");
+
+        engine.process(impulse).unwrap();
+        engine.reset();
+        let fresh = engine.process(vec![0.0; 32]).unwrap();
+        assert!(fresh.iter().all(|&x| x == 0.0), "Echo leaked after reset: {:?}", fresh);
+    }
+
+    #[test]
+    fn test_setters_validate_range() {
+        let mut engine = AudioEngine::new();
+
+        engine.set_noise_reduction_threshold(0.2).unwrap();
+        engine.set_target_peak(0.7).unwrap();
+        assert_eq!(engine.noise_reduction_threshold(), 0.2);
+        assert_eq!(engine.target_peak(), 0.7);
+
+        for invalid in [-0.1, 1.1, f32::NAN] {
+            assert!(engine.set_noise_reduction_threshold(invalid).is_err());
+            assert!(engine.set_target_peak(invalid).is_err());
+        }
+
+        // Rejected values leave the previous settings in place
+        assert_eq!(engine.noise_reduction_threshold(), 0.2);
+        assert_eq!(engine.target_peak(), 0.7);
+    }
     
     #[test]
     fn test_process() {
@@ -551,6 +1764,7 @@ mod tests {
             knee_width: 0.2,  // 20% knee width
             make_up_gain: 0.0, // No make-up gain
             ratio: 10.0,      // 10:1 ratio for hard limiting
+            ..Default::default()
         };
         
         let engine = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
@@ -582,6 +1796,7 @@ mod tests {
             knee_width: 0.2,
             make_up_gain: 6.0, // +6dB make-up gain (2x linear)
             ratio: 10.0,
+            ..Default::default()
         };
         
         let engine = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
@@ -590,4 +1805,854 @@ mod tests {
         // With +6dB make-up gain, 0.1 should become ~0.2 (but may be less due to limiting)
         assert!(result[0] >= 0.1 * 2.0 * 0.9, "Make-up gain not applied correctly");
     }
+
+    #[test]
+    fn test_limiter_ceiling_is_independent_of_target_peak() {
+        // Picos por encima de escala completa sobre un cuerpo moderado
+        let input: Vec<f32> = (0..4096)
+            .map(|i| 0.4 * (i as f32 * 0.01).sin() + if i % 1024 == 512 { 1.5 } else { 0.0 })
+            .collect();
+        let limiter = LimiterConfig { threshold: 0.9, ratio: 4.0, ..Default::default() };
+        let quiet = AudioEngine::with_limiter(0.0, 0.3, limiter).unwrap();
+        let loud = AudioEngine::with_limiter(0.0, 0.95, limiter).unwrap();
+
+        // El limitador da lo mismo con cualquier objetivo, y respeta su techo
+        let mut limited = input.clone();
+        quiet.apply_soft_limiter(&mut limited);
+        let mut limited_loud = input.clone();
+        loud.apply_soft_limiter(&mut limited_loud);
+        assert_eq!(limited, limited_loud);
+        let limited_peak = limited.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert_eq!(limited_peak, limiter.ceiling());
+
+        // La normalización solo escala la salida del limitador: sin picos
+        // aplanados al objetivo, y el cuerpo conserva su proporción con ellos
+        let output = quiet.process(input).unwrap();
+        let scale = 0.3 / limited_peak;
+        for (y, x) in output.iter().zip(&limited) {
+            assert_relative_eq!(*y, x * scale, epsilon = 1e-6);
+        }
+        let at_peak = output.iter().filter(|x| x.abs() > 0.3 - 1e-6).count();
+        assert_eq!(at_peak, 4);
+    }
+
+    #[test]
+    fn test_auto_makeup_restores_rms() {
+        let tone = |freq: f32, i: usize| (2.0 * std::f32::consts::PI * freq * i as f32 / 44100.0).sin();
+        let signal: Vec<f32> = (0..44100).map(|i| 0.9 * tone(100.0, i) + 0.3 * tone(370.0, i)).collect();
+        let input_db = linear_to_db(rms(&signal));
+
+        for mode in [LimiterMode::Soft, LimiterMode::HardClip] {
+            let limiter = LimiterConfig { threshold: 0.5, ratio: 4.0, mode, ..Default::default() };
+
+            // Sin compensación el limitador deja la señal varios dB más baja
+            let engine = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
+            let mut limited = signal.clone();
+            engine.apply_soft_limiter(&mut limited);
+            assert!(linear_to_db(rms(&limited)) < input_db - 2.0, "{:?}", mode);
+
+            let engine = AudioEngine::with_limiter(0.0, 1.0, LimiterConfig { auto_makeup: true, ..limiter }).unwrap();
+            let mut compensated = signal.clone();
+            engine.apply_soft_limiter(&mut compensated);
+            let output_db = linear_to_db(rms(&compensated));
+            assert!((output_db - input_db).abs() < 1.0, "{:?}: {} dB vs {} dB", mode, output_db, input_db);
+        }
+    }
+
+    #[test]
+    fn test_true_peak_normalization() {
+        use crate::utils::true_peak_dbtp;
+        use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+        // Seno a fs/4 desfasado 45°: el pico real está entre muestras
+        let signal: Vec<f32> = (0..1024)
+            .map(|n| 0.3 * (FRAC_PI_2 * n as f32 + FRAC_PI_4).sin())
+            .collect();
+        let target_dbtp = 20.0 * 0.8f32.log10();
+
+        // Normalización por pico de muestra: los picos entre muestras superan el objetivo
+        let engine = AudioEngine::with_settings(0.0, 0.8).unwrap();
+        let mut audio = Array1::from_vec(signal.clone());
+        engine.normalize_audio(&mut audio).unwrap();
+        assert!(true_peak_dbtp(audio.as_slice().unwrap()) > target_dbtp + 1.0);
+
+        // Normalización por pico real: el dBTP queda en o por debajo del objetivo
+        let mut engine = AudioEngine::with_settings(0.0, 0.8).unwrap();
+        engine.true_peak_normalization = true;
+        let mut audio = Array1::from_vec(signal);
+        engine.normalize_audio(&mut audio).unwrap();
+        let output_dbtp = true_peak_dbtp(audio.as_slice().unwrap());
+        assert!(
+            output_dbtp <= target_dbtp + 1e-3,
+            "True peak {} dBTP exceeds target {} dBTP",
+            output_dbtp,
+            target_dbtp
+        );
+        assert!(output_dbtp > target_dbtp - 0.1);
+    }
+
+    #[test]
+    fn test_process_in_place_matches_process() {
+        use crate::effects::{AudioEffect, SoftLimiter};
+
+        let mut engine = AudioEngine::with_settings(0.05, 0.9).unwrap();
+        engine.add_effect(SoftLimiter::new(0.7, 0.1).boxed());
+
+        let input: Vec<f32> = (0..2048)
+            .map(|i| 1.2 * (i as f32 * 0.05).sin() + 0.01 * (i as f32 * 1.3).cos())
+            .collect();
+
+        let allocated = engine.process(input.clone()).unwrap();
+
+        let mut in_place = input;
+        engine.process_in_place(&mut in_place).unwrap();
+
+        assert_eq!(allocated, in_place);
+
+        // Empty buffers are rejected the same way
+        assert!(matches!(
+            engine.process_in_place(&mut []).unwrap_err(),
+            AudioProcessingError::EmptyBuffer
+        ));
+    }
+
+    #[test]
+    fn test_process_chunked_matches_process() {
+        use crate::effects::{AudioEffect, Delay};
+
+        // Un eco con estado, limitador con ventana de detección y picos por
+        // encima del umbral, para que todas las etapas crucen las fronteras
+        let limiter = LimiterConfig {
+            threshold: 0.5,
+            detection_window_ms: 2.0,
+            sample_rate: 44100.0,
+            ..Default::default()
+        };
+        let mut engine = AudioEngine::with_limiter(0.05, 0.8, limiter).unwrap();
+        engine.normalize_before_limiter = true;
+        engine.add_effect(Delay::new(10.0, 0.4, 0.6, 0.4, 44100).boxed());
+
+        // La parte más fuerte está al final, así que cada bloque por separado
+        // se normalizaría a otro nivel
+        let input: Vec<f32> = (0..20_000)
+            .map(|i| (i as f32 / 20_000.0) * 1.5 * (i as f32 * 0.03).sin() + 0.005 * (i as f32 * 1.7).cos())
+            .collect();
+
+        let whole = engine.process(input.clone()).unwrap();
+        let peak = whole.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+        assert_relative_eq!(peak, 0.8, epsilon = 1e-6);
+
+        for chunk_size in [1, 100, 1000, 4096, 20_000, 50_000] {
+            engine.reset();
+            let chunked = engine.process_chunked(&input, chunk_size).unwrap();
+            assert_eq!(chunked, whole, "chunk size {}", chunk_size);
+        }
+
+        // También con normalización por percentil
+        engine.normalize_mode = NormalizeMode::Percentile(99.0);
+        engine.reset();
+        let whole = engine.process(input.clone()).unwrap();
+        engine.reset();
+        assert_eq!(engine.process_chunked(&input, 777).unwrap(), whole);
+
+        assert!(matches!(engine.process_chunked(&[], 64), Err(AudioProcessingError::EmptyBuffer)));
+        assert!(engine.process_chunked(&input, 0).is_err());
+    }
+
+    #[test]
+    fn test_process_report_headroom() {
+        let signal: Vec<f32> = (0..4096).map(|i| 0.3 * (i as f32 * 0.01).sin()).collect();
+
+        // Salida normalizada justo al techo del limitador, que no es 0 dBFS:
+        // casi sin margen
+        let ceiling = LimiterConfig::default().ceiling();
+        assert!(linear_to_db(ceiling) < -0.5);
+        let engine = AudioEngine::with_settings(0.0, ceiling).unwrap();
+        let (output, report) = engine.process_with_report(signal.clone()).unwrap();
+        assert_eq!(output.len(), signal.len());
+        assert!(report.headroom_db.abs() < 0.1, "Expected ~0 dB headroom, got {}", report.headroom_db);
+        assert!((report.peak - ceiling).abs() < 1e-3);
+
+        // Salida baja: mucho margen hasta el techo del limitador
+        let engine = AudioEngine::with_settings(0.0, 0.1).unwrap();
+        let (_, report) = engine.process_with_report(signal.clone()).unwrap();
+        let expected = linear_to_db(ceiling) + 20.0;
+        assert!((report.headroom_db - expected).abs() < 0.5, "Expected ~{} dB headroom, got {}", expected, report.headroom_db);
+        assert!(report.true_peak_dbtp < -19.5);
+
+        // Con techo de seguridad el margen se mide contra él (-6 dBFS de
+        // salida bajo un techo de -3 dBFS => ~3 dB)
+        let engine = AudioEngine {
+            safety_ceiling: Some(db_to_linear(-3.0)),
+            ..AudioEngine::with_settings(0.0, db_to_linear(-6.0)).unwrap()
+        };
+        let (_, report) = engine.process_with_report(signal).unwrap();
+        assert!((report.headroom_db - 3.0).abs() < 0.1, "Expected ~3 dB headroom, got {}", report.headroom_db);
+    }
+
+    #[test]
+    fn test_safety_never_exceeds_ceiling() {
+        use crate::effects::{AudioEffect, Delay};
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(285);
+        let mut inputs: Vec<Vec<f32>> = (0..50)
+            .map(|_| {
+                let len = rng.gen_range(1..2048);
+                let scale = 10.0f32.powf(rng.gen_range(-4.0..3.0));
+                (0..len).map(|_| rng.gen_range(-scale..scale)).collect()
+            })
+            .collect();
+
+        // Casos patológicos
+        inputs.push(vec![1e30; 64]);
+        inputs.push(vec![f32::MAX, f32::MIN, f32::MAX, f32::MIN]);
+        inputs.push(vec![f32::INFINITY, 0.5, f32::NEG_INFINITY, f32::NAN]);
+        inputs.push(vec![f32::MIN_POSITIVE; 16]);
+        inputs.push((0..1024).map(|n| if n % 2 == 0 { 1.0 } else { -1.0 }).collect());
+        inputs.push((0..1024).map(|n| (std::f32::consts::FRAC_PI_2 * n as f32 + 0.785).sin()).collect());
+        inputs.push(vec![0.0; 32]);
+
+        // Ajustes hostiles aplicados después de construir el motor
+        let mut hostile = AudioEngine::with_safety();
+        hostile.set_target_peak(1.0).unwrap();
+        hostile.true_peak_normalization = false;
+        hostile.limiter.make_up_gain = 24.0;
+        hostile.limiter.ratio = 1.0;
+        hostile.add_effect(Delay::new(1.0, 0.99, 1.0, 1.0, 44100).boxed());
+
+        for engine in [AudioEngine::with_safety(), hostile] {
+            for input in &inputs {
+                let output = engine.process(input.clone()).unwrap();
+                for &x in &output {
+                    assert!(
+                        x.is_finite() && x.abs() <= SAFETY_CEILING,
+                        "Output sample {} exceeds the safety ceiling {}",
+                        x,
+                        SAFETY_CEILING
+                    );
+                }
+                let peak = true_peak(&output);
+                assert!(peak <= SAFETY_CEILING + 1e-5, "True peak {} exceeds the safety ceiling", peak);
+            }
+        }
+    }
+
+    #[test]
+    fn test_safety_ceiling_covers_inter_sample_peaks() {
+        // Seno a fs/4 muestreado a 45° de sus crestas: todas las muestras
+        // valen ±0.707 del pico real, que cae siempre entre dos muestras
+        let input: Vec<f32> = (0..1024)
+            .map(|n| (std::f32::consts::FRAC_PI_2 * n as f32 + std::f32::consts::FRAC_PI_4).sin())
+            .collect();
+        assert!(true_peak(&input) > 1.3 * simd::peak_abs(&input));
+
+        // Normalizando contra el pico de muestra y recortando solo las
+        // muestras, el pico real quedaría un 41% por encima del techo
+        let mut engine = AudioEngine::with_safety();
+        engine.set_target_peak(1.0).unwrap();
+        engine.true_peak_normalization = false;
+
+        let output = engine.process(input.clone()).unwrap();
+        let peak = true_peak(&output);
+        assert!(peak <= SAFETY_CEILING + 1e-5, "True peak {} exceeds the safety ceiling", peak);
+        assert!(peak > 0.99 * SAFETY_CEILING);
+
+        // Igual en estéreo y por bloques
+        let (mut left, mut right) = (input.clone(), input.clone());
+        engine.process_stereo(&mut left, &mut right).unwrap();
+        assert!(true_peak(&left) <= SAFETY_CEILING + 1e-5);
+        let chunked = engine.process_chunked(&input, 100).unwrap();
+        assert!(true_peak(&chunked) <= SAFETY_CEILING + 1e-5);
+    }
+
+    #[test]
+    fn test_optimal_normalization_gain() {
+        // Voz simulada: cuerpo a bajo nivel con algunos picos aislados
+        let mut signal: Vec<f32> = (0..8192).map(|i| 0.1 * (i as f32 * 0.07).sin()).collect();
+        for i in (500..8192).step_by(1500) {
+            signal[i] = 0.5;
+        }
+
+        let rms = |x: &[f32]| (x.iter().map(|v| v * v).sum::<f32>() / x.len() as f32).sqrt();
+        let engine = AudioEngine::with_settings(0.0, 0.95).unwrap();
+
+        let gain = engine.optimal_normalization_gain(&signal);
+        assert!(gain > 1.0, "Sparse peaks should leave room to gain up, got {}", gain);
+
+        let default_output = engine.process(signal.clone()).unwrap();
+        let gained: Vec<f32> = signal.iter().map(|x| x * gain).collect();
+        let optimal_output = engine.process(gained.clone()).unwrap();
+
+        // Más volumen con el mismo pico de salida
+        assert!(rms(&optimal_output) > rms(&default_output) * 1.2);
+        let peak = optimal_output.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert!(peak <= 0.95 + 1e-6);
+
+        // El limitador no trabaja más de lo previsto sobre los picos
+        let mut limited = gained.clone();
+        engine.apply_soft_limiter(&mut limited);
+        let input_peak = gained.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        let limited_peak = limited.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        let reduction_db = 20.0 * (input_peak / limited_peak).log10();
+        assert!(reduction_db <= MAX_PREDICTED_GAIN_REDUCTION_DB + 0.01, "Gain reduction {} dB", reduction_db);
+        assert!(reduction_db > MAX_PREDICTED_GAIN_REDUCTION_DB - 0.5);
+
+        // Silencio: ganancia neutra
+        assert_eq!(engine.optimal_normalization_gain(&[0.0; 16]), 1.0);
+    }
+
+    #[test]
+    fn test_limiter_detection_window() {
+        // Pasaje fuerte con una sola muestra baja en medio
+        let mut signal = vec![1.0f32; 256];
+        signal[128] = 0.5;
+
+        let limiter = LimiterConfig { threshold: 0.5, ratio: 10.0, ..Default::default() };
+        let gain_at = |engine: &AudioEngine, i: usize| {
+            let mut output = signal.clone();
+            engine.apply_soft_limiter(&mut output);
+            output[i] / signal[i]
+        };
+
+        // Sin ventana, la muestra baja queda fuera del limitador: la ganancia salta
+        let instant = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
+        let loud_gain = gain_at(&instant, 127);
+        assert!(loud_gain < 0.7);
+        assert!(gain_at(&instant, 128) > loud_gain + 0.2);
+
+        // Con ventana, la muestra baja recibe la misma reducción que sus vecinas
+        let windowed = AudioEngine::with_limiter(
+            0.0,
+            1.0,
+            LimiterConfig { detection_window_ms: 1.0, sample_rate: 44100.0, ..limiter },
+        )
+        .unwrap();
+        assert_relative_eq!(gain_at(&windowed, 128), gain_at(&windowed, 127), epsilon = 1e-6);
+        assert_relative_eq!(gain_at(&windowed, 127), loud_gain, epsilon = 1e-6);
+
+        // Ventanas negativas no son válidas
+        let invalid = LimiterConfig { detection_window_ms: -1.0, ..Default::default() };
+        assert!(AudioEngine::with_limiter(0.0, 1.0, invalid).is_err());
+    }
+
+    #[test]
+    fn test_limiter_release_shapes() {
+        // Un pico muy por encima del umbral seguido de señal baja: release de
+        // 20 ms a 1 kHz, es decir 20 muestras
+        let mut input = vec![0.1; 40];
+        input[0] = 1.0;
+        let gains = |shape: ReleaseShape| {
+            let limiter = LimiterConfig {
+                threshold: 0.5,
+                knee_width: 0.0,
+                ratio: 20.0,
+                release_ms: 20.0,
+                release_shape: shape,
+                sample_rate: 1000.0,
+                ..Default::default()
+            };
+            let engine = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
+            let mut output = input.clone();
+            engine.apply_soft_limiter(&mut output);
+            output.iter().zip(&input).map(|(y, x)| y / x).collect::<Vec<f32>>()
+        };
+        let linear = gains(ReleaseShape::Linear);
+        let logarithmic = gains(ReleaseShape::Logarithmic);
+
+        // Ambas parten de la reducción del pico
+        assert_relative_eq!(linear[0], 0.525, epsilon = 1e-6);
+        assert_relative_eq!(logarithmic[0], 0.525, epsilon = 1e-6);
+
+        // La logarítmica recupera más al principio y avanza más despacio al final
+        assert!(logarithmic[3] > linear[3] + 0.1, "{} vs {}", logarithmic[3], linear[3]);
+        let last_step = |g: &[f32]| g[20] - g[19];
+        assert!(last_step(&logarithmic) < last_step(&linear));
+        assert!(linear.windows(2).all(|w| w[1] >= w[0]));
+        assert!(logarithmic.windows(2).all(|w| w[1] >= w[0]));
+
+        // Las dos llegan a ganancia unidad al terminar el release
+        assert!(linear[20..].iter().all(|&g| (g - 1.0).abs() < 1e-6));
+        assert!(logarithmic[20..].iter().all(|&g| (g - 1.0).abs() < 1e-6));
+
+        let invalid = LimiterConfig { release_ms: -1.0, ..Default::default() };
+        assert!(AudioEngine::with_limiter(0.0, 1.0, invalid).is_err());
+    }
+
+    #[test]
+    fn test_process_stereo_links_channels() {
+        use crate::effects::{AudioEffect, Delay, StereoWidener};
+
+        let left: Vec<f32> = (0..1000).map(|i| 0.5 * (i as f32 * 0.05).sin()).collect();
+        let right: Vec<f32> = left.iter().rev().copied().collect();
+
+        // Un efecto mono con estado procesa cada canal con su propia instancia:
+        // el eco de un impulso en un canal no aparece en el otro
+        let impulse = |at: usize| -> Vec<f32> { (0..40).map(|i| if i == at { 0.5 } else { 0.0 }).collect() };
+        let mut engine = AudioEngine::new();
+        engine.add_effect(Delay::new(10.0, 0.0, 0.5, 1.0, 1000).boxed());
+        let (mut l, mut r) = (impulse(15), vec![0.0; 40]);
+        engine.process_stereo(&mut l, &mut r).unwrap();
+        assert!(l[25] > 0.1, "Missing echo on the left: {:?}", l);
+        assert!(r.iter().all(|&x| x == 0.0), "Left echo leaked into the right: {:?}", r);
+
+        // Tampoco pasa del canal derecho al izquierdo del bloque siguiente
+        let (mut l, mut r) = (vec![0.0; 40], impulse(35));
+        engine.process_stereo(&mut l, &mut r).unwrap();
+        assert!(l.iter().all(|&x| x == 0.0), "Right state leaked into the left: {:?}", l);
+        let (mut l, mut r) = (vec![0.0; 40], vec![0.0; 40]);
+        engine.process_stereo(&mut l, &mut r).unwrap();
+        assert!(r[5] > 0.1, "Missing right echo in the next block: {:?}", r);
+        assert!(l.iter().all(|&x| x == 0.0));
+
+        // Un efecto estéreo ve los dos canales a la vez: con anchura 0 quedan iguales
+        let mut engine = AudioEngine::new();
+        engine.add_effect(StereoWidener::new(0.0).boxed());
+        let (mut l, mut r) = (left.clone(), right.clone());
+        engine.process_stereo(&mut l, &mut r).unwrap();
+        assert_eq!(l, r);
+        assert!(l.iter().zip(&left).any(|(y, x)| (y - x).abs() > 0.1));
+
+        // La normalización enlazada conserva el balance entre canales
+        let engine = AudioEngine::new();
+        let (mut l, mut r) = (left.clone(), right.iter().map(|x| x * 0.5).collect::<Vec<f32>>());
+        engine.process_stereo(&mut l, &mut r).unwrap();
+        let peak = |x: &[f32]| x.iter().fold(0.0f32, |max, v| max.max(v.abs()));
+        assert_relative_eq!(peak(&r) / peak(&l), 0.5, epsilon = 1e-4);
+
+        assert!(engine.process_stereo(&mut [0.1; 4], &mut [0.1; 3]).is_err());
+        assert!(engine.process_stereo(&mut [], &mut []).is_err());
+    }
+
+    #[test]
+    fn test_hard_clip_limiter_never_exceeds_threshold() {
+        let input: Vec<f32> = (0..1000).map(|i| 1.2 * (i as f32 * 0.05).sin()).collect();
+
+        // La curva suave deja picos por encima del umbral
+        let limiter = LimiterConfig { threshold: 0.7, ..Default::default() };
+        let soft = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
+        let mut output = input.clone();
+        soft.apply_soft_limiter(&mut output);
+        assert!(output.iter().any(|x| x.abs() > 0.7));
+
+        let hard = AudioEngine::with_limiter(
+            0.0,
+            1.0,
+            LimiterConfig { mode: LimiterMode::HardClip, ..limiter },
+        )
+        .unwrap();
+        let mut output = input.clone();
+        hard.apply_soft_limiter(&mut output);
+        let peak = output.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert_eq!(peak, 0.7);
+
+        // Lo que queda bajo el umbral no se toca
+        for (y, x) in output.iter().zip(&input) {
+            if x.abs() <= 0.7 {
+                assert_eq!(y, x);
+            }
+        }
+    }
+
+    #[test]
+    fn test_suggest_settings_threshold_between_noise_and_signal() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(324);
+        let noise_amplitude = 0.01;
+
+        // Dos segundos de ruido de fondo con un tono fuerte en el segundo central
+        let clip: Vec<f32> = (0..88200)
+            .map(|i| {
+                let noise = rng.gen_range(-noise_amplitude..noise_amplitude);
+                let signal = if (29400..58800).contains(&i) { 0.6 * (i as f32 * 0.06).sin() } else { 0.0 };
+                signal + noise
+            })
+            .collect();
+        let noise_rms = noise_amplitude / 3.0f32.sqrt();
+        let signal_rms = 0.6 / 2.0f32.sqrt();
+
+        let config = AudioEngine::suggest_settings(&clip);
+        let peak = clip.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        let threshold = config.noise_reduction_threshold * peak;
+        assert!(threshold > noise_rms && threshold < signal_rms, "Threshold {}", threshold);
+        // Los bordes abruptos del tono añaden picos entre muestras
+        assert!(config.target_peak > 0.8 && config.target_peak <= 0.95, "{:?}", config);
+        assert!(AudioEngine::with_settings(config.noise_reduction_threshold, config.target_peak).is_ok());
+
+        // Sin señal no hay nada que analizar
+        let silent = AudioEngine::suggest_settings(&[0.0; 64]);
+        assert_eq!(silent.noise_reduction_threshold, AudioEngine::new().noise_reduction_threshold());
+    }
+
+    #[test]
+    fn test_percentile_normalization_ignores_spike() {
+        let mut input: Vec<f32> = (0..44100).map(|i| 0.1 * (i as f32 * 0.03).sin()).collect();
+        input[20000] = 1.0;
+        let bulk_peak = |output: &[f32]| {
+            output.iter().enumerate().filter(|&(i, _)| i != 20000).fold(0.0f32, |max, (_, x)| max.max(x.abs()))
+        };
+
+        let engine = AudioEngine::with_settings(0.0, 0.95).unwrap();
+        let mut peak_normalized = input.clone();
+        engine.normalize_samples(&mut peak_normalized);
+
+        let percentile_engine = AudioEngine {
+            normalize_mode: NormalizeMode::Percentile(99.0),
+            ..AudioEngine::with_settings(0.0, 0.95).unwrap()
+        };
+        let mut percentile_normalized = input.clone();
+        percentile_engine.normalize_samples(&mut percentile_normalized);
+
+        // El pico aislado decide la ganancia en modo pico: el resto queda en 0.095
+        assert!((bulk_peak(&peak_normalized) - 0.095).abs() < 1e-3);
+        // En modo percentil el grueso de la señal sube casi hasta el objetivo
+        assert!(bulk_peak(&percentile_normalized) > 5.0 * bulk_peak(&peak_normalized));
+        assert!(bulk_peak(&percentile_normalized) > 0.9);
+        // y el pico se dobla bajo el objetivo con una rodilla suave
+        assert!(percentile_normalized.iter().all(|x| x.abs() <= 0.95));
+        assert!(percentile_normalized[20000] > bulk_peak(&percentile_normalized));
+    }
+
+    #[test]
+    fn test_percentile_overshoot_goes_through_soft_knee() {
+        let engine = AudioEngine {
+            normalize_mode: NormalizeMode::Percentile(80.0),
+            ..AudioEngine::with_settings(0.0, 0.9).unwrap()
+        };
+
+        // Rampa lineal: el 20% superior queda por encima del objetivo tras la ganancia
+        let mut ramp: Vec<f32> = (1..=600).map(|i| i as f32 / 1000.0).collect();
+        let gain = 0.9 / magnitude_percentile(&ramp, 80.0);
+        engine.normalize_samples(&mut ramp);
+
+        // Por debajo de la rodilla la ganancia es lineal
+        assert_relative_eq!(ramp[199], 0.2 * gain, epsilon = 1e-5);
+        // Por encima la curva sigue subiendo sin llegar al objetivo ni
+        // aplanarse: no hay dos muestras seguidas iguales
+        assert!(ramp.iter().all(|&x| x < 0.9));
+        assert!(ramp.windows(2).all(|w| w[1] > w[0]));
+        let knee_start = 0.9 * 0.9;
+        let in_knee = ramp.iter().filter(|&&x| x > knee_start && x < 0.89).count();
+        assert!(in_knee > 10, "Only {} samples in the knee", in_knee);
+    }
+
+    #[test]
+    fn test_voice_preset() {
+        let sample_rate = 44100.0;
+        let engine = AudioEngine::voice_preset(sample_rate);
+        let names: Vec<&str> = engine.effects.iter().map(|e| e.lock().unwrap().name()).collect();
+        assert_eq!(names, ["ButterworthFilter", "Compressor", "DeEsser"]);
+        assert_eq!(engine.normalize_mode, NormalizeMode::Rms(-16.0));
+        assert_eq!(engine.noise_reduction_mode, NoiseReductionMode::SoftGate);
+
+        // Voz sintética: tono armónico de 150 Hz con sílabas de 4 Hz, un
+        // retumbo de 30 Hz y ruido de fondo
+        let mut rng = rand::rngs::StdRng::seed_from_u64(369);
+        let voice: Vec<f32> = (0..2 * 44100)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                let phase = 2.0 * std::f32::consts::PI * 150.0 * t;
+                let syllables = 0.5 + 0.5 * (2.0 * std::f32::consts::PI * 4.0 * t).sin().abs();
+                let tone = 0.2 * phase.sin() + 0.1 * (2.0 * phase).sin() + 0.05 * (3.0 * phase).sin();
+                syllables * tone
+                    + 0.1 * (2.0 * std::f32::consts::PI * 30.0 * t).sin()
+                    + rng.gen_range(-0.01..0.01)
+            })
+            .collect();
+
+        let output = engine.process(voice).unwrap();
+        let level_db = linear_to_db(rms(&output));
+        assert!((-19.0..=-15.0).contains(&level_db), "Output at {:.1} dBFS RMS", level_db);
+        assert!(output.iter().all(|x| x.abs() <= engine.target_peak()));
+
+        // A una frecuencia de muestreo sin sitio para el paso alto se omite
+        assert_eq!(AudioEngine::voice_preset(100.0).effects.len(), 2);
+    }
+
+    #[test]
+    fn test_rms_normalization_does_not_pin_peaks() {
+        // Tono armónico con sílabas de 4 Hz y algo de ruido, con un factor de
+        // cresta normal para la voz: a -3 dBFS RMS los picos superan el
+        // objetivo de 0.95
+        let sample_rate = 44100.0;
+        let mut rng = rand::rngs::StdRng::seed_from_u64(369);
+        let input: Vec<f32> = (0..44100)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                let phase = 2.0 * std::f32::consts::PI * 150.0 * t;
+                let syllables = 0.5 + 0.5 * (2.0 * std::f32::consts::PI * 4.0 * t).sin().abs();
+                let tone = 0.2 * phase.sin() + 0.1 * (2.0 * phase).sin() + 0.05 * (3.0 * phase).sin();
+                syllables * tone + rng.gen_range(-0.01..0.01)
+            })
+            .collect();
+        let engine = AudioEngine {
+            normalize_mode: NormalizeMode::Rms(-3.0),
+            normalize_before_limiter: true,
+            ..AudioEngine::new()
+        };
+        let target_peak = engine.target_peak();
+        let boosted_peak = simd::peak_abs(&input) * db_to_linear(-3.0) / rms(&input);
+        assert!(boosted_peak > target_peak, "Boosted peak {:.3}", boosted_peak);
+
+        let output = engine.process(input).unwrap();
+        assert!(output.iter().all(|x| x.abs() <= target_peak));
+        // Ningún tramo de muestras seguidas queda aplanado en el pico
+        let output_peak = simd::peak_abs(&output);
+        let flat = |x: &f32| x.abs() >= output_peak - 1e-6;
+        let longest_run = output
+            .iter()
+            .fold((0, 0), |(run, longest), x| {
+                let run = if flat(x) { run + 1 } else { 0 };
+                (run, longest.max(run))
+            })
+            .1;
+        assert!(longest_run < 2, "{} samples in a row flattened at {:.3}", longest_run, output_peak);
+        let level_db = linear_to_db(rms(&output));
+        assert!((-5.0..=-2.0).contains(&level_db), "Output at {:.1} dBFS RMS", level_db);
+    }
+
+    #[test]
+    fn test_normalize_before_limiter_with_boosting_eq() {
+        use crate::filters::GraphicEq;
+
+        let sample_rate = 44100.0;
+        let input: Vec<f32> = (0..8192)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 500.0 * i as f32 / sample_rate).sin())
+            .collect();
+        // +12 dB a 500 Hz: la señal sale del ecualizador muy por encima de 1.0
+        let boosted_eq = || {
+            let mut eq = GraphicEq::iso_10_band(sample_rate);
+            eq.set_gain(4, 12.0);
+            eq
+        };
+
+        let mut reference = input.clone();
+        boosted_eq().process_buffer(&mut reference);
+        let reference_peak = simd::peak_abs(&reference);
+        assert!(reference_peak > 1.5);
+
+        // Objetivo por debajo de la rodilla del limitador (0.81)
+        let target = 0.8;
+        let mut engine = AudioEngine::with_settings(0.0, target).unwrap();
+        engine.normalize_before_limiter = true;
+        engine.add_effect(boosted_eq().boxed());
+        let output = engine.process(input.clone()).unwrap();
+
+        // La salida es la señal ecualizada escalada, sin que el limitador la toque
+        assert_relative_eq!(simd::peak_abs(&output), target, epsilon = 1e-6);
+        for (out, eq) in output.iter().zip(&reference) {
+            assert!((out - eq * target / reference_peak).abs() < 1e-5);
+        }
+
+        // En el orden por defecto el limitador aplasta los picos antes de normalizar
+        let mut engine = AudioEngine::with_settings(0.0, target).unwrap();
+        engine.add_effect(boosted_eq().boxed());
+        let squashed = engine.process(input).unwrap();
+        let max_diff = squashed
+            .iter()
+            .zip(&reference)
+            .fold(0.0f32, |max, (out, eq)| max.max((out - eq * target / reference_peak).abs()));
+        assert!(max_diff > 0.05, "Limiter left the shape intact ({})", max_diff);
+    }
+
+    #[test]
+    fn test_poisoned_effect_returns_error() {
+        use crate::effects::Gain;
+
+        // Un hilo que entra en pánico con el cerrojo tomado envenena el mutex
+        let effect = Gain::new(0.0).boxed();
+        let shared = Arc::clone(&effect);
+        let result = std::thread::spawn(move || {
+            let _guard = shared.lock().unwrap();
+            panic!("effect panicked");
+        })
+        .join();
+        assert!(result.is_err());
+        assert!(effect.is_poisoned());
+
+        let mut engine = AudioEngine::new();
+        engine.add_effect(effect);
+        match engine.process(vec![0.1, 0.5, -0.3]) {
+            Err(AudioProcessingError::ProcessingError(msg)) => assert_eq!(msg, "effect lock poisoned"),
+            other => panic!("Expected a ProcessingError, got {:?}", other),
+        }
+        assert!(engine.process_stereo(&mut [0.1, 0.5], &mut [0.2, 0.3]).is_err());
+
+        // Muestra a muestra no hay error que devolver: el efecto se salta
+        assert!(engine.process_sample(0.5).is_finite());
+    }
+
+    #[cfg(feature = "wav")]
+    #[test]
+    fn test_process_files_resets_between_files() {
+        use crate::effects::Delay;
+        use crate::io::write_wav;
+
+        let temp_path = |name: &str| {
+            std::env::temp_dir().join(format!("clearcast_batch_{}_{}.wav", name, std::process::id()))
+        };
+        let tone = |amplitude: f32, len: usize| -> Vec<f32> {
+            (0..len).map(|i| amplitude * (i as f32 * 0.05).sin()).collect()
+        };
+        let paths = [temp_path("first"), temp_path("second")];
+        write_wav(&paths[0], &tone(0.8, 44100), 44100).unwrap();
+        write_wav(&paths[1], &tone(0.2, 22050), 44100).unwrap();
+
+        // Un delay largo: su cola llegaría al segundo fichero si no se reiniciara
+        let mut engine = AudioEngine::with_settings(0.0, 0.95).unwrap();
+        engine.add_effect(Delay::new(300.0, 0.6, 0.5, 1.0, 44100).boxed());
+        let outputs = engine.process_files(&paths);
+
+        // Referencia: el segundo fichero con un motor recién creado
+        let mut fresh = AudioEngine::with_settings(0.0, 0.95).unwrap();
+        fresh.add_effect(Delay::new(300.0, 0.6, 0.5, 1.0, 44100).boxed());
+        let (second, _) = crate::io::read_wav(&paths[1]).unwrap();
+        let expected = fresh.process(second).unwrap();
+        for path in &paths {
+            std::fs::remove_file(path).ok();
+        }
+
+        let outputs = outputs.unwrap();
+        assert_eq!(outputs.len(), 2);
+        assert_eq!(outputs[0].len(), 44100);
+        assert_eq!(outputs[1].len(), 22050);
+        assert_eq!(outputs[1], expected);
+
+        // Un fichero que no existe da un error con su nombre
+        match engine.process_files(&[temp_path("missing")]) {
+            Err(AudioProcessingError::ProcessingError(msg)) => assert!(msg.contains("clearcast_batch_missing")),
+            other => panic!("Expected a ProcessingError, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_process_sample_matches_buffer_chain() {
+        use crate::effects::{PolarityInvert, Saturation};
+
+        let input: Vec<f32> = (0..2048).map(|i| 1.4 * (i as f32 * 0.02).sin() + 0.1 * (i as f32 * 0.7).sin()).collect();
+
+        for mode in [LimiterMode::Soft, LimiterMode::HardClip] {
+            let limiter = LimiterConfig { mode, make_up_gain: 1.0, ..Default::default() };
+            // Objetivo en escala completa: muestra a muestra solo recorta el techo del limitador
+            let mut engine = AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap();
+            // Cadena sin estado: el resultado no depende de cómo se trocea la señal
+            engine.add_effect(Saturation::new(2.0, 0.5).boxed());
+            engine.add_effect(PolarityInvert.boxed());
+
+            let mut expected = input.clone();
+            engine.apply_effects(&mut expected).unwrap();
+            engine.apply_soft_limiter(&mut expected);
+
+            let output: Vec<f32> = input.iter().map(|&x| engine.process_sample(x)).collect();
+            assert_eq!(output, expected, "{:?}", mode);
+        }
+
+        let mut engine = AudioEngine::new();
+        assert_eq!(engine.process_sample(f32::NAN), 0.0);
+
+        // Sin normalización que la baje, la salida no pasa del pico objetivo
+        let mut quiet = AudioEngine::with_settings(0.0, 0.3).unwrap();
+        let output: Vec<f32> = input.iter().map(|&x| quiet.process_sample(x)).collect();
+        assert!(output.iter().all(|x| x.abs() <= 0.3));
+        assert!(output.iter().any(|x| x.abs() == 0.3));
+    }
+
+    #[test]
+    fn test_process_sample_follows_limiter_changes() {
+        let mut engine = AudioEngine::with_settings(0.0, 1.0).unwrap();
+        let plain = engine.process_sample(0.95);
+
+        // El factor de compensación se guarda en caché, pero cambiar el
+        // limitador tiene que invalidarlo
+        engine.limiter.auto_makeup = true;
+        let make_up = engine.limiter.make_up_factor();
+        assert!(make_up > 1.0);
+        let with_make_up = engine.process_sample(0.95);
+        assert!(with_make_up > plain);
+        assert_relative_eq!(with_make_up, engine.limiter.limit(0.95, make_up), epsilon = 1e-6);
+        assert_eq!(engine.process_sample(0.95), with_make_up);
+
+        engine.limiter.auto_makeup = false;
+        assert_eq!(engine.process_sample(0.95), plain);
+    }
+
+    #[test]
+    fn test_non_finite_input_is_zeroed() {
+        let mut input: Vec<f32> = (0..256).map(|i| 0.5 * (i as f32 * 0.1).sin()).collect();
+        let poisoned = [3, 100, 200];
+        input[poisoned[0]] = f32::NAN;
+        input[poisoned[1]] = f32::INFINITY;
+        input[poisoned[2]] = f32::NEG_INFINITY;
+
+        let engine = AudioEngine::new();
+        let output = engine.process(input.clone()).unwrap();
+        assert!(output.iter().all(|x| x.is_finite()));
+        for &i in &poisoned {
+            assert_eq!(output[i], 0.0, "Sample {} should be zeroed", i);
+        }
+
+        // The finite samples are still normalized to the target peak
+        let peak = output.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert_relative_eq!(peak, engine.target_peak(), epsilon = 1e-4);
+
+        let strict = AudioEngine { non_finite: NonFiniteHandling::Error, ..AudioEngine::new() };
+        assert!(matches!(
+            strict.process(input.clone()),
+            Err(AudioProcessingError::ProcessingError(_))
+        ));
+
+        // The Array1 helpers measure the peak on finite samples only
+        let mut audio = Array1::from(input);
+        engine.normalize_audio(&mut audio).unwrap();
+        let finite_peak = audio.iter().filter(|x| x.is_finite()).fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert_relative_eq!(finite_peak, engine.target_peak(), epsilon = 1e-4);
+    }
+
+    #[test]
+    fn test_soft_gate_is_continuous() {
+        // Ramp from -1.0 to 1.0: with a 20% threshold, everything within ±0.2 is sub-threshold
+        let ramp: Vec<f32> = (0..=400).map(|i| i as f32 / 200.0 - 1.0).collect();
+        let step = 1.0 / 200.0;
+        let threshold = 0.2;
+
+        let mut engine = AudioEngine::with_settings(threshold, 1.0).unwrap();
+        let mut gated = Array1::from(ramp.clone());
+        engine.apply_noise_reduction(&mut gated).unwrap();
+
+        engine.noise_reduction_mode = NoiseReductionMode::SoftGate;
+        let mut soft = Array1::from(ramp.clone());
+        engine.apply_noise_reduction(&mut soft).unwrap();
+
+        let max_jump = |x: &Array1<f32>| {
+            x.iter().zip(x.iter().skip(1)).fold(0.0f32, |max, (a, b)| max.max((b - a).abs()))
+        };
+
+        // The hard gate zeroes the whole sub-threshold region and jumps at its edges
+        let below: Vec<usize> = (0..ramp.len()).filter(|&i| ramp[i].abs() < threshold - 1e-3).collect();
+        assert!(below.iter().all(|&i| gated[i] == 0.0));
+        assert!(max_jump(&gated) > threshold - 2.0 * step);
+
+        // The soft gate follows a continuous curve: no step is larger than the ramp's own
+        assert!(max_jump(&soft) <= 3.0 * step + 1e-6, "Max jump {}", max_jump(&soft));
+        for &i in &below {
+            let expected = ramp[i] * (ramp[i].abs() / threshold).powi(2);
+            assert!((soft[i] - expected).abs() < 1e-6);
+            if ramp[i] != 0.0 {
+                assert_ne!(soft[i], 0.0);
+            }
+        }
+
+        // Samples above the threshold are untouched by either mode
+        for i in (0..ramp.len()).filter(|&i| ramp[i].abs() >= threshold) {
+            assert_eq!(gated[i], ramp[i]);
+            assert_eq!(soft[i], ramp[i]);
+        }
+    }
+
+    #[test]
+    fn test_windowed_peak() {
+        let samples = [0.1, -0.9, 0.2, 0.3, 0.0, -0.4];
+        assert_eq!(windowed_peak(&samples, &[], 1), vec![0.1, 0.9, 0.2, 0.3, 0.0, 0.4]);
+        assert_eq!(windowed_peak(&samples, &[], 3), vec![0.1, 0.9, 0.9, 0.9, 0.3, 0.4]);
+        assert_eq!(windowed_peak(&samples[3..], &samples[..3], 3), vec![0.9, 0.3, 0.4]);
+    }
 }
@@ -0,0 +1,360 @@
+//! Procesamiento por bloques con estado persistente
+//!
+//! `AudioEngine::process` está pensado para búferes completos: la reducción de
+//! ruido y la normalización se calculan a partir del pico de todo el búfer. En un
+//! callback de tiempo real se reciben bloques pequeños (p. ej. 128 muestras) y
+//! normalizar cada bloque por separado produce bombeo de nivel y clics en las
+//! fronteras. `StreamingEngine` mantiene un seguidor de picos y una ganancia
+//! suavizada entre llamadas para que la salida sea continua.
+
+use std::collections::VecDeque;
+
+use super::{AudioEngine, AudioProcessingError, ReleaseEnvelope};
+
+/// Default time for the normalization gain to settle (in milliseconds)
+const DEFAULT_GAIN_SMOOTHING_MS: f32 = 50.0;
+/// Default decay time of the peak detector's lookback (in milliseconds)
+const DEFAULT_PEAK_RELEASE_MS: f32 = 500.0;
+/// Maximum gain the normalizer may apply (+20 dB) to avoid amplifying silence
+const MAX_NORMALIZATION_GAIN: f32 = 10.0;
+/// How far the normalizer's peak detector runs ahead of the output (in milliseconds)
+const LOOKAHEAD_MS: f32 = 5.0;
+/// Attack time constants that fit in the look-ahead: the gain is within 1% of
+/// its new value by the time an onset reaches the output
+const ATTACK_TIME_CONSTANTS: f32 = 5.0;
+
+/// Block-based wrapper around [`AudioEngine`] for real-time callbacks
+///
+/// Effects keep their own state between calls, and the noise gate and
+/// normalizer use a running peak estimate instead of the per-block maximum,
+/// so feeding a signal in fixed-size blocks produces the same output as
+/// feeding it in a single call. The normalizer looks ahead, so the output is
+/// delayed by [`StreamingEngine::latency`] samples.
+///
+/// # Example
+/// ```rust
+/// use clearcast_core::{AudioEngine, StreamingEngine};
+///
+/// let mut stream = StreamingEngine::new(AudioEngine::new(), 44100.0);
+/// let mut block = vec![0.1f32; 128];
+/// stream.process_block(&mut block).unwrap();
+/// ```
+pub struct StreamingEngine {
+    engine: AudioEngine,
+    peak_release_coeff: f32,
+    gain_smoothing_coeff: f32,
+    attack_coeff: f32,
+    lookahead: VecDeque<f32>,
+    input_envelope: f32,
+    peak_envelope: f32,
+    gain: f32,
+    limiter_release: ReleaseEnvelope,
+    /// Unlimited tail of the previous blocks, for the limiter's detection window
+    limiter_history: Vec<f32>,
+}
+
+impl StreamingEngine {
+    /// Creates a streaming wrapper with default smoothing times
+    ///
+    /// # Arguments
+    /// * `engine` - Configured engine whose effects and settings are used
+    /// * `sample_rate` - Sample rate of the stream in Hz
+    pub fn new(engine: AudioEngine, sample_rate: f32) -> Self {
+        Self::with_smoothing(
+            engine,
+            sample_rate,
+            DEFAULT_GAIN_SMOOTHING_MS,
+            DEFAULT_PEAK_RELEASE_MS,
+        )
+    }
+
+    /// Creates a streaming wrapper with custom smoothing times
+    ///
+    /// # Arguments
+    /// * `engine` - Configured engine whose effects and settings are used
+    /// * `sample_rate` - Sample rate of the stream in Hz
+    /// * `gain_smoothing_ms` - Time for the normalization gain to recover
+    ///   after a peak; it comes down within the look-ahead
+    /// * `peak_release_ms` - Decay time of the running peak estimate
+    pub fn with_smoothing(
+        engine: AudioEngine,
+        sample_rate: f32,
+        gain_smoothing_ms: f32,
+        peak_release_ms: f32,
+    ) -> Self {
+        Self {
+            engine,
+            peak_release_coeff: time_constant_coeff(peak_release_ms, sample_rate),
+            gain_smoothing_coeff: time_constant_coeff(gain_smoothing_ms, sample_rate),
+            attack_coeff: time_constant_coeff(LOOKAHEAD_MS / ATTACK_TIME_CONSTANTS, sample_rate),
+            lookahead: VecDeque::from(vec![0.0; lookahead_samples(sample_rate)]),
+            input_envelope: 0.0,
+            peak_envelope: 0.0,
+            gain: 1.0,
+            limiter_release: ReleaseEnvelope::default(),
+            limiter_history: Vec::new(),
+        }
+    }
+
+    /// Delay of the output relative to the input, in samples
+    pub fn latency(&self) -> usize {
+        self.lookahead.len()
+    }
+
+    /// Returns the wrapped engine
+    pub fn engine(&self) -> &AudioEngine {
+        &self.engine
+    }
+
+    /// Returns the wrapped engine mutably (e.g. to add effects)
+    pub fn engine_mut(&mut self) -> &mut AudioEngine {
+        &mut self.engine
+    }
+
+    /// Clears the running peak, the smoothed gain, the look-ahead, the limiter
+    /// release and history, and the state of every effect
+    pub fn reset(&mut self) {
+        self.lookahead.iter_mut().for_each(|x| *x = 0.0);
+        self.input_envelope = 0.0;
+        self.peak_envelope = 0.0;
+        self.gain = 1.0;
+        self.limiter_release = ReleaseEnvelope::default();
+        self.limiter_history.clear();
+        self.engine.reset();
+    }
+
+    /// Processes one block of audio in place
+    ///
+    /// The block can be of any size; state is carried over to the next call.
+    /// The normalizer's peak detector runs [`StreamingEngine::latency`]
+    /// samples ahead of the output, so the gain has already come down when an
+    /// onset is output. What little still overshoots the target is rounded
+    /// off by a soft knee below the target peak rather than clipped.
+    ///
+    /// # Errors
+    /// Returns the error of the first effect that fails; the block is then
+    /// left partly processed.
+    pub fn process_block(&mut self, block: &mut [f32]) -> Result<(), AudioProcessingError> {
+        if block.is_empty() {
+            return Ok(());
+        }
+
+        // Noise gate relative to the running peak rather than the block peak
+        let noise_threshold = self.engine.noise_reduction_threshold;
+        let noise_mode = self.engine.noise_reduction_mode;
+        if noise_threshold > 0.0 {
+            for sample in block.iter_mut() {
+                self.input_envelope = self.track_peak(self.input_envelope, sample.abs());
+                *sample = noise_mode.apply(*sample, self.input_envelope * noise_threshold);
+            }
+        }
+
+        // Effects keep their own state across calls
+        self.engine.apply_effects(block)?;
+
+        // The soft limiter's release is carried over, and its detection window
+        // looks back into the unlimited tail of the previous blocks, as in
+        // process_chunked
+        let lookback = self.engine.limiter.detection_window_samples().saturating_sub(1);
+        self.limiter_history.drain(..self.limiter_history.len().saturating_sub(lookback));
+        let history_len = self.limiter_history.len();
+        self.limiter_history.extend_from_slice(block);
+        self.engine.limit_with_history(
+            block,
+            &self.limiter_history[..history_len],
+            &mut self.limiter_release,
+        );
+
+        // Smoothed normalization towards the target peak, applied to the
+        // samples leaving the look-ahead
+        let target_peak = self.engine.target_peak;
+        for sample in block.iter_mut() {
+            self.peak_envelope = self.track_peak(self.peak_envelope, sample.abs());
+            self.lookahead.push_back(*sample);
+            let delayed = self.lookahead.pop_front().unwrap_or(0.0);
+
+            let target_gain = if self.peak_envelope > f32::EPSILON {
+                (target_peak / self.peak_envelope).min(MAX_NORMALIZATION_GAIN)
+            } else {
+                self.gain
+            };
+            let coeff = if target_gain < self.gain { self.attack_coeff } else { self.gain_smoothing_coeff };
+            self.gain = target_gain + coeff * (self.gain - target_gain);
+
+            *sample = delayed * self.gain;
+        }
+        self.engine.round_off_overshoot(block);
+
+        self.engine.apply_safety_ceiling(block);
+        Ok(())
+    }
+
+    /// Instant-attack peak detector with exponential release
+    fn track_peak(&self, envelope: f32, level: f32) -> f32 {
+        if level > envelope {
+            level
+        } else {
+            envelope * self.peak_release_coeff
+        }
+    }
+}
+
+/// Length of the normalizer's look-ahead in samples
+fn lookahead_samples(sample_rate: f32) -> usize {
+    (LOOKAHEAD_MS * 0.001 * sample_rate).round().max(0.0) as usize
+}
+
+/// One-pole smoothing coefficient for the given time constant
+fn time_constant_coeff(time_ms: f32, sample_rate: f32) -> f32 {
+    if time_ms <= 0.0 || sample_rate <= 0.0 {
+        return 0.0;
+    }
+    (-1.0 / (time_ms * 0.001 * sample_rate)).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::effects::{AudioEffect, Delay};
+    use crate::engine::LimiterConfig;
+
+    fn test_signal(num_samples: usize, sample_rate: f32) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                // Tono de 220 Hz con una envolvente lenta
+                let envelope = 0.3 + 0.2 * (2.0 * std::f32::consts::PI * 0.5 * t).sin();
+                envelope * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+            })
+            .collect()
+    }
+
+    fn streaming_engine(sample_rate: f32) -> StreamingEngine {
+        let mut engine = AudioEngine::with_settings(0.0, 0.9).unwrap();
+        engine.add_effect(Delay::new(5.0, 0.3, 0.3, 0.7, sample_rate as u32).boxed());
+        StreamingEngine::new(engine, sample_rate)
+    }
+
+    #[test]
+    fn test_block_processing_matches_whole_buffer() {
+        let sample_rate = 44100.0;
+        let signal = test_signal(sample_rate as usize, sample_rate);
+
+        // Procesar la señal completa de una sola vez
+        let mut whole = signal.clone();
+        streaming_engine(sample_rate).process_block(&mut whole).unwrap();
+
+        // Procesar la misma señal en bloques de 128 muestras
+        let mut stream = streaming_engine(sample_rate);
+        let mut blocks = signal.clone();
+        for block in blocks.chunks_mut(128) {
+            stream.process_block(block).unwrap();
+        }
+
+        for (i, (&a, &b)) in whole.iter().zip(blocks.iter()).enumerate() {
+            assert!((a - b).abs() < 1e-6, "Mismatch at sample {}: {} vs {}", i, a, b);
+        }
+
+        // No debe haber saltos en las fronteras de bloque mayores que los del resto de la señal
+        let max_step = blocks.windows(2).map(|w| (w[1] - w[0]).abs()).fold(0.0f32, f32::max);
+        for boundary in (128..blocks.len()).step_by(128) {
+            let step = (blocks[boundary] - blocks[boundary - 1]).abs();
+            assert!(step <= max_step, "Discontinuity at block boundary {}: {}", boundary, step);
+        }
+        assert!(max_step < 0.1, "Output has a discontinuity: {}", max_step);
+    }
+
+    #[test]
+    fn test_detection_window_spans_block_boundaries() {
+        let sample_rate = 44100.0;
+        let limiter = LimiterConfig::default().with_detection_window(5.0, sample_rate);
+        let stream = || StreamingEngine::new(AudioEngine::with_limiter(0.0, 0.9, limiter).unwrap(), sample_rate);
+        // Tono fuerte que entra y sale del limitador a lo largo de varios bloques
+        let signal: Vec<f32> = test_signal(sample_rate as usize / 2, sample_rate)
+            .iter()
+            .map(|x| 2.5 * x)
+            .collect();
+
+        let mut whole = signal.clone();
+        stream().process_block(&mut whole).unwrap();
+
+        for block_size in [32, 100, 128] {
+            let mut streaming = stream();
+            let mut blocks = signal.clone();
+            for block in blocks.chunks_mut(block_size) {
+                streaming.process_block(block).unwrap();
+            }
+            // Mismas operaciones en el mismo orden: la salida es idéntica
+            assert_eq!(blocks, whole, "Block size {}", block_size);
+        }
+    }
+
+    #[test]
+    fn test_streaming_reaches_target_peak() {
+        let sample_rate = 44100.0;
+        let mut stream = streaming_engine(sample_rate);
+        let mut signal = test_signal(sample_rate as usize * 2, sample_rate);
+        for block in signal.chunks_mut(128) {
+            stream.process_block(block).unwrap();
+        }
+
+        let tail = &signal[signal.len() / 2..];
+        let peak = tail.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+        assert!(peak <= 0.9 + 1e-6, "Peak {} exceeds target", peak);
+        assert!(peak > 0.7, "Normalizer should bring the signal close to target, got {}", peak);
+    }
+
+    #[test]
+    fn test_onsets_follow_whole_buffer_processing() {
+        let sample_rate = 44100.0;
+        // Ráfagas de 220 Hz que arrancan de golpe tras un fondo casi en silencio
+        let burst = |t: f32| (t * 2.0) as usize % 2 == 1;
+        let signal: Vec<f32> = (0..2 * 44100)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                let level = if burst(t) { 0.5 } else { 0.02 };
+                level * (2.0 * std::f32::consts::PI * 220.0 * t).sin()
+            })
+            .collect();
+
+        let whole = AudioEngine::with_settings(0.0, 0.9).unwrap().process(signal.clone()).unwrap();
+        let mut stream = StreamingEngine::new(AudioEngine::with_settings(0.0, 0.9).unwrap(), sample_rate);
+        let mut streamed = signal.clone();
+        for block in streamed.chunks_mut(128) {
+            stream.process_block(block).unwrap();
+        }
+        let latency = stream.latency();
+        assert!(latency > 0);
+
+        // Como en process, los arranques no se recortan: ninguna muestra
+        // alcanza el objetivo, ni mucho menos varias seguidas
+        assert!(whole.iter().all(|x| x.abs() <= 0.9 + 1e-6));
+        assert!(streamed.iter().all(|x| x.abs() < 0.9), "Onset clipped at the target peak");
+
+        // Dentro de cada ráfaga la salida sigue a la de process con el retardo
+        // de la anticipación, también justo después del arranque
+        for (i, &expected) in whole.iter().enumerate().take(whole.len() - latency) {
+            if burst(i as f32 / sample_rate) {
+                let actual = streamed[i + latency];
+                assert!((actual - expected).abs() < 0.06, "Sample {}: {} vs {}", i, actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_reset_clears_state() {
+        let sample_rate = 44100.0;
+        let mut stream = streaming_engine(sample_rate);
+        let mut first = test_signal(4096, sample_rate);
+        stream.process_block(&mut first).unwrap();
+
+        stream.reset();
+        let mut after_reset = test_signal(4096, sample_rate);
+        stream.process_block(&mut after_reset).unwrap();
+
+        let mut fresh = test_signal(4096, sample_rate);
+        streaming_engine(sample_rate).process_block(&mut fresh).unwrap();
+
+        assert_eq!(after_reset, fresh);
+    }
+}
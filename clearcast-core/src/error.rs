@@ -0,0 +1,53 @@
+//! Tipo de error común de la biblioteca
+//!
+//! Solo necesita `alloc`. Los errores sin asignación del núcleo `no_std`
+//! (como [`crate::filters::EqError`]) se convierten en él con `?`.
+
+use alloc::string::{String, ToString};
+use core::fmt;
+
+use crate::filters::EqError;
+
+/// Tipos de error para operaciones de procesamiento de audio
+///
+/// Este enum define los posibles errores que pueden ocurrir durante el
+/// procesamiento de audio, permitiendo un manejo de errores detallado.
+///
+/// # Ejemplos
+/// ```rust
+/// use clearcast_core::{AudioEngine, AudioProcessingError};
+///
+/// let engine = AudioEngine::new();
+/// match engine.process(vec![]) {
+///     Err(AudioProcessingError::EmptyBuffer) => {
+///         println!("Error: Se proporcionó un búfer vacío");
+///     }
+///     Err(e) => println!("Error inesperado: {}", e),
+///     Ok(_) => println!("Procesamiento exitoso"),
+/// }
+/// ```
+#[derive(Debug)]
+pub enum AudioProcessingError {
+    /// Error that occurs when an empty buffer is provided
+    EmptyBuffer,
+    /// Error that occurs during audio processing
+    ProcessingError(String),
+}
+
+impl fmt::Display for AudioProcessingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyBuffer => write!(f, "Empty audio buffer provided"),
+            Self::ProcessingError(message) => write!(f, "Audio processing error: {}", message),
+        }
+    }
+}
+
+impl From<EqError> for AudioProcessingError {
+    fn from(error: EqError) -> Self {
+        Self::ProcessingError(error.to_string())
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for AudioProcessingError {}
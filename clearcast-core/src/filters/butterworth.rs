@@ -0,0 +1,198 @@
+//! Butterworth low-pass and high-pass filters of configurable order
+//!
+//! An order-N Butterworth filter is built as a cascade of N/2 biquads sharing
+//! the cutoff frequency, each with the Q of one conjugate pair of poles. The
+//! response is maximally flat in the passband, -3 dB at the cutoff, and rolls
+//! off at 6·N dB per octave beyond it.
+//!
+//! [`ButterworthFilter`] keeps the filter state between calls, so it can run
+//! as an effect in a chain; the free functions filter a whole buffer at once.
+
+use std::f32::consts::PI;
+
+use biquad::frequency::*;
+use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType};
+
+use crate::effects::AudioEffect;
+use crate::AudioProcessingError;
+
+/// Highest supported filter order
+const MAX_ORDER: usize = 8;
+
+/// Applies a Butterworth low-pass filter
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `cutoff_hz` - Cutoff (-3 dB) frequency in Hz, strictly between 0 Hz and Nyquist
+/// * `order` - Filter order: 2, 4, 6 or 8 (6 dB per octave of slope per order)
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Returns
+/// New filtered buffer, or a `ProcessingError` for an unsupported order or a
+/// cutoff outside the valid range
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::butterworth_lowpass;
+///
+/// let input: Vec<f32> = (0..1024).map(|i| (i as f32 * 0.05).sin()).collect();
+/// let output = butterworth_lowpass(&input, 1000.0, 4, 44100.0).unwrap();
+/// assert_eq!(output.len(), input.len());
+/// assert!(butterworth_lowpass(&input, 1000.0, 3, 44100.0).is_err());
+/// ```
+pub fn butterworth_lowpass(
+    input: &[f32],
+    cutoff_hz: f32,
+    order: usize,
+    sample_rate: f32,
+) -> Result<Vec<f32>, AudioProcessingError> {
+    let mut filter = ButterworthFilter::lowpass(cutoff_hz, order, sample_rate)?;
+    Ok(input.iter().map(|&x| filter.process_sample(x)).collect())
+}
+
+/// Applies a Butterworth high-pass filter
+///
+/// The mirror image of [`butterworth_lowpass`], with the same arguments and errors.
+pub fn butterworth_highpass(
+    input: &[f32],
+    cutoff_hz: f32,
+    order: usize,
+    sample_rate: f32,
+) -> Result<Vec<f32>, AudioProcessingError> {
+    let mut filter = ButterworthFilter::highpass(cutoff_hz, order, sample_rate)?;
+    Ok(input.iter().map(|&x| filter.process_sample(x)).collect())
+}
+
+/// Butterworth low-pass or high-pass filter that keeps its state between calls
+///
+/// # Example
+/// ```
+/// use clearcast_core::effects::AudioEffect;
+/// use clearcast_core::filters::ButterworthFilter;
+///
+/// // Rumble filter for voice: 12 dB per octave below 80 Hz
+/// let mut highpass = ButterworthFilter::highpass(80.0, 2, 44100.0).unwrap();
+/// let mut buffer = vec![0.5; 64];
+/// highpass.process_buffer(&mut buffer);
+/// assert!(buffer[63].abs() < 0.5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct ButterworthFilter {
+    /// One biquad per conjugate pair of poles, run in series
+    stages: Vec<DirectForm1<f32>>,
+}
+
+impl ButterworthFilter {
+    /// Creates a low-pass filter; see [`butterworth_lowpass`] for the arguments and errors
+    pub fn lowpass(cutoff_hz: f32, order: usize, sample_rate: f32) -> Result<Self, AudioProcessingError> {
+        Self::new(FilterType::LowPass, cutoff_hz, order, sample_rate)
+    }
+
+    /// Creates a high-pass filter; see [`butterworth_highpass`] for the arguments and errors
+    pub fn highpass(cutoff_hz: f32, order: usize, sample_rate: f32) -> Result<Self, AudioProcessingError> {
+        Self::new(FilterType::HighPass, cutoff_hz, order, sample_rate)
+    }
+
+    fn new(
+        filter_type: FilterType<f32>,
+        cutoff_hz: f32,
+        order: usize,
+        sample_rate: f32,
+    ) -> Result<Self, AudioProcessingError> {
+        if !(2..=MAX_ORDER).contains(&order) || !order.is_multiple_of(2) {
+            return Err(AudioProcessingError::ProcessingError(format!(
+                "Butterworth order must be 2, 4, 6 or 8, got {}",
+                order
+            )));
+        }
+        if !(cutoff_hz > 0.0 && cutoff_hz < sample_rate / 2.0) {
+            return Err(AudioProcessingError::ProcessingError(format!(
+                "Butterworth cutoff {} Hz must lie between 0 Hz and Nyquist ({} Hz)",
+                cutoff_hz,
+                sample_rate / 2.0
+            )));
+        }
+
+        // One biquad per conjugate pair of poles, evenly spread on the unit circle
+        let mut stages = Vec::with_capacity(order / 2);
+        for k in 0..order / 2 {
+            let q = 1.0 / (2.0 * (PI * (2 * k + 1) as f32 / (2 * order) as f32).cos());
+            let coeffs = Coefficients::<f32>::from_params(filter_type, sample_rate.hz(), cutoff_hz.hz(), q)
+                .map_err(|e| AudioProcessingError::ProcessingError(format!("Invalid Butterworth section: {:?}", e)))?;
+            stages.push(DirectForm1::<f32>::new(coeffs));
+        }
+
+        Ok(Self { stages })
+    }
+}
+
+impl AudioEffect for ButterworthFilter {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        self.stages.iter_mut().fold(sample, |x, stage| stage.run(x))
+    }
+
+    fn reset(&mut self) {
+        for stage in &mut self.stages {
+            stage.reset_state();
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "ButterworthFilter"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filters::low_pass;
+
+    /// Ganancia en dB de una senoide filtrada, ignorando el transitorio inicial
+    fn sine_gain_db(freq: f32, sample_rate: f32, filter: impl Fn(&[f32]) -> Vec<f32>) -> f32 {
+        let input: Vec<f32> = (0..sample_rate as usize)
+            .map(|i| 0.5 * (2.0 * PI * freq * i as f32 / sample_rate).sin())
+            .collect();
+        let output = filter(&input);
+        let skip = input.len() / 4;
+        let energy = |x: &[f32]| x[skip..].iter().map(|v| v * v).sum::<f32>();
+        10.0 * (energy(&output) / energy(&input)).log10()
+    }
+
+    #[test]
+    fn test_butterworth_slopes() {
+        let sample_rate = 44100.0;
+        let lowpass4 = |x: &[f32]| butterworth_lowpass(x, 1000.0, 4, sample_rate).unwrap();
+
+        // Banda de paso plana y -3 dB en la frecuencia de corte
+        assert!(sine_gain_db(100.0, sample_rate, lowpass4).abs() < 0.1);
+        assert!((sine_gain_db(1000.0, sample_rate, lowpass4) + 3.0).abs() < 0.2);
+
+        // Dos octavas por encima: unos 48 dB con orden 4, frente a los ~12 dB
+        // del paso bajo de primer orden con el mismo corte
+        let alpha = 1.0 - (-2.0 * PI * 1000.0 / sample_rate).exp();
+        let first_order = sine_gain_db(4000.0, sample_rate, |x| low_pass(x, alpha));
+        let fourth_order = sine_gain_db(4000.0, sample_rate, lowpass4);
+        assert!(fourth_order < -40.0, "4th order: {:.1} dB", fourth_order);
+        assert!(fourth_order < first_order - 25.0, "1st order: {:.1} dB", first_order);
+
+        // Cada orden añade pendiente
+        let lowpass8 = |x: &[f32]| butterworth_lowpass(x, 1000.0, 8, sample_rate).unwrap();
+        assert!(sine_gain_db(2000.0, sample_rate, lowpass8) < sine_gain_db(2000.0, sample_rate, lowpass4) - 20.0);
+
+        // El paso alto es el simétrico
+        let highpass4 = |x: &[f32]| butterworth_highpass(x, 1000.0, 4, sample_rate).unwrap();
+        assert!(sine_gain_db(8000.0, sample_rate, highpass4).abs() < 0.1);
+        assert!(sine_gain_db(250.0, sample_rate, highpass4) < -40.0);
+
+        // Órdenes y cortes no válidos
+        for order in [0, 1, 3, 10] {
+            assert!(butterworth_lowpass(&[0.0; 8], 1000.0, order, sample_rate).is_err());
+        }
+        assert!(butterworth_highpass(&[0.0; 8], 0.0, 2, sample_rate).is_err());
+        assert!(butterworth_highpass(&[0.0; 8], 22050.0, 2, sample_rate).is_err());
+    }
+}
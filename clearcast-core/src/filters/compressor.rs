@@ -1,5 +1,9 @@
 //! Audio compression utilities
 
+use super::envelope::{EnvelopeFollower, EnvelopeMode};
+use crate::effects::AudioEffect;
+use crate::utils::Sample;
+
 /// Applies RMS compression to an audio buffer
 /// 
 /// # Arguments
@@ -12,6 +16,9 @@
 /// 
 /// # Returns
 /// Compressed audio buffer with the same length as input
+///
+/// Works on `f32` and `f64` buffers; the envelope and gain smoothing run in
+/// the same precision as the samples.
 /// 
 /// # Example
 /// ```
@@ -20,67 +27,293 @@
 /// let output = compress_rms(&input, -20.0, 4.0, 10.0, 100.0, 44100.0);
 /// assert_eq!(output.len(), input.len());
 /// ```
-pub fn compress_rms(
-    input: &[f32],
-    threshold: f32,
-    ratio: f32,
-    attack_ms: f32,
-    release_ms: f32,
-    sample_rate: f32,
-) -> Vec<f32> {
+pub fn compress_rms<T: Sample>(
+    input: &[T],
+    threshold: T,
+    ratio: T,
+    attack_ms: T,
+    release_ms: T,
+    sample_rate: T,
+) -> Vec<T> {
+    compress_rms_with_gain(input, threshold, ratio, attack_ms, release_ms, sample_rate).0
+}
+
+/// Applies RMS compression and also returns the gain applied to each sample
+///
+/// Same processing as [`compress_rms`]. The second buffer holds the linear
+/// gain applied to each sample (1.0 = no gain reduction), so that
+/// `output[i] == input[i] * gain[i]`, which is what a gain-reduction meter
+/// needs to draw the compression envelope over time.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::compress_rms_with_gain;
+/// let input = vec![0.5, 0.6, 0.7, 0.8, 0.9, 1.0];
+/// let (output, gain) = compress_rms_with_gain(&input, -20.0, 4.0, 10.0, 100.0, 44100.0);
+/// assert_eq!(gain.len(), output.len());
+/// assert!(gain.iter().all(|&g| g <= 1.0));
+/// ```
+pub fn compress_rms_with_gain<T: Sample>(
+    input: &[T],
+    threshold: T,
+    ratio: T,
+    attack_ms: T,
+    release_ms: T,
+    sample_rate: T,
+) -> (Vec<T>, Vec<T>) {
     if input.is_empty() {
-        return Vec::new();
+        return (Vec::new(), Vec::new());
     }
     
     // If threshold is negative infinity, return input as is (no compression)
-    if threshold == f32::NEG_INFINITY {
-        return input.to_vec();
+    if threshold == T::neg_infinity() {
+        return (input.to_vec(), vec![T::one(); input.len()]);
     }
 
     // Convert threshold from dBFS to linear scale (0.0 to 1.0)
-    let _threshold_linear = 10.0f32.powf(threshold / 20.0);
+    let _threshold_linear = T::from_db(threshold);
     // Nota: threshold_linear_sq no se usa en el código, se comenta para evitar warnings
     // let threshold_linear_sq = _threshold_linear * _threshold_linear;
     
-    // Convert times from ms to samples
-    let attack_coeff = (-1.0 / (attack_ms * 0.001 * sample_rate)).exp();
-    let release_coeff = (-1.0 / (release_ms * 0.001 * sample_rate)).exp();
-    
+    let mut computer = GainComputer::new(threshold, ratio, attack_ms, release_ms, sample_rate);
     let mut result = Vec::with_capacity(input.len());
-    let mut envelope = 0.0;
-    let mut gain = 1.0;
-    let inverse_ratio = 1.0 / ratio;
+    let mut gains = Vec::with_capacity(input.len());
 
     for &sample in input {
-        // Calculate squared sample for RMS
-        let sample_sq = sample * sample;
+        let gain = computer.next_gain(sample);
         
-        // Smooth the envelope with attack/release
-        let target = sample_sq.max(1e-10); // Avoid log(0)
-        let coeff = if target > envelope { attack_coeff } else { release_coeff };
-        envelope = (1.0 - coeff) * target + coeff * envelope;
+        // Apply gain, ensuring we don't introduce NaNs or Infs
+        let output = sample * gain;
+        result.push(if output.is_finite() { output } else { T::zero() });
+        gains.push(gain);
+    }
+    
+    (result, gains)
+}
+
+/// Parameters of [`compress_rms_with_params`]
+///
+/// The first four fields mean the same as the arguments of [`compress_rms`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CompressorParams<T: Sample = f32> {
+    /// Threshold in dBFS where compression begins
+    pub threshold: T,
+    /// Compression ratio (e.g., 4.0 for 4:1 compression)
+    pub ratio: T,
+    /// Attack time in milliseconds
+    pub attack_ms: T,
+    /// Release time in milliseconds
+    pub release_ms: T,
+    /// How far the detector runs ahead of the audio, in milliseconds (0.0 = no lookahead)
+    pub lookahead_ms: T,
+}
+
+impl<T: Sample> Default for CompressorParams<T> {
+    fn default() -> Self {
+        Self {
+            threshold: T::from_f64(-20.0),
+            ratio: T::from_f64(4.0),
+            attack_ms: T::from_f64(10.0),
+            release_ms: T::from_f64(100.0),
+            lookahead_ms: T::zero(),
+        }
+    }
+}
+
+impl<T: Sample> CompressorParams<T> {
+    /// Latency added by the lookahead, in samples
+    ///
+    /// The output of [`compress_rms_with_params`] is delayed by this many
+    /// samples relative to the input, and is this many samples longer.
+    pub fn latency_samples(&self, sample_rate: T) -> usize {
+        let samples = (self.lookahead_ms * sample_rate / T::from_f64(1000.0)).round();
+        samples.to_usize().unwrap_or(0)
+    }
+}
+
+/// Applies RMS compression with optional lookahead
+///
+/// Without lookahead this is the same as [`compress_rms`]. With lookahead the
+/// detector sees each sample [`CompressorParams::latency_samples`] before it
+/// reaches the output, so the gain reduction has already started when a
+/// transient arrives instead of letting it through during the attack. The
+/// price is latency: the output is the input delayed by that many samples
+/// (the first ones are silence), with the gain applied. The delay line is
+/// flushed at the end, so the output is `input.len() + latency` samples long
+/// and the tail of the input is not lost.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::{compress_rms_with_params, CompressorParams};
+/// let params = CompressorParams { lookahead_ms: 5.0, ..CompressorParams::default() };
+/// let input = vec![0.5f32; 4410];
+/// let output = compress_rms_with_params(&input, &params, 44100.0);
+/// assert_eq!(params.latency_samples(44100.0), 221);
+/// assert_eq!(output.len(), input.len() + 221);
+/// assert!(output[..221].iter().all(|&x| x == 0.0));
+/// ```
+pub fn compress_rms_with_params<T: Sample>(input: &[T], params: &CompressorParams<T>, sample_rate: T) -> Vec<T> {
+    if input.is_empty() {
+        return Vec::new();
+    }
+
+    let latency = params.latency_samples(sample_rate);
+    let output_len = input.len() + latency;
+    let delayed = |i: usize| if i >= latency { input[i - latency] } else { T::zero() };
+
+    // If threshold is negative infinity, only the delay is applied
+    if params.threshold == T::neg_infinity() {
+        return (0..output_len).map(delayed).collect();
+    }
+
+    let mut computer = GainComputer::new(
+        params.threshold,
+        params.ratio,
+        params.attack_ms,
+        params.release_ms,
+        sample_rate,
+    );
+    (0..output_len)
+        .map(|i| {
+            // The detector is fed the newest sample, and silence once the input
+            // runs out; the gain goes to the delayed one
+            let gain = computer.next_gain(input.get(i).copied().unwrap_or_else(T::zero));
+            let output = delayed(i) * gain;
+            if output.is_finite() { output } else { T::zero() }
+        })
+        .collect()
+}
+
+/// Applies linked RMS compression to a stereo pair in place
+///
+/// A single detector follows the louder of the two channels at every sample
+/// and the same gain is applied to both, so a loud event on one side doesn't
+/// shift the stereo image towards the other. Parameters are the same as
+/// [`compress_rms`]. If the channels differ in length only the common part is
+/// processed.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::compress_rms_stereo;
+/// let mut left = vec![0.9f32; 4410];
+/// let mut right = vec![0.1f32; 4410];
+/// compress_rms_stereo(&mut left, &mut right, -20.0, 4.0, 10.0, 100.0, 44100.0);
+/// assert!((left[4409] / 0.9 - right[4409] / 0.1).abs() < 1e-5);
+/// ```
+pub fn compress_rms_stereo<T: Sample>(
+    left: &mut [T],
+    right: &mut [T],
+    threshold: T,
+    ratio: T,
+    attack_ms: T,
+    release_ms: T,
+    sample_rate: T,
+) {
+    if threshold == T::neg_infinity() {
+        return;
+    }
+
+    let mut computer = GainComputer::new(threshold, ratio, attack_ms, release_ms, sample_rate);
+    for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+        let gain = computer.next_gain(l.abs().max(r.abs()));
+        for sample in [l, r] {
+            let output = *sample * gain;
+            *sample = if output.is_finite() { output } else { T::zero() };
+        }
+    }
+}
+
+/// RMS compressor that keeps its state between calls
+///
+/// Same processing as [`compress_rms`], but as an [`AudioEffect`] so it can
+/// run in an effect chain and process a stream buffer by buffer.
+///
+/// # Example
+/// ```
+/// use clearcast_core::effects::AudioEffect;
+/// use clearcast_core::filters::Compressor;
+///
+/// let mut compressor = Compressor::new(-20.0, 4.0, 10.0, 100.0, 44100.0);
+/// let mut buffer = vec![0.8; 4410];
+/// compressor.process_buffer(&mut buffer);
+/// assert!(buffer[4409] < 0.8);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Compressor {
+    computer: GainComputer<f32>,
+}
+
+impl Compressor {
+    /// Creates a compressor; the arguments mean the same as in [`compress_rms`]
+    pub fn new(threshold: f32, ratio: f32, attack_ms: f32, release_ms: f32, sample_rate: f32) -> Self {
+        Self {
+            computer: GainComputer::new(threshold, ratio, attack_ms, release_ms, sample_rate),
+        }
+    }
+}
+
+impl AudioEffect for Compressor {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        let output = sample * self.computer.next_gain(sample);
+        if output.is_finite() { output } else { 0.0 }
+    }
+
+    fn reset(&mut self) {
+        self.computer.follower.reset();
+        self.computer.gain = 1.0;
+    }
+
+    fn name(&self) -> &'static str {
+        "Compressor"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+/// RMS detector and smoothed gain computer shared by the compressors
+#[derive(Debug, Clone)]
+struct GainComputer<T: Sample> {
+    /// RMS detector with the attack/release times
+    follower: EnvelopeFollower<T>,
+    threshold: T,
+    inverse_ratio: T,
+    gain: T,
+}
+
+impl<T: Sample> GainComputer<T> {
+    fn new(threshold: T, ratio: T, attack_ms: T, release_ms: T, sample_rate: T) -> Self {
+        Self {
+            follower: EnvelopeFollower::new(attack_ms, release_ms, sample_rate, EnvelopeMode::Rms),
+            threshold,
+            inverse_ratio: ratio.recip(),
+            gain: T::one(),
+        }
+    }
+
+    /// Feeds one detector sample and returns the gain to apply to it
+    fn next_gain(&mut self, sample: T) -> T {
+        // Smooth the RMS level with attack/release
+        let level = self.follower.process(sample);
+        let coeff = self.follower.last_coeff();
         
         // Calculate gain reduction in dB
-        let env_db = 10.0 * envelope.log10();
-        let over_db = (env_db - threshold).max(0.0);
-        let reduction_db = over_db * (1.0 - inverse_ratio);
+        let env_db = level.to_db();
+        let over_db = (env_db - self.threshold).max(T::zero());
+        let reduction_db = over_db * (T::one() - self.inverse_ratio);
         
         // Convert reduction to linear gain
-        let target_gain = if env_db > threshold {
-            10.0f32.powf(-reduction_db / 20.0)
+        let target_gain = if env_db > self.threshold {
+            T::from_db(-reduction_db)
         } else {
-            1.0
+            T::one()
         };
         
         // Smooth gain changes to avoid clicks
-        gain = (1.0 - coeff) * target_gain + coeff * gain;
-        
-        // Apply gain, ensuring we don't introduce NaNs or Infs
-        let output = sample * gain;
-        result.push(if output.is_finite() { output } else { 0.0 });
+        self.gain = (T::one() - coeff) * target_gain + coeff * self.gain;
+        self.gain
     }
-    
-    result
 }
 
 #[cfg(test)]
@@ -290,6 +523,124 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_lookahead_attenuates_first_loud_sample() {
+        let sample_rate = 44100.0;
+        let step = 4410;
+        // Quiet passage followed by a sudden loud step
+        let input: Vec<f32> = (0..8820).map(|i| if i < step { 0.01 } else { 0.9 }).collect();
+
+        let params = CompressorParams { threshold: -20.0, ratio: 4.0, attack_ms: 10.0, release_ms: 100.0, lookahead_ms: 0.0 };
+        let output = compress_rms_with_params(&input, &params, sample_rate);
+        assert_eq!(output, compress_rms(&input, -20.0, 4.0, 10.0, 100.0, sample_rate));
+        // Without lookahead the attack lets the first loud sample through
+        assert!(output[step] / input[step] > 0.99, "gain {}", output[step] / input[step]);
+
+        let params = CompressorParams { lookahead_ms: 5.0, ..params };
+        let latency = params.latency_samples(sample_rate);
+        assert_eq!(latency, 221);
+        let output = compress_rms_with_params(&input, &params, sample_rate);
+        assert_eq!(output.len(), input.len() + latency);
+        assert!(output[..latency].iter().all(|&x| x == 0.0));
+        assert_eq!(output[latency], input[0]);
+        // With lookahead it is already attenuated when it reaches the output
+        let gain = output[step + latency] / input[step];
+        assert!(gain < 0.9, "gain {}", gain);
+
+        // The last samples of the input come out of the delay line, still compressed
+        let tail = &output[input.len()..];
+        assert_eq!(tail.len(), latency);
+        assert!(tail.iter().all(|&x| x > 0.0 && x < 0.9), "tail {:?}", &tail[..4]);
+        assert!(output.iter().all(|&x| x.is_finite()));
+    }
+
+    #[test]
+    fn test_f64_path_reduces_accumulated_error() {
+        // 10 seconds of a 1 kHz sine; its exact RMS level is amplitude / sqrt(2)
+        let sample_rate = 48000.0;
+        let amplitude = 0.5;
+        let input: Vec<f64> = (0..10 * 48000)
+            .map(|i| amplitude * (2.0 * std::f64::consts::PI * 1000.0 * i as f64 / sample_rate).sin())
+            .collect();
+        let input_f32: Vec<f32> = input.iter().map(|&x| x as f32).collect();
+        let expected = amplitude / 2.0f64.sqrt();
+
+        // A one-second RMS integration adds a tiny increment to a much larger
+        // running value on every sample, which is where f32 loses precision
+        let mut follower = EnvelopeFollower::new(1000.0, 1000.0, sample_rate, EnvelopeMode::Rms);
+        let level_f64 = input.iter().map(|&x| follower.process(x)).last().unwrap();
+        let mut follower = EnvelopeFollower::new(1000.0, 1000.0, sample_rate as f32, EnvelopeMode::Rms);
+        let level_f32 = input_f32.iter().map(|&x| follower.process(x)).last().unwrap();
+
+        let error_f64 = (level_f64 - expected).abs();
+        let error_f32 = (level_f32 as f64 - expected).abs();
+        assert!(error_f64 < error_f32, "f64 error {} vs f32 error {}", error_f64, error_f32);
+
+        // Both precisions compress the same signal the same way
+        let output_f64 = compress_rms(&input, -20.0, 4.0, 10.0, 1000.0, sample_rate);
+        let output_f32 = compress_rms(&input_f32, -20.0, 4.0, 10.0, 1000.0, sample_rate as f32);
+        for (a, b) in output_f64.iter().zip(&output_f32).step_by(997) {
+            assert!((a - *b as f64).abs() < 1e-3, "f64 {} vs f32 {}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_compress_rms_stereo_links_channels() {
+        let sample_rate = 44100.0;
+        let quiet = generate_sine_wave(220.0, sample_rate, 0.2, 0.1);
+        // Transitorio fuerte solo en el canal izquierdo entre 50 y 100 ms
+        let mut left = quiet.clone();
+        for sample in &mut left[2205..4410] {
+            *sample *= 9.0;
+        }
+        let mut right = quiet.clone();
+        let left_input = left.clone();
+
+        compress_rms_stereo(&mut left, &mut right, -20.0, 4.0, 5.0, 100.0, sample_rate);
+
+        // Durante el transitorio el canal derecho recibe la misma reducción
+        for i in (2800..4410).step_by(101) {
+            if quiet[i].abs() < 1e-3 {
+                continue;
+            }
+            let left_gain = left[i] / left_input[i];
+            let right_gain = right[i] / quiet[i];
+            assert!(left_gain < 0.8, "No gain reduction at {}", i);
+            assert_relative_eq!(left_gain, right_gain, epsilon = 1e-5);
+        }
+
+        // Comprimidos por separado, el canal derecho no se reduciría
+        let independent = compress_rms(&quiet, -20.0, 4.0, 5.0, 100.0, sample_rate);
+        assert!((independent[3000] / quiet[3000] - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn test_compress_rms_with_gain() {
+        let sample_rate = 44100.0;
+        let quiet = generate_sine_wave(440.0, sample_rate, 0.25, 0.01);
+        let loud = generate_sine_wave(440.0, sample_rate, 0.25, 0.9);
+        let input: Vec<f32> = quiet.iter().chain(&loud).copied().collect();
+
+        let (output, gain) = compress_rms_with_gain(&input, -20.0, 4.0, 5.0, 50.0, sample_rate);
+        assert_eq!(output, compress_rms(&input, -20.0, 4.0, 5.0, 50.0, sample_rate));
+        assert_eq!(gain.len(), input.len());
+
+        // The output is exactly the input scaled by the reported gain
+        for ((&x, &y), &g) in input.iter().zip(&output).zip(&gain) {
+            assert_eq!(y, x * g);
+        }
+
+        // -40 dBFS stays below the threshold: no gain reduction
+        for &g in &gain[..quiet.len()] {
+            assert_relative_eq!(g, 1.0, epsilon = 1e-5);
+        }
+
+        // Once the attack has settled, the loud passage is turned down
+        let settled = quiet.len() + (0.05 * sample_rate) as usize;
+        assert!(gain[settled..].iter().all(|&g| g < 1.0));
+        assert!(gain[settled..].iter().all(|&g| g > 0.0));
+    }
+
     #[wasm_bindgen_test]
     fn test_wasm_compatibility() {
         // Simple test to verify the function works in WASM
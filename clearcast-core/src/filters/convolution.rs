@@ -0,0 +1,147 @@
+//! FFT convolution, e.g. with the impulse response of a room or a cabinet
+//!
+//! Direct convolution costs one multiply per signal sample and impulse tap, so
+//! a reverb tail of a few seconds quickly becomes too slow. Overlap-add splits
+//! the signal into blocks, convolves each one in the frequency domain with the
+//! impulse's spectrum (computed once) and sums the overlapping results.
+
+use realfft::RealFftPlanner;
+
+use crate::AudioProcessingError;
+
+/// Convolves a signal with an impulse response using overlap-add FFT convolution
+///
+/// The output holds the full convolution, including the tail of the impulse
+/// after the last signal sample, so it is `signal.len() + impulse.len() - 1`
+/// samples long. An empty signal or impulse gives an empty output.
+/// Non-finite samples don't make it fail, but spread through the output.
+///
+/// # Arguments
+/// * `signal` - Input audio buffer
+/// * `impulse` - Impulse response, e.g. loaded from an IR file at the same
+///   sample rate as the signal
+///
+/// # Errors
+/// [`AudioProcessingError::ProcessingError`] if an FFT fails.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::convolve;
+///
+/// // An echo 2 samples later at half the level
+/// let output = convolve(&[1.0, 0.5], &[1.0, 0.0, 0.5]).unwrap();
+/// assert_eq!(output.len(), 4);
+/// assert!((output[2] - 0.5).abs() < 1e-6);
+/// assert!((output[3] - 0.25).abs() < 1e-6);
+/// ```
+pub fn convolve(signal: &[f32], impulse: &[f32]) -> Result<Vec<f32>, AudioProcessingError> {
+    if signal.is_empty() || impulse.is_empty() {
+        return Ok(Vec::new());
+    }
+    let fft_error =
+        |e: realfft::FftError| AudioProcessingError::ProcessingError(format!("Convolution FFT failed: {}", e));
+
+    // Each block of `block_len` samples convolved with the impulse fits in one FFT frame
+    let fft_size = (2 * impulse.len()).next_power_of_two();
+    let block_len = fft_size - impulse.len() + 1;
+
+    let mut planner = RealFftPlanner::<f32>::new();
+    let r2c = planner.plan_fft_forward(fft_size);
+    let c2r = planner.plan_fft_inverse(fft_size);
+    let mut frame = r2c.make_input_vec();
+    let mut spectrum = r2c.make_output_vec();
+
+    frame[..impulse.len()].copy_from_slice(impulse);
+    let mut impulse_spectrum = r2c.make_output_vec();
+    r2c.process(&mut frame, &mut impulse_spectrum).map_err(fft_error)?;
+
+    let mut output = vec![0.0; signal.len() + impulse.len() - 1];
+    let scale = 1.0 / fft_size as f32;
+
+    for (i, block) in signal.chunks(block_len).enumerate() {
+        frame[..block.len()].copy_from_slice(block);
+        frame[block.len()..].iter_mut().for_each(|x| *x = 0.0);
+
+        r2c.process(&mut frame, &mut spectrum).map_err(fft_error)?;
+        for (bin, h) in spectrum.iter_mut().zip(&impulse_spectrum) {
+            *bin *= h;
+        }
+        // The inverse FFT rejects imaginary parts at DC and Nyquist, which a
+        // NaN or infinite sample leaves behind
+        let bins = spectrum.len();
+        spectrum[0].im = 0.0;
+        spectrum[bins - 1].im = 0.0;
+        c2r.process(&mut spectrum, &mut frame).map_err(fft_error)?;
+
+        let start = i * block_len;
+        for (out, &y) in output[start..].iter_mut().zip(&frame) {
+            *out += y * scale;
+        }
+    }
+
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+
+    /// Convolución directa de referencia
+    fn direct_convolve(signal: &[f32], impulse: &[f32]) -> Vec<f32> {
+        let mut output = vec![0.0; signal.len() + impulse.len() - 1];
+        for (i, &x) in signal.iter().enumerate() {
+            for (j, &h) in impulse.iter().enumerate() {
+                output[i + j] += x * h;
+            }
+        }
+        output
+    }
+
+    #[test]
+    fn test_convolve_identity_echo_and_long_impulse() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(358);
+        let signal: Vec<f32> = (0..5000).map(|_| rng.gen_range(-0.5..0.5)).collect();
+
+        // Impulso unidad: la salida es la entrada
+        let identity = convolve(&signal, &[1.0]).unwrap();
+        assert_eq!(identity.len(), signal.len());
+        for (y, x) in identity.iter().zip(&signal) {
+            assert!((y - x).abs() < 1e-5);
+        }
+
+        // Dos muestras no nulas: la señal más un eco 100 muestras después a -6 dB
+        let mut echo_ir = vec![0.0; 101];
+        echo_ir[0] = 1.0;
+        echo_ir[100] = 0.5;
+        let echoed = convolve(&signal, &echo_ir).unwrap();
+        assert_eq!(echoed.len(), signal.len() + 100);
+        for (n, y) in echoed.iter().enumerate() {
+            let dry = signal.get(n).copied().unwrap_or(0.0);
+            let echo = if n >= 100 { 0.5 * signal.get(n - 100).copied().unwrap_or(0.0) } else { 0.0 };
+            assert!((y - (dry + echo)).abs() < 1e-5, "sample {}", n);
+        }
+
+        // Cola de reverberación larga frente a la convolución directa
+        let tail: Vec<f32> = (0..3000).map(|i| rng.gen_range(-1.0..1.0) * (-(i as f32) / 500.0).exp()).collect();
+        let expected = direct_convolve(&signal, &tail);
+        let output = convolve(&signal, &tail).unwrap();
+        assert_eq!(output.len(), expected.len());
+        for (y, e) in output.iter().zip(&expected) {
+            assert!((y - e).abs() < 1e-3, "{} != {}", y, e);
+        }
+
+        assert!(convolve(&[], &tail).unwrap().is_empty());
+        assert!(convolve(&signal, &[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_convolve_non_finite_input_does_not_panic() {
+        // Un NaN o un infinito deja partes imaginarias en DC y Nyquist que la IFFT rechazaría
+        let output = convolve(&[1.0, f32::NAN, 0.5, 0.25], &[1.0, 0.5]).unwrap();
+        assert_eq!(output.len(), 5);
+
+        let output = convolve(&[1.0, 0.5, 0.25], &[f32::INFINITY, 0.5, 0.0]).unwrap();
+        assert_eq!(output.len(), 5);
+    }
+}
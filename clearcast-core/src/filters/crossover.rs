@@ -0,0 +1,156 @@
+//! Linkwitz-Riley crossover for splitting a signal into frequency bands
+//!
+//! Each crossover point is a 4th-order Linkwitz-Riley pair (two cascaded
+//! Butterworth biquads per side). The low and high outputs of such a pair are
+//! in phase at every frequency and add up to an allpass, so the bands can be
+//! processed separately and summed back without a dip or bump at the
+//! crossover frequencies.
+
+use biquad::frequency::*;
+use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType, Q_BUTTERWORTH_F32};
+
+/// 4th-order Linkwitz-Riley lowpass or highpass
+struct LinkwitzRiley {
+    stages: [DirectForm1<f32>; 2],
+}
+
+impl LinkwitzRiley {
+    /// `freq_hz` must lie strictly between 0 Hz and Nyquist
+    fn new(filter_type: FilterType<f32>, freq_hz: f32, sample_rate: f32) -> Self {
+        let coeffs =
+            Coefficients::<f32>::from_params(filter_type, sample_rate.hz(), freq_hz.hz(), Q_BUTTERWORTH_F32).unwrap();
+        Self {
+            stages: [DirectForm1::<f32>::new(coeffs), DirectForm1::<f32>::new(coeffs)],
+        }
+    }
+
+    fn process_buffer(&mut self, buffer: &mut [f32]) {
+        let [first, second] = &mut self.stages;
+        for x in buffer.iter_mut() {
+            *x = second.run(first.run(*x));
+        }
+    }
+}
+
+/// Splits `buffer` at `freq_hz` in place, returning the low band and leaving the high band
+fn split_at(buffer: &mut [f32], freq_hz: f32, sample_rate: f32) -> Vec<f32> {
+    let mut low = buffer.to_vec();
+    LinkwitzRiley::new(FilterType::LowPass, freq_hz, sample_rate).process_buffer(&mut low);
+    LinkwitzRiley::new(FilterType::HighPass, freq_hz, sample_rate).process_buffer(buffer);
+    low
+}
+
+/// Splits a signal into frequency bands with Linkwitz-Riley crossovers
+///
+/// With `n` valid crossover frequencies the result has `n + 1` bands, from the
+/// lowest to the highest. The lower bands go through the same phase shift as
+/// the higher ones, so [`sum_bands`] gives back the input with a flat
+/// magnitude response (only the phase is rotated around each crossover).
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `crossover_freqs` - Crossover frequencies in Hz, in any order. Values that
+///   are not strictly between 0 Hz and Nyquist, and duplicates, are ignored
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Returns
+/// One buffer per band, each as long as `input`
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::{split_bands, sum_bands};
+///
+/// let input: Vec<f32> = (0..1024).map(|i| (i as f32 * 0.05).sin()).collect();
+/// let bands = split_bands(&input, &[200.0, 2000.0], 44100.0);
+/// assert_eq!(bands.len(), 3);
+/// let output = sum_bands(&bands);
+/// assert_eq!(output.len(), input.len());
+/// ```
+pub fn split_bands(input: &[f32], crossover_freqs: &[f32], sample_rate: f32) -> Vec<Vec<f32>> {
+    let nyquist = sample_rate / 2.0;
+    let mut freqs: Vec<f32> = crossover_freqs
+        .iter()
+        .copied()
+        .filter(|&freq| freq > 0.0 && freq < nyquist)
+        .collect();
+    freqs.sort_by(f32::total_cmp);
+    freqs.dedup();
+
+    let mut bands = Vec::with_capacity(freqs.len() + 1);
+    let mut remaining = input.to_vec();
+    for (i, &freq) in freqs.iter().enumerate() {
+        let mut low = split_at(&mut remaining, freq, sample_rate);
+
+        // The higher bands still go through the remaining crossovers; passing
+        // this band through the same allpass responses keeps them in phase
+        for &upper in &freqs[i + 1..] {
+            let allpass_low = split_at(&mut low, upper, sample_rate);
+            for (x, y) in low.iter_mut().zip(allpass_low) {
+                *x += y;
+            }
+        }
+
+        bands.push(low);
+    }
+    bands.push(remaining);
+
+    bands
+}
+
+/// Sums the bands produced by [`split_bands`] back into one signal
+///
+/// Bands of different lengths are summed over the length of the longest one.
+pub fn sum_bands(bands: &[Vec<f32>]) -> Vec<f32> {
+    let len = bands.iter().map(Vec::len).max().unwrap_or(0);
+    let mut output = vec![0.0; len];
+    for band in bands {
+        for (out, &x) in output.iter_mut().zip(band) {
+            *out += x;
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    fn peak(signal: &[f32]) -> f32 {
+        signal.iter().fold(0.0f32, |max, x| max.max(x.abs()))
+    }
+
+    #[test]
+    fn test_split_and_sum_reconstructs_input() {
+        let sample_rate = 44100.0;
+        let crossovers = [2000.0, 200.0];
+
+        // Tonos lejos y justo en las frecuencias de cruce
+        for freq in [60.0, 200.0, 700.0, 2000.0, 8000.0] {
+            let input: Vec<f32> = (0..22050).map(|i| (2.0 * PI * freq * i as f32 / sample_rate).sin()).collect();
+            let bands = split_bands(&input, &crossovers, sample_rate);
+            assert_eq!(bands.len(), 3);
+
+            // Régimen permanente: la suma conserva la amplitud
+            let steady = 4410..;
+            let output = sum_bands(&bands);
+            let gain_db = 20.0 * peak(&output[steady.clone()]).log10();
+            assert!(gain_db.abs() < 0.1, "{} Hz: {} dB", freq, gain_db);
+
+            // Lejos de los cruces, el tono queda en su banda
+            let expected_band = match freq {
+                60.0 => Some(0),
+                700.0 => Some(1),
+                8000.0 => Some(2),
+                _ => None,
+            };
+            if let Some(band) = expected_band {
+                assert!(peak(&bands[band][steady]) > 0.9, "{} Hz not in band {}", freq, band);
+            }
+        }
+
+        // Frecuencias fuera de rango se ignoran
+        assert_eq!(split_bands(&[0.0; 16], &[0.0, 30000.0, f32::NAN], sample_rate).len(), 1);
+        assert!(sum_bands(&[]).is_empty());
+    }
+}
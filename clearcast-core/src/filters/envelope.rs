@@ -0,0 +1,198 @@
+//! Envelope follower shared by dynamics processors and meters
+//!
+//! An envelope follower tracks the level of a signal with separate attack and
+//! release times: it rises quickly when the signal gets louder and falls back
+//! slowly when it gets quieter. Both times are one-pole time constants, so
+//! after `attack_ms` a step input has covered about 63% (1 - 1/e) of the way.
+
+use crate::utils::Sample;
+
+/// How the follower measures the level of the signal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeMode {
+    /// Smooths the squared signal and returns its square root (RMS level)
+    Rms,
+    /// Smooths the absolute value of the signal (peak level)
+    Peak,
+}
+
+/// One-pole attack/release envelope follower
+///
+/// Runs in `f32` by default; use `EnvelopeFollower<f64>` for long signals
+/// where the smoothing would otherwise accumulate rounding error.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::{EnvelopeFollower, EnvelopeMode};
+///
+/// let mut follower: EnvelopeFollower = EnvelopeFollower::new(5.0, 50.0, 44100.0, EnvelopeMode::Peak);
+/// let level = (0..4410).map(|_| follower.process(0.5)).last().unwrap();
+/// assert!((level - 0.5).abs() < 1e-3);
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnvelopeFollower<T: Sample = f32> {
+    mode: EnvelopeMode,
+    attack_coeff: T,
+    release_coeff: T,
+    /// Smoothed level (squared in RMS mode)
+    envelope: T,
+    last_coeff: T,
+}
+
+impl<T: Sample> EnvelopeFollower<T> {
+    /// Creates a new envelope follower
+    ///
+    /// # Arguments
+    /// * `attack_ms` - Attack time constant in milliseconds
+    /// * `release_ms` - Release time constant in milliseconds
+    /// * `sample_rate` - Sample rate in Hz
+    /// * `mode` - RMS or peak detection
+    pub fn new(attack_ms: T, release_ms: T, sample_rate: T, mode: EnvelopeMode) -> Self {
+        let attack_coeff = time_constant_coeff(attack_ms, sample_rate);
+        Self {
+            mode,
+            attack_coeff,
+            release_coeff: time_constant_coeff(release_ms, sample_rate),
+            envelope: T::zero(),
+            last_coeff: attack_coeff,
+        }
+    }
+
+    /// Feeds one sample and returns the current level (linear amplitude)
+    pub fn process(&mut self, sample: T) -> T {
+        let target = match self.mode {
+            EnvelopeMode::Rms => sample * sample,
+            EnvelopeMode::Peak => sample.abs(),
+        };
+
+        let coeff = if target > self.envelope { self.attack_coeff } else { self.release_coeff };
+        self.envelope = (T::one() - coeff) * target + coeff * self.envelope;
+        self.last_coeff = coeff;
+
+        self.level()
+    }
+
+    /// Current level without feeding a new sample (linear amplitude)
+    pub fn level(&self) -> T {
+        match self.mode {
+            EnvelopeMode::Rms => self.envelope.sqrt(),
+            EnvelopeMode::Peak => self.envelope,
+        }
+    }
+
+    /// Smoothing coefficient used by the last call to [`EnvelopeFollower::process`]
+    ///
+    /// Lets a gain computer smooth its gain with the same attack or release
+    /// ballistics the detector is currently following.
+    pub fn last_coeff(&self) -> T {
+        self.last_coeff
+    }
+
+    /// Clears the envelope back to silence
+    pub fn reset(&mut self) {
+        self.envelope = T::zero();
+        self.last_coeff = self.attack_coeff;
+    }
+}
+
+/// Runs an envelope follower over a whole buffer and returns its level at every sample
+///
+/// This is the detector [`crate::filters::compress_rms`] uses in
+/// [`EnvelopeMode::Rms`], exposed on its own so it can be plotted or checked
+/// without any gain reduction applied.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `attack_ms` - Attack time constant in milliseconds
+/// * `release_ms` - Release time constant in milliseconds
+/// * `sample_rate` - Sample rate in Hz
+/// * `mode` - RMS or peak detection
+///
+/// # Returns
+/// The envelope (linear amplitude), one value per input sample
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::{analyze_envelope, EnvelopeMode};
+/// let input = vec![0.5f32; 4410];
+/// let envelope = analyze_envelope(&input, 5.0, 50.0, 44100.0, EnvelopeMode::Peak);
+/// assert_eq!(envelope.len(), input.len());
+/// assert!((envelope[4409] - 0.5).abs() < 1e-3);
+/// ```
+pub fn analyze_envelope<T: Sample>(
+    input: &[T],
+    attack_ms: T,
+    release_ms: T,
+    sample_rate: T,
+    mode: EnvelopeMode,
+) -> Vec<T> {
+    let mut follower = EnvelopeFollower::new(attack_ms, release_ms, sample_rate, mode);
+    input.iter().map(|&x| follower.process(x)).collect()
+}
+
+/// Coefficient of a one-pole smoother with the given time constant
+fn time_constant_coeff<T: Sample>(time_ms: T, sample_rate: T) -> T {
+    (-(time_ms * T::from_f64(0.001) * sample_rate).recip()).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::E;
+
+    /// Samples needed to cover one time constant at 1 kHz
+    const TAU: usize = 20;
+
+    fn follow(follower: &mut EnvelopeFollower, value: f32, samples: usize) -> f32 {
+        (0..samples).map(|_| follower.process(value)).last().unwrap()
+    }
+
+    #[test]
+    fn test_envelope_step_response_peak() {
+        let mut follower = EnvelopeFollower::new(TAU as f32, 5.0 * TAU as f32, 1000.0, EnvelopeMode::Peak);
+
+        // One attack time constant covers 1 - 1/e of the step
+        let attacked = follow(&mut follower, 1.0, TAU);
+        assert!((attacked - (1.0 - 1.0 / E)).abs() < 0.01, "Attack level {}", attacked);
+        let settled = follow(&mut follower, 1.0, 20 * TAU);
+        assert!((settled - 1.0).abs() < 1e-3);
+
+        // One release time constant decays to 1/e
+        let released = follow(&mut follower, 0.0, 5 * TAU);
+        assert!((released - 1.0 / E).abs() < 0.01, "Release level {}", released);
+    }
+
+    #[test]
+    fn test_envelope_step_response_rms() {
+        let mut follower = EnvelopeFollower::new(TAU as f32, 5.0 * TAU as f32, 1000.0, EnvelopeMode::Rms);
+
+        // The time constants apply to the mean square, so the RMS level is its root
+        let attacked = follow(&mut follower, -1.0, TAU);
+        assert!((attacked - (1.0 - 1.0 / E).sqrt()).abs() < 0.01, "Attack level {}", attacked);
+        follow(&mut follower, 1.0, 20 * TAU);
+
+        let released = follow(&mut follower, 0.0, 5 * TAU);
+        assert!((released - (1.0 / E).sqrt()).abs() < 0.01, "Release level {}", released);
+
+        follower.reset();
+        assert_eq!(follower.level(), 0.0);
+    }
+
+    #[test]
+    fn test_analyze_envelope_attack_times() {
+        // Square wave: its peak and RMS levels are both 0.5
+        let input: Vec<f32> = (0..2000).map(|i| if (i / 5) % 2 == 0 { 0.5 } else { -0.5 }).collect();
+        let reaches = |envelope: &[f32]| envelope.iter().position(|&level| level >= 0.45).unwrap();
+
+        for mode in [EnvelopeMode::Peak, EnvelopeMode::Rms] {
+            let fast = analyze_envelope(&input, TAU as f32, 5.0 * TAU as f32, 1000.0, mode);
+            let slow = analyze_envelope(&input, 4.0 * TAU as f32, 5.0 * TAU as f32, 1000.0, mode);
+            assert_eq!(fast.len(), input.len());
+
+            // The envelope rises steadily towards the amplitude
+            assert!(fast.windows(2).all(|w| w[1] >= w[0]));
+            assert!((fast[input.len() - 1] - 0.5).abs() < 1e-3);
+            assert!(reaches(&fast) < reaches(&slow), "{:?}", mode);
+        }
+    }
+}
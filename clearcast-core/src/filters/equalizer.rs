@@ -1,11 +1,29 @@
 //! Parametric equalizer implementation using biquad filters
 
-use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType};
-use biquad::frequency::*;
+use biquad::{Biquad, Coefficients, DirectForm1, Errors as FilterError, Type as FilterType};
+
+use core::fmt;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+#[cfg(feature = "alloc")]
+use crate::utils::db_to_linear;
 
 // Alias for frequency in Hz
 type Hertz = f32;
 
+/// Default corner frequency and Q of the low shelf
+const DEFAULT_LOW_FREQ: Hertz = 250.0;
+const DEFAULT_LOW_Q: f32 = 0.707;
+/// Default center frequency and Q of the mid peak, centered on the geometric
+/// middle of the 200-3000 Hz range
+const DEFAULT_MID_FREQ: Hertz = 774.6;
+const DEFAULT_MID_Q: f32 = 2.0;
+/// Default corner frequency and Q of the high shelf
+const DEFAULT_HIGH_FREQ: Hertz = 2500.0;
+const DEFAULT_HIGH_Q: f32 = 0.707;
+
 /// 3-band parametric equalizer
 /// 
 /// This equalizer splits the audio into three frequency bands:
@@ -13,12 +31,20 @@ type Hertz = f32;
 /// - Mid band: 200 Hz - 3000 Hz
 /// - High band: > 3000 Hz
 /// 
-/// Each band has its own gain control that can boost or cut the signal.
+/// Each band has its own gain control that can boost or cut the signal, and
+/// its frequency and Q can be tuned with [`ParametricEQ::set_frequency`] and
+/// [`ParametricEQ::set_q`].
 pub struct ParametricEQ {
     sample_rate: f32,  // Sample rate in Hz
     low_gain: f32,
     mid_gain: f32,
     high_gain: f32,
+    low_freq: Hertz,
+    mid_freq: Hertz,
+    high_freq: Hertz,
+    low_q: f32,
+    mid_q: f32,
+    high_q: f32,
     low_filter: DirectForm1<f32>,
     mid_filter: DirectForm1<f32>,
     high_filter: DirectForm1<f32>,
@@ -32,38 +58,132 @@ impl ParametricEQ {
     /// * `low_gain` - Gain for low frequencies (<200 Hz) in dB
     /// * `mid_gain` - Gain for mid frequencies (200-3000 Hz) in dB
     /// * `high_gain` - Gain for high frequencies (>3000 Hz) in dB
-    pub fn new(sample_rate: f32, low_gain: f32, mid_gain: f32, high_gain: f32) -> Self {
+    ///
+    /// # Errors
+    /// Returns an [`EqError`] if a band filter cannot be created, e.g. when the
+    /// sample rate is not positive or is so low that a default band frequency
+    /// lies above Nyquist. With `alloc` it converts into
+    /// `AudioProcessingError` through `?`.
+    pub fn new(sample_rate: f32, low_gain: f32, mid_gain: f32, high_gain: f32) -> Result<Self, EqError> {
+        let band_error = |band: Band, frequency: Hertz| {
+            move |_: FilterError| EqError { band, frequency, sample_rate }
+        };
+
         // Create filters for each band
-        let low_filter = Self::create_low_shelf(sample_rate, low_gain);
-        let mid_filter = Self::create_band_pass(sample_rate, mid_gain);
-        let high_filter = Self::create_high_shelf(sample_rate, high_gain);
+        let low_filter = Self::create_low_shelf(sample_rate, low_gain, DEFAULT_LOW_FREQ, DEFAULT_LOW_Q)
+            .map_err(band_error(Band::Low, DEFAULT_LOW_FREQ))?;
+        let mid_filter = Self::create_band_pass(sample_rate, mid_gain, DEFAULT_MID_FREQ, DEFAULT_MID_Q)
+            .map_err(band_error(Band::Mid, DEFAULT_MID_FREQ))?;
+        let high_filter = Self::create_high_shelf(sample_rate, high_gain, DEFAULT_HIGH_FREQ, DEFAULT_HIGH_Q)
+            .map_err(band_error(Band::High, DEFAULT_HIGH_FREQ))?;
         
-        Self {
+        Ok(Self {
             sample_rate: sample_rate,
             low_gain,
             mid_gain,
             high_gain,
+            low_freq: DEFAULT_LOW_FREQ,
+            mid_freq: DEFAULT_MID_FREQ,
+            high_freq: DEFAULT_HIGH_FREQ,
+            low_q: DEFAULT_LOW_Q,
+            mid_q: DEFAULT_MID_Q,
+            high_q: DEFAULT_HIGH_Q,
             low_filter,
             mid_filter,
             high_filter,
-        }
+        })
     }
     
     /// Update the gain for a specific band
     pub fn set_gain(&mut self, band: Band, gain: f32) {
         match band {
-            Band::Low => {
-                self.low_gain = gain;
-                self.low_filter = Self::create_low_shelf(self.sample_rate, gain);
-            }
-            Band::Mid => {
-                self.mid_gain = gain;
-                self.mid_filter = Self::create_band_pass(self.sample_rate, gain);
-            }
-            Band::High => {
-                self.high_gain = gain;
-                self.high_filter = Self::create_high_shelf(self.sample_rate, gain);
-            }
+            Band::Low => self.low_gain = gain,
+            Band::Mid => self.mid_gain = gain,
+            Band::High => self.high_gain = gain,
+        }
+        self.rebuild(band);
+    }
+
+    /// Update the corner (shelves) or center (mid) frequency of a band in Hz
+    ///
+    /// Frequencies that are not between 0 Hz and Nyquist are ignored.
+    pub fn set_frequency(&mut self, band: Band, freq_hz: Hertz) {
+        if freq_hz.is_nan() || freq_hz <= 0.0 || freq_hz >= self.sample_rate / 2.0 {
+            log::warn!("ParametricEQ::set_frequency: {} Hz is out of range", freq_hz);
+            return;
+        }
+
+        match band {
+            Band::Low => self.low_freq = freq_hz,
+            Band::Mid => self.mid_freq = freq_hz,
+            Band::High => self.high_freq = freq_hz,
+        }
+        self.rebuild(band);
+    }
+
+    /// Update the Q of a band (higher = narrower peak or steeper shelf)
+    ///
+    /// Non-positive values are ignored.
+    pub fn set_q(&mut self, band: Band, q: f32) {
+        if q.is_nan() || q <= 0.0 {
+            log::warn!("ParametricEQ::set_q: Q must be positive, got {}", q);
+            return;
+        }
+
+        match band {
+            Band::Low => self.low_q = q,
+            Band::Mid => self.mid_q = q,
+            Band::High => self.high_q = q,
+        }
+        self.rebuild(band);
+    }
+
+    /// Update the Q of a band from its bandwidth in octaves
+    ///
+    /// Converts with [`q_from_bandwidth_octaves`], so a 1-octave mid peak
+    /// reaches half its gain in dB half an octave either side of the center.
+    /// Non-positive bandwidths are ignored.
+    pub fn set_bandwidth_octaves(&mut self, band: Band, bw_octaves: f32) {
+        if bw_octaves.is_nan() || bw_octaves <= 0.0 {
+            log::warn!("ParametricEQ::set_bandwidth_octaves: bandwidth must be positive, got {}", bw_octaves);
+            return;
+        }
+        self.set_q(band, q_from_bandwidth_octaves(bw_octaves));
+    }
+
+    /// Returns the corner or center frequency of a band in Hz
+    pub fn frequency(&self, band: Band) -> Hertz {
+        match band {
+            Band::Low => self.low_freq,
+            Band::Mid => self.mid_freq,
+            Band::High => self.high_freq,
+        }
+    }
+
+    /// Returns the Q of a band
+    pub fn q(&self, band: Band) -> f32 {
+        match band {
+            Band::Low => self.low_q,
+            Band::Mid => self.mid_q,
+            Band::High => self.high_q,
+        }
+    }
+
+    /// Recreate the filter of a band from its current gain, frequency and Q
+    ///
+    /// The setters validate their input, so this only fails on values the
+    /// constructor already rejected; the previous filter is kept in that case.
+    fn rebuild(&mut self, band: Band) {
+        let result = match band {
+            Band::Low => Self::create_low_shelf(self.sample_rate, self.low_gain, self.low_freq, self.low_q)
+                .map(|filter| self.low_filter = filter),
+            Band::Mid => Self::create_band_pass(self.sample_rate, self.mid_gain, self.mid_freq, self.mid_q)
+                .map(|filter| self.mid_filter = filter),
+            Band::High => Self::create_high_shelf(self.sample_rate, self.high_gain, self.high_freq, self.high_q)
+                .map(|filter| self.high_filter = filter),
+        };
+        if let Err(error) = result {
+            log::warn!("ParametricEQ: cannot rebuild the {:?} band filter: {:?}", band, error);
         }
     }
     
@@ -82,7 +202,7 @@ impl ParametricEQ {
             // Aplicar una función de transferencia suave para valores cercanos a los límites
             let sign = result.signum();
             let x = (result.abs() - SOFT_LIMIT_THRESHOLD) / (1.0 - SOFT_LIMIT_THRESHOLD);
-            let y = x.tanh(); // Suaviza la transición
+            let y = libm::tanhf(x); // Suaviza la transición
             sign * (SOFT_LIMIT_THRESHOLD + (1.0 - SOFT_LIMIT_THRESHOLD) * y)
         } else {
             result
@@ -96,53 +216,37 @@ impl ParametricEQ {
         }
     }
     
-    fn create_low_shelf(sample_rate: f32, gain_db: f32) -> DirectForm1<f32> {
-        // Usar una frecuencia de corte más baja para mejor separación de bandas
-        let freq = 250.0; // Hz
-        // Usar un Q más alto para una transición más pronunciada
-        let q = 0.707; // Q de Butterworth
-        
+    fn create_low_shelf(sample_rate: f32, gain_db: f32, freq: Hertz, q: f32) -> Result<DirectForm1<f32>, FilterError> {
         let coeffs = Coefficients::<f32>::from_params(
             FilterType::LowShelf(gain_db),
-            sample_rate.hz(),
-            freq.hz(),
+            biquad::Hertz::<f32>::from_hz(sample_rate)?,
+            biquad::Hertz::<f32>::from_hz(freq)?,
             q,
-        ).unwrap();
+        )?;
         
-        DirectForm1::<f32>::new(coeffs)
+        Ok(DirectForm1::<f32>::new(coeffs))
     }
     
-    fn create_band_pass(sample_rate: f32, gain_db: f32) -> DirectForm1<f32> {
-        // Usar una frecuencia central en la mitad geométrica del rango medio
-        let center_freq = (200.0f32 * 3000.0f32).sqrt(); // ≈ 775 Hz
-        // Usar un ancho de banda de 2 octavas para mejor cobertura
-        let bandwidth = center_freq / 2.0; // 1 octava a cada lado
-        let q = center_freq / bandwidth; // Q ≈ 1.0
-        
+    fn create_band_pass(sample_rate: f32, gain_db: f32, center_freq: Hertz, q: f32) -> Result<DirectForm1<f32>, FilterError> {
         let coeffs = Coefficients::<f32>::from_params(
             FilterType::PeakingEQ(gain_db),
-            sample_rate.hz(),
-            center_freq.hz(),
+            biquad::Hertz::<f32>::from_hz(sample_rate)?,
+            biquad::Hertz::<f32>::from_hz(center_freq)?,
             q,
-        ).unwrap();
+        )?;
         
-        DirectForm1::<f32>::new(coeffs)
+        Ok(DirectForm1::<f32>::new(coeffs))
     }
     
-    fn create_high_shelf(sample_rate: f32, gain_db: f32) -> DirectForm1<f32> {
-        // Usar una frecuencia de corte más alta para mejor separación de bandas
-        let freq = 2500.0; // Hz
-        // Usar un Q más alto para una transición más pronunciada
-        let q = 0.707; // Q de Butterworth
-        
+    fn create_high_shelf(sample_rate: f32, gain_db: f32, freq: Hertz, q: f32) -> Result<DirectForm1<f32>, FilterError> {
         let coeffs = Coefficients::<f32>::from_params(
             FilterType::HighShelf(gain_db),
-            sample_rate.hz(),
-            freq.hz(),
+            biquad::Hertz::<f32>::from_hz(sample_rate)?,
+            biquad::Hertz::<f32>::from_hz(freq)?,
             q,
-        ).unwrap();
+        )?;
         
-        DirectForm1::<f32>::new(coeffs)
+        Ok(DirectForm1::<f32>::new(coeffs))
     }
 }
 
@@ -157,6 +261,68 @@ pub enum Band {
     High,
 }
 
+/// Error returned by [`ParametricEQ::new`] when a band filter cannot be created
+///
+/// Carries no heap data so that it is available without `alloc`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EqError {
+    band: Band,
+    frequency: Hertz,
+    sample_rate: f32,
+}
+
+impl EqError {
+    /// Band whose filter could not be created
+    pub fn band(&self) -> Band {
+        self.band
+    }
+
+    /// Frequency of that band in Hz
+    pub fn frequency(&self) -> f32 {
+        self.frequency
+    }
+
+    /// Sample rate the equalizer was created for, in Hz
+    pub fn sample_rate(&self) -> f32 {
+        self.sample_rate
+    }
+}
+
+impl fmt::Display for EqError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let band = match self.band {
+            Band::Low => "low shelf",
+            Band::Mid => "mid peak",
+            Band::High => "high shelf",
+        };
+        write!(
+            f,
+            "Cannot create the {} filter at {} Hz for a sample rate of {} Hz",
+            band, self.frequency, self.sample_rate
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for EqError {}
+
+/// Converts a bandwidth in octaves to the equivalent filter Q
+///
+/// Uses the analog relation `Q = sqrt(2^N) / (2^N - 1)`: 1 octave gives
+/// Q ≈ 1.414, 2 octaves Q ≈ 0.667. The result is only meaningful for positive
+/// bandwidths; zero gives an infinite Q and negative values a negative one.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::q_from_bandwidth_octaves;
+///
+/// assert!((q_from_bandwidth_octaves(1.0) - core::f32::consts::SQRT_2).abs() < 1e-6);
+/// ```
+pub fn q_from_bandwidth_octaves(bw_octaves: f32) -> f32 {
+    let ratio = libm::exp2f(bw_octaves);
+    libm::sqrtf(ratio) / (ratio - 1.0)
+}
+
 /// Applies parametric equalization to the input buffer
 /// 
 /// # Arguments
@@ -167,21 +333,29 @@ pub enum Band {
 /// * `high_gain` - Gain for high frequencies (>3000 Hz) in dB
 /// 
 /// # Returns
-/// New buffer with equalization applied
+/// New buffer with equalization applied, or an unchanged copy of `input` if
+/// the equalizer cannot be created for `sample_rate`
+#[cfg(feature = "alloc")]
 pub fn parametric_eq(input: &[f32], sample_rate: f32, low_gain: f32, mid_gain: f32, high_gain: f32) -> Vec<f32> {
     // Limitar las ganancias para evitar saturación extrema
     let low_gain = low_gain.clamp(-12.0, 12.0);
     let mid_gain = mid_gain.clamp(-12.0, 12.0);
     let high_gain = high_gain.clamp(-12.0, 12.0);
     
-    let mut eq = ParametricEQ::new(sample_rate, low_gain, mid_gain, high_gain);
     let mut output = input.to_vec();
+    let mut eq = match ParametricEQ::new(sample_rate, low_gain, mid_gain, high_gain) {
+        Ok(eq) => eq,
+        Err(error) => {
+            log::warn!("parametric_eq: {}", error);
+            return output;
+        }
+    };
     
     // Escalar la señal de entrada para dejar espacio para las ganancias
     let input_peak = input.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
     let scale_factor = if input_peak > 0.0 {
         // Dejar espacio para la ganancia máxima que podríamos aplicar
-        let max_gain = 10.0f32.powf(low_gain.max(mid_gain).max(high_gain).abs() / 20.0);
+        let max_gain = db_to_linear(low_gain.max(mid_gain).max(high_gain).abs());
         (1.0f32 / max_gain).min(1.0f32)
     } else {
         1.0f32
@@ -399,6 +573,108 @@ mod tests {
         assert!(empty_processed.is_empty(), "El ecualizador debería manejar señales vacías");
     }
     
+    /// Ganancia en dB que aplica el ecualizador a una senoide, ignorando el transitorio inicial
+    fn sine_gain_db(eq: &mut ParametricEQ, freq: f32, sample_rate: f32) -> f32 {
+        let input: Vec<f32> = (0..sample_rate as usize)
+            .map(|i| 0.1 * (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate).sin())
+            .collect();
+        let mut output = input.clone();
+        eq.process_buffer(&mut output);
+
+        let skip = input.len() / 4;
+        let energy = |x: &[f32]| x[skip..].iter().map(|v| v * v).sum::<f32>();
+        10.0 * (energy(&output) / energy(&input)).log10()
+    }
+
+    #[test]
+    fn test_set_frequency_moves_low_shelf() {
+        let sample_rate = 44100.0;
+        let mut eq = ParametricEQ::new(sample_rate, 12.0, 0.0, 0.0).unwrap();
+
+        // Con el corte en 100 Hz solo se realzan los graves profundos
+        eq.set_frequency(Band::Low, 100.0);
+        assert_eq!(eq.frequency(Band::Low), 100.0);
+        let deep_low = sine_gain_db(&mut eq, 40.0, sample_rate);
+        let low_mid = sine_gain_db(&mut eq, 800.0, sample_rate);
+        assert!(deep_low > 10.0, "40 Hz boost {:.2} dB", deep_low);
+        assert!(low_mid < 1.0, "800 Hz boost {:.2} dB", low_mid);
+
+        // Al subir el corte a 2 kHz también se realzan los 800 Hz
+        eq.set_frequency(Band::Low, 2000.0);
+        let low_mid = sine_gain_db(&mut eq, 800.0, sample_rate);
+        assert!(low_mid > 9.0, "800 Hz boost {:.2} dB", low_mid);
+
+        // Las frecuencias fuera de rango se ignoran
+        eq.set_frequency(Band::Low, sample_rate);
+        eq.set_frequency(Band::Low, -10.0);
+        assert_eq!(eq.frequency(Band::Low), 2000.0);
+    }
+
+    #[test]
+    fn test_set_q_narrows_mid_band() {
+        let sample_rate = 44100.0;
+        let mut eq = ParametricEQ::new(sample_rate, 0.0, 12.0, 0.0).unwrap();
+        eq.set_frequency(Band::Mid, 1000.0);
+
+        // Una octava por encima del centro, un Q alto deja pasar la señal casi intacta
+        eq.set_q(Band::Mid, 0.5);
+        let wide = sine_gain_db(&mut eq, 2000.0, sample_rate);
+        eq.set_q(Band::Mid, 8.0);
+        let narrow = sine_gain_db(&mut eq, 2000.0, sample_rate);
+        assert!(narrow < wide - 3.0, "Wide {:.2} dB, narrow {:.2} dB", wide, narrow);
+
+        // El centro conserva el realce completo
+        let center = sine_gain_db(&mut eq, 1000.0, sample_rate);
+        assert!((center - 12.0).abs() < 0.5, "Center boost {:.2} dB", center);
+
+        eq.set_q(Band::Mid, 0.0);
+        assert_eq!(eq.q(Band::Mid), 8.0);
+    }
+
+    #[test]
+    fn test_bandwidth_octaves_sets_q() {
+        assert!((q_from_bandwidth_octaves(1.0) - 1.414).abs() < 1e-3);
+        assert!((q_from_bandwidth_octaves(2.0) - 0.667).abs() < 1e-3);
+
+        // Un realce de 6 dB y una octava de ancho: en los bordes de la banda,
+        // media octava por debajo y por encima del centro, queda a 3 dB del pico
+        let sample_rate = 44100.0;
+        let mut eq = ParametricEQ::new(sample_rate, 0.0, 6.0, 0.0).unwrap();
+        eq.set_frequency(Band::Mid, 1000.0);
+        eq.set_bandwidth_octaves(Band::Mid, 1.0);
+        assert!((eq.q(Band::Mid) - core::f32::consts::SQRT_2).abs() < 1e-4);
+
+        let center = sine_gain_db(&mut eq, 1000.0, sample_rate);
+        let lower = sine_gain_db(&mut eq, 1000.0 / core::f32::consts::SQRT_2, sample_rate);
+        let upper = sine_gain_db(&mut eq, 1000.0 * core::f32::consts::SQRT_2, sample_rate);
+        assert!((center - 6.0).abs() < 0.3, "Center boost {:.2} dB", center);
+        assert!((center - lower - 3.0).abs() < 0.3, "Lower edge {:.2} dB", lower);
+        assert!((center - upper - 3.0).abs() < 0.3, "Upper edge {:.2} dB", upper);
+
+        eq.set_bandwidth_octaves(Band::Mid, 0.0);
+        assert!((eq.q(Band::Mid) - core::f32::consts::SQRT_2).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_new_rejects_band_above_nyquist() {
+        // A 4 kHz sample rate puts the 2.5 kHz high shelf above Nyquist
+        match ParametricEQ::new(4000.0, 0.0, 0.0, 0.0) {
+            Err(error) => {
+                assert_eq!(error.band(), Band::High);
+                assert_eq!(error.frequency(), DEFAULT_HIGH_FREQ);
+                let message = crate::AudioProcessingError::from(error).to_string();
+                assert!(message.contains("high shelf"), "{}", message);
+            }
+            Ok(_) => panic!("Expected an error for a band above Nyquist"),
+        }
+        assert!(ParametricEQ::new(0.0, 0.0, 0.0, 0.0).is_err());
+        assert!(ParametricEQ::new(f32::NAN, 0.0, 0.0, 0.0).is_err());
+
+        // The free function leaves the signal untouched instead of panicking
+        let input = vec![0.5, -0.25, 0.125];
+        assert_eq!(parametric_eq(&input, 4000.0, 6.0, 0.0, 0.0), input);
+    }
+
     #[test]
     fn test_no_clipping() {
         let sample_rate = 44100.0;
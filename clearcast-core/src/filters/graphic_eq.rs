@@ -0,0 +1,196 @@
+//! Graphic equalizer implementation using a bank of peaking biquad filters
+//!
+//! Unlike the parametric equalizer, the band frequencies of a graphic
+//! equalizer are fixed at construction time and only their gains change.
+
+use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType};
+use biquad::frequency::*;
+
+use crate::effects::AudioEffect;
+
+/// Center frequencies of the standard 10-band ISO octave graphic equalizer (in Hz)
+pub const ISO_OCTAVE_CENTERS: [f32; 10] = [
+    31.25, 62.5, 125.0, 250.0, 500.0, 1000.0, 2000.0, 4000.0, 8000.0, 16000.0,
+];
+
+/// Q giving each band a bandwidth of one octave
+const OCTAVE_Q: f32 = std::f32::consts::SQRT_2;
+
+/// One fixed-frequency band of the graphic equalizer
+#[derive(Debug, Clone)]
+struct GraphicBand {
+    center_freq: f32,
+    gain_db: f32,
+    /// `None` when the center frequency is not below Nyquist; the band is then bypassed
+    filter: Option<DirectForm1<f32>>,
+}
+
+/// Fixed-frequency graphic equalizer
+///
+/// Each band is a peaking filter at its center frequency, all bands share the
+/// same Q, and every band starts flat (0 dB).
+///
+/// # Example
+/// ```
+/// use clearcast_core::effects::AudioEffect;
+/// use clearcast_core::filters::GraphicEq;
+///
+/// let mut eq = GraphicEq::iso_10_band(44100.0);
+/// eq.set_gain(5, 6.0); // +6 dB at 1 kHz
+/// let mut buffer = vec![0.1, -0.2, 0.3];
+/// eq.process_buffer(&mut buffer);
+/// ```
+#[derive(Debug, Clone)]
+pub struct GraphicEq {
+    sample_rate: f32,
+    q: f32,
+    bands: Vec<GraphicBand>,
+}
+
+impl GraphicEq {
+    /// Creates a graphic equalizer with the given band centers
+    ///
+    /// # Arguments
+    /// * `sample_rate` - The sample rate of the audio in Hz
+    /// * `center_freqs` - Center frequency of each band in Hz
+    /// * `q` - Quality factor shared by all bands (higher = narrower bands)
+    pub fn new(sample_rate: f32, center_freqs: &[f32], q: f32) -> Self {
+        let q = q.max(0.01);
+        let bands = center_freqs
+            .iter()
+            .map(|&center_freq| GraphicBand {
+                center_freq,
+                gain_db: 0.0,
+                filter: Self::create_peaking(sample_rate, center_freq, q, 0.0)
+                    .map(DirectForm1::<f32>::new),
+            })
+            .collect();
+
+        Self { sample_rate, q, bands }
+    }
+
+    /// Creates the standard 10-band ISO octave equalizer (31.25 Hz to 16 kHz)
+    pub fn iso_10_band(sample_rate: f32) -> Self {
+        Self::new(sample_rate, &ISO_OCTAVE_CENTERS, OCTAVE_Q)
+    }
+
+    /// Sets the gain of a band in dB
+    ///
+    /// Out-of-range band indices are ignored. The filter state is kept, so
+    /// gains can be changed while audio is playing.
+    pub fn set_gain(&mut self, band: usize, gain_db: f32) {
+        let Some(band) = self.bands.get_mut(band) else {
+            log::warn!("GraphicEq::set_gain: band {} does not exist", band);
+            return;
+        };
+
+        band.gain_db = gain_db;
+        if let (Some(filter), Some(coeffs)) = (
+            band.filter.as_mut(),
+            Self::create_peaking(self.sample_rate, band.center_freq, self.q, gain_db),
+        ) {
+            filter.update_coefficients(coeffs);
+        }
+    }
+
+    /// Returns the gain of a band in dB, or `None` if the band does not exist
+    pub fn gain(&self, band: usize) -> Option<f32> {
+        self.bands.get(band).map(|band| band.gain_db)
+    }
+
+    /// Returns the number of bands
+    pub fn num_bands(&self) -> usize {
+        self.bands.len()
+    }
+
+    /// Returns the center frequency of every band in Hz
+    pub fn center_freqs(&self) -> Vec<f32> {
+        self.bands.iter().map(|band| band.center_freq).collect()
+    }
+
+    fn create_peaking(sample_rate: f32, center_freq: f32, q: f32, gain_db: f32) -> Option<Coefficients<f32>> {
+        Coefficients::<f32>::from_params(
+            FilterType::PeakingEQ(gain_db),
+            sample_rate.hz(),
+            center_freq.hz(),
+            q,
+        )
+        .ok()
+    }
+}
+
+impl AudioEffect for GraphicEq {
+    fn process_sample(&mut self, sample: f32) -> f32 {
+        self.bands
+            .iter_mut()
+            .filter_map(|band| band.filter.as_mut())
+            .fold(sample, |acc, filter| filter.run(acc))
+    }
+
+    fn reset(&mut self) {
+        for filter in self.bands.iter_mut().filter_map(|band| band.filter.as_mut()) {
+            filter.reset_state();
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "GraphicEq"
+    }
+
+    fn clone_box(&self) -> Option<Box<dyn AudioEffect>> {
+        Some(Box::new(self.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sine(freq: f32, sample_rate: f32, num_samples: usize) -> Vec<f32> {
+        (0..num_samples)
+            .map(|i| 0.25 * (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate).sin())
+            .collect()
+    }
+
+    /// Change in energy (dB) after the EQ, ignoring the filter settling time
+    fn gain_db(eq: &mut GraphicEq, input: &[f32]) -> f32 {
+        eq.reset();
+        let mut output = input.to_vec();
+        eq.process_buffer(&mut output);
+
+        let skip = input.len() / 4;
+        let energy = |x: &[f32]| x[skip..].iter().map(|v| v * v).sum::<f32>();
+        10.0 * (energy(&output) / energy(input)).log10()
+    }
+
+    #[test]
+    fn test_graphic_eq_boosts_only_its_band() {
+        let sample_rate = 44100.0;
+        let mut eq = GraphicEq::iso_10_band(sample_rate);
+        assert_eq!(eq.num_bands(), 10);
+        assert_eq!(eq.center_freqs()[5], 1000.0);
+
+        eq.set_gain(5, 12.0);
+        assert_eq!(eq.gain(5), Some(12.0));
+
+        let boosted = gain_db(&mut eq, &sine(1000.0, sample_rate, 22050));
+        let untouched = gain_db(&mut eq, &sine(100.0, sample_rate, 22050));
+
+        assert!((boosted - 12.0).abs() < 1.0, "1 kHz should gain ~12 dB, got {:.2} dB", boosted);
+        assert!(untouched.abs() < 0.5, "100 Hz should be roughly unchanged, got {:.2} dB", untouched);
+    }
+
+    #[test]
+    fn test_graphic_eq_bypasses_bands_above_nyquist() {
+        // A 22.05 kHz sample rate puts the 16 kHz band above Nyquist
+        let mut eq = GraphicEq::iso_10_band(22050.0);
+        eq.set_gain(9, 12.0);
+        eq.set_gain(42, 12.0);
+
+        let input = sine(440.0, 22050.0, 1024);
+        let mut output = input.clone();
+        eq.process_buffer(&mut output);
+        assert!(output.iter().zip(&input).all(|(a, b)| (a - b).abs() < 1e-4));
+        assert_eq!(eq.gain(42), None);
+    }
+}
@@ -1,14 +1,80 @@
 //! Audio filters for ClearCast
+//!
+//! Without the `std` feature only [`ParametricEQ`] and the gain helpers are
+//! available.
 
-pub mod compressor;
 pub mod equalizer;
+#[cfg(feature = "std")]
+pub mod butterworth;
+#[cfg(feature = "std")]
+pub mod comb;
+#[cfg(feature = "std")]
+pub mod compressor;
+#[cfg(feature = "std")]
+pub mod convolution;
+#[cfg(feature = "std")]
+pub mod crossover;
+#[cfg(feature = "std")]
+pub mod envelope;
+#[cfg(feature = "std")]
+pub mod graphic_eq;
+#[cfg(feature = "std")]
 pub mod wiener_filter;
+#[cfg(feature = "std")]
 pub mod multiband;
+#[cfg(feature = "std")]
+pub mod notch;
+#[cfg(feature = "std")]
+pub mod spectral_gate;
+
+pub use equalizer::{Band, EqError, ParametricEQ};
+#[cfg(feature = "alloc")]
+pub use equalizer::{parametric_eq, q_from_bandwidth_octaves};
+#[cfg(feature = "std")]
+pub use butterworth::{butterworth_highpass, butterworth_lowpass, ButterworthFilter};
+#[cfg(feature = "std")]
+pub use comb::{AllpassFilter, CombFilter};
+#[cfg(feature = "std")]
+pub use compressor::{
+    compress_rms, compress_rms_stereo, compress_rms_with_gain, compress_rms_with_params, Compressor, CompressorParams,
+};
+#[cfg(feature = "std")]
+pub use convolution::convolve;
+#[cfg(feature = "std")]
+pub use crossover::{split_bands, sum_bands};
+#[cfg(feature = "std")]
+pub use envelope::{analyze_envelope, EnvelopeFollower, EnvelopeMode};
+#[cfg(feature = "std")]
+pub use graphic_eq::{GraphicEq, ISO_OCTAVE_CENTERS};
+#[cfg(feature = "std")]
+pub use multiband::{MultibandCompressor, MultibandReport, BandParams};
+#[cfg(feature = "std")]
+pub use notch::{notch, remove_hum};
+#[cfg(feature = "std")]
+pub use spectral_gate::spectral_gate;
+#[cfg(feature = "std")]
+pub use wiener_filter::{reduce_noise_wiener, estimate_noise_profile, estimate_noise_profile_with, WienerDenoiser};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 
-pub use compressor::compress_rms;
-pub use equalizer::{parametric_eq, Band, ParametricEQ};
-pub use multiband::{MultibandCompressor, BandParams};
-pub use wiener_filter::{reduce_noise_wiener, estimate_noise_profile};
+use crate::utils::Sample;
+
+/// Applies a simple gain to a buffer in place
+///
+/// The allocation-free counterpart of [`apply_gain`], available without
+/// `std` or `alloc`.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::apply_gain_in_place;
+/// let mut buffer = [1.0, 0.5, -0.5, -1.0];
+/// apply_gain_in_place(&mut buffer, 2.0);
+/// assert_eq!(buffer, [2.0, 1.0, -1.0, -2.0]);
+/// ```
+pub fn apply_gain_in_place<T: Sample>(buffer: &mut [T], gain: T) {
+    crate::utils::simd::scale_in_place(buffer, gain);
+}
 
 /// Applies a simple gain to the audio signal
 /// 
@@ -17,7 +83,7 @@ pub use wiener_filter::{reduce_noise_wiener, estimate_noise_profile};
 /// * `gain` - Gain factor to apply (1.0 = no change)
 /// 
 /// # Returns
-/// New buffer with gain applied
+/// New buffer with gain applied, in the same precision as `input`
 /// 
 /// # Example
 /// ```
@@ -26,8 +92,251 @@ pub use wiener_filter::{reduce_noise_wiener, estimate_noise_profile};
 /// let output = apply_gain(&input, 2.0);
 /// assert_eq!(output, vec![2.0, 1.0, -1.0, -2.0]);
 /// ```
-pub fn apply_gain(input: &[f32], gain: f32) -> Vec<f32> {
-    input.iter().map(|x| x * gain).collect()
+#[cfg(feature = "alloc")]
+pub fn apply_gain<T: Sample>(input: &[T], gain: T) -> Vec<T> {
+    let mut output = input.to_vec();
+    apply_gain_in_place(&mut output, gain);
+    output
+}
+
+/// Applies a gain to the audio signal and soft-limits the result to `ceiling`
+///
+/// Unlike [`apply_gain`], which lets boosted samples go past full scale,
+/// the gained signal goes through the same soft limiter as [`limit`]: if any
+/// sample ends up above the ceiling, only the top 10% below it is bent, and
+/// everything quieter keeps its exact shape.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `gain` - Linear gain factor
+/// * `ceiling` - Maximum output level (0.0 to 1.0)
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::apply_gain_limited;
+/// let output = apply_gain_limited(&[0.1, 0.5, -0.8], 4.0, 0.9);
+/// assert_eq!(output[0], 0.4);
+/// assert!(output.iter().all(|x| x.abs() <= 0.9));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn apply_gain_limited(input: &[f32], gain: f32, ceiling: f32) -> Vec<f32> {
+    let mut output = apply_gain(input, gain);
+    limit_in_place(&mut output, ceiling);
+    output
+}
+
+/// Multiplies the audio signal by a gain envelope, sample by sample
+///
+/// Useful for fades and ducking drawn as automation curves. The envelope holds
+/// one linear gain factor per input sample.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `gain_envelope` - Linear gain for each sample, as long as `input`
+///
+/// # Returns
+/// New buffer with the envelope applied, or a `ProcessingError` if the
+/// lengths differ
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::apply_gain_envelope;
+/// let output = apply_gain_envelope(&[1.0, 1.0, 1.0], &[0.0, 0.5, 1.0]).unwrap();
+/// assert_eq!(output, vec![0.0, 0.5, 1.0]);
+/// assert!(apply_gain_envelope(&[1.0, 1.0], &[0.5]).is_err());
+/// ```
+#[cfg(feature = "alloc")]
+pub fn apply_gain_envelope(input: &[f32], gain_envelope: &[f32]) -> Result<Vec<f32>, crate::AudioProcessingError> {
+    if input.len() != gain_envelope.len() {
+        return Err(crate::AudioProcessingError::ProcessingError(alloc::format!(
+            "Gain envelope has {} samples but the input has {}",
+            gain_envelope.len(),
+            input.len()
+        )));
+    }
+
+    Ok(input.iter().zip(gain_envelope).map(|(x, g)| x * g).collect())
+}
+
+/// Applies a gain that changes linearly from `start_gain` to `end_gain`
+///
+/// The first sample is scaled by `start_gain` and the last one by `end_gain`,
+/// so a ramp from 0.0 to 1.0 is a linear fade-in over the whole buffer.
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::apply_gain_ramp;
+/// assert_eq!(apply_gain_ramp(&[1.0; 5], 0.0, 1.0), vec![0.0, 0.25, 0.5, 0.75, 1.0]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn apply_gain_ramp(input: &[f32], start_gain: f32, end_gain: f32) -> Vec<f32> {
+    let steps = input.len().saturating_sub(1).max(1) as f32;
+    input
+        .iter()
+        .enumerate()
+        .map(|(i, x)| x * (start_gain + (end_gain - start_gain) * i as f32 / steps))
+        .collect()
+}
+
+/// Scales the audio signal so its peak lands exactly on `target_peak`
+///
+/// A single linear gain with no limiting at all, so the relative dynamics of
+/// the signal are untouched, unlike the engine's normalization, which runs
+/// alongside its limiter. Non-finite samples don't count for the peak, and a
+/// silent buffer is returned unchanged.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `target_peak` - Peak level of the output (linear, e.g. 0.95)
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::normalize_peak;
+/// assert_eq!(normalize_peak(&[0.1, -0.4, 0.2], 0.8), vec![0.2, -0.8, 0.4]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn normalize_peak(input: &[f32], target_peak: f32) -> Vec<f32> {
+    let peak = crate::utils::simd::peak_abs(input);
+    if peak == 0.0 {
+        return input.to_vec();
+    }
+
+    let gain = target_peak / peak;
+    input
+        .iter()
+        .map(|&x| {
+            // Pin the peak itself so rounding can't leave it a hair off target
+            if x.abs() != peak {
+                x * gain
+            } else if x < 0.0 {
+                -target_peak
+            } else {
+                target_peak
+            }
+        })
+        .collect()
+}
+
+/// Fraction of the ceiling used as the knee of [`limit`]
+#[cfg(feature = "alloc")]
+const LIMIT_KNEE_FRACTION: f32 = 0.1;
+
+/// Soft-limits the audio signal so its peaks stay at or below `ceiling`
+///
+/// Runs the buffer through a [`SoftLimiter`](crate::effects::SoftLimiter)
+/// whose knee spans the top 10% below the ceiling. A signal whose peak is
+/// already at or below the ceiling is returned unchanged.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `ceiling` - Maximum output level (0.0 to 1.0)
+///
+/// # Returns
+/// New buffer with peaks limited to `ceiling`
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::limit;
+/// let output = limit(&[0.2, -1.5, 3.0], 0.8);
+/// assert_eq!(output[0], 0.2);
+/// assert!(output.iter().all(|x| x.abs() <= 0.8));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn limit(input: &[f32], ceiling: f32) -> Vec<f32> {
+    let mut output = input.to_vec();
+    limit_in_place(&mut output, ceiling);
+    output
+}
+
+/// In-place body of [`limit`]
+#[cfg(feature = "alloc")]
+fn limit_in_place(buffer: &mut [f32], ceiling: f32) {
+    use crate::effects::{AudioEffect, SoftLimiter};
+
+    let ceiling = ceiling.clamp(0.0, 1.0);
+    if buffer.iter().all(|x| x.abs() <= ceiling) {
+        return;
+    }
+
+    let mut limiter = SoftLimiter::new(ceiling * (1.0 - LIMIT_KNEE_FRACTION), ceiling * LIMIT_KNEE_FRACTION);
+    limiter.process_buffer(buffer);
+    // The limiter has a minimum threshold; keep very low ceilings exact
+    for x in buffer.iter_mut() {
+        *x = x.clamp(-ceiling, ceiling);
+    }
+}
+
+/// Inverts the polarity of the audio signal
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+///
+/// # Returns
+/// New buffer with every sample negated
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::invert_polarity;
+/// assert_eq!(invert_polarity(&[0.5, -1.0]), vec![-0.5, 1.0]);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn invert_polarity(input: &[f32]) -> Vec<f32> {
+    input.iter().map(|&x| -x).collect()
+}
+
+/// Applies a first-order pre-emphasis filter (`y[n] = x[n] - coeff * x[n-1]`)
+///
+/// Boosts high frequencies relative to low ones, typically before noise
+/// reduction or encoding. Undo it with [`de_emphasis`] and the same coefficient.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `coeff` - Emphasis coefficient (typically 0.9 to 0.97)
+///
+/// # Returns
+/// New buffer with pre-emphasis applied
+#[cfg(feature = "alloc")]
+pub fn pre_emphasis(input: &[f32], coeff: f32) -> Vec<f32> {
+    let mut prev = 0.0;
+    input
+        .iter()
+        .map(|&x| {
+            let y = x - coeff * prev;
+            prev = x;
+            y
+        })
+        .collect()
+}
+
+/// Applies a first-order de-emphasis filter (`y[n] = x[n] + coeff * y[n-1]`)
+///
+/// The inverse of [`pre_emphasis`]. `coeff` must be below 1.0 in absolute
+/// value for the filter to be stable.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `coeff` - Emphasis coefficient used for the pre-emphasis
+///
+/// # Returns
+/// New buffer with de-emphasis applied
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::{de_emphasis, pre_emphasis};
+/// let input = vec![0.5, -0.25, 0.125];
+/// let restored = de_emphasis(&pre_emphasis(&input, 0.95), 0.95);
+/// assert!(restored.iter().zip(&input).all(|(a, b)| (a - b).abs() < 1e-6));
+/// ```
+#[cfg(feature = "alloc")]
+pub fn de_emphasis(input: &[f32], coeff: f32) -> Vec<f32> {
+    let mut prev = 0.0;
+    input
+        .iter()
+        .map(|&x| {
+            prev = x + coeff * prev;
+            prev
+        })
+        .collect()
 }
 
 /// Applies a simple low-pass filter (first-order IIR)
@@ -38,6 +347,7 @@ pub fn apply_gain(input: &[f32], gain: f32) -> Vec<f32> {
 /// 
 /// # Returns
 /// New buffer with low-pass filter applied
+#[cfg(feature = "alloc")]
 pub fn low_pass(input: &[f32], alpha: f32) -> Vec<f32> {
     if input.is_empty() {
         return Vec::new();
@@ -68,6 +378,129 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_limit() {
+        let loud: Vec<f32> = (0..4096).map(|i| 3.0 * (i as f32 * 0.05).sin()).collect();
+        for ceiling in [1.0, 0.8, 0.25] {
+            let output = limit(&loud, ceiling);
+            assert_eq!(output.len(), loud.len());
+            assert!(output.iter().all(|x| x.abs() <= ceiling), "Peak above {}", ceiling);
+        }
+
+        // Quiet samples of a limited signal are untouched
+        let output = limit(&loud, 0.8);
+        for (y, x) in output.iter().zip(&loud) {
+            if x.abs() <= 0.72 {
+                assert_eq!(y, x);
+            }
+        }
+
+        // A signal already below the ceiling passes through unchanged
+        let quiet: Vec<f32> = loud.iter().map(|x| x * 0.25).collect();
+        assert_eq!(limit(&quiet, 0.8), quiet);
+        assert!(limit(&[], 0.8).is_empty());
+    }
+
+    #[test]
+    fn test_apply_gain_limited() {
+        let input: Vec<f32> = (0..4096).map(|i| 0.5 * (i as f32 * 0.05).sin()).collect();
+
+        // +18 dB would take the peaks to 4.0
+        let raw = apply_gain(&input, 8.0);
+        assert!(raw.iter().any(|x| x.abs() > 1.0));
+
+        let output = apply_gain_limited(&input, 8.0, 0.9);
+        assert_eq!(output.len(), input.len());
+        assert!(output.iter().all(|x| x.abs() <= 0.9));
+
+        // Below the knee the gained signal is untouched, and the limited
+        // part still follows the waveform's ups and downs
+        for (y, r) in output.iter().zip(&raw) {
+            if r.abs() <= 0.81 {
+                assert_eq!(y, r);
+            }
+        }
+        for (y, r) in output.windows(2).zip(raw.windows(2)) {
+            assert!((y[1] - y[0]) * (r[1] - r[0]) >= 0.0);
+        }
+
+        // Without anything above the ceiling it is a plain gain
+        assert_eq!(apply_gain_limited(&input, 1.5, 0.9), apply_gain(&input, 1.5));
+    }
+
+    #[test]
+    fn test_gain_envelope_and_ramp() {
+        let input: Vec<f32> = (0..1000).map(|i| 0.4 * (i as f32 * 0.07).sin()).collect();
+
+        let doubled = apply_gain_envelope(&input, &vec![2.0; input.len()]).unwrap();
+        assert_eq!(doubled, apply_gain(&input, 2.0));
+        assert!(apply_gain_envelope(&input, &[2.0; 999]).is_err());
+
+        // A 0 -> 1 ramp on a constant signal is a straight line between the endpoints
+        let fade = apply_gain_ramp(&[0.8; 101], 0.0, 1.0);
+        assert_eq!(fade[0], 0.0);
+        assert!((fade[100] - 0.8).abs() < 1e-6);
+        for (i, y) in fade.iter().enumerate() {
+            assert!((y - 0.008 * i as f32).abs() < 1e-5, "sample {}: {}", i, y);
+        }
+
+        // The ramp equals the matching envelope
+        let envelope: Vec<f32> = (0..input.len()).map(|i| 1.0 - i as f32 / 999.0).collect();
+        let ramped = apply_gain_ramp(&input, 1.0, 0.0);
+        for (r, e) in ramped.iter().zip(apply_gain_envelope(&input, &envelope).unwrap()) {
+            assert!((r - e).abs() < 1e-6);
+        }
+        assert!(apply_gain_ramp(&[], 0.0, 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_peak() {
+        let input: Vec<f32> = (0..4096)
+            .map(|i| 0.37 * (i as f32 * 0.05).sin() * (1.0 + (i as f32 * 0.001).cos()))
+            .collect();
+        let input_peak = input.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+
+        for target in [0.95, 0.5, 1.0, 0.123] {
+            let output = normalize_peak(&input, target);
+            let peak = output.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+            assert_eq!(peak, target);
+
+            // Every sample gets the same gain: no limiting or clamping
+            let gain = target / input_peak;
+            for (y, x) in output.iter().zip(&input) {
+                assert!((y - x * gain).abs() <= 1e-6 * target, "{} != {} * {}", y, x, gain);
+            }
+        }
+
+        // Silence and empty buffers pass through
+        assert_eq!(normalize_peak(&[0.0; 8], 0.9), vec![0.0; 8]);
+        assert!(normalize_peak(&[], 0.9).is_empty());
+    }
+
+    #[test]
+    fn test_invert_polarity() {
+        let input = vec![1.0, 0.5, 0.0, -0.25];
+        let inverted = invert_polarity(&input);
+        assert_eq!(inverted, vec![-1.0, -0.5, -0.0, 0.25]);
+        assert_eq!(invert_polarity(&inverted), input);
+    }
+
+    #[test]
+    fn test_emphasis_round_trip() {
+        let input: Vec<f32> = (0..4096)
+            .map(|i| 0.5 * (i as f32 * 0.05).sin() + 0.3 * (i as f32 * 1.3).sin())
+            .collect();
+
+        let emphasized = pre_emphasis(&input, 0.97);
+        assert_ne!(emphasized, input);
+
+        let restored = de_emphasis(&emphasized, 0.97);
+        assert_eq!(restored.len(), input.len());
+        for (a, b) in restored.iter().zip(&input) {
+            assert!((a - b).abs() < 1e-5, "{} != {}", a, b);
+        }
+    }
+
     #[wasm_bindgen_test]
     fn test_low_pass() {
         let input = vec![0.0, 1.0, 0.0, 1.0, 0.0];
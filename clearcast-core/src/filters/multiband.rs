@@ -4,6 +4,7 @@
 //! multiple frequency bands and applies compression independently to each band.
 
 use crate::filters::compressor::compress_rms;
+use crate::AudioProcessingError;
 
 /// Parameters for a single band in the multiband compressor
 #[derive(Debug, Clone, Copy)]
@@ -35,6 +36,34 @@ impl Default for BandParams {
     }
 }
 
+/// Peak levels measured by [`MultibandCompressor::process_with_report`]
+///
+/// Each band can stay below full scale while their sum does not, so the
+/// per-band peaks show which band is driving the output over ±1.0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MultibandReport {
+    /// Peak absolute level of each compressed band, sorted by frequency
+    pub band_peaks: Vec<f32>,
+    /// Peak absolute level of the summed output
+    pub summed_peak: f32,
+}
+
+impl MultibandReport {
+    /// Returns true if the summed output exceeds full scale (±1.0)
+    pub fn is_clipping(&self) -> bool {
+        self.summed_peak > 1.0
+    }
+
+    /// Index of the band with the highest peak, or `None` without bands
+    pub fn dominant_band(&self) -> Option<usize> {
+        self.band_peaks
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, _)| i)
+    }
+}
+
 /// A multiband compressor that splits the audio into multiple frequency bands
 /// and applies compression independently to each band.
 pub struct MultibandCompressor {
@@ -49,34 +78,58 @@ pub struct MultibandCompressor {
 impl MultibandCompressor {
     /// Creates a new multiband compressor with the specified bands and sample rate.
     /// 
+    /// The bands may be given in any order; they are sorted by frequency.
+    /// 
     /// # Arguments
     /// * `bands` - Vector of band parameters
     /// * `sample_rate` - Sample rate in Hz
     /// 
-    /// # Panics
-    /// Panics if the bands overlap or don't cover the full frequency range.
-    pub fn new(bands: Vec<BandParams>, sample_rate: f32) -> Self {
+    /// # Errors
+    /// Returns `AudioProcessingError::ProcessingError` if a band has a zero or
+    /// negative width, if two bands overlap, or if a frequency is not finite or
+    /// lies outside 0 Hz to Nyquist.
+    pub fn new(bands: Vec<BandParams>, sample_rate: f32) -> Result<Self, AudioProcessingError> {
+        let nyquist = sample_rate / 2.0;
+        for (i, band) in bands.iter().enumerate() {
+            let in_range = |freq: f32| freq.is_finite() && (0.0..=nyquist).contains(&freq);
+            if !in_range(band.low_freq) || !in_range(band.high_freq) {
+                return Err(AudioProcessingError::ProcessingError(format!(
+                    "Band {} ({} Hz - {} Hz) is outside the valid range 0 Hz - {} Hz",
+                    i, band.low_freq, band.high_freq, nyquist
+                )));
+            }
+            if band.low_freq >= band.high_freq {
+                return Err(AudioProcessingError::ProcessingError(format!(
+                    "Band {} has an invalid frequency range: {} Hz - {} Hz",
+                    i, band.low_freq, band.high_freq
+                )));
+            }
+        }
+
+        let mut sorted_bands = bands;
+        sorted_bands.sort_by(|a, b| a.low_freq.total_cmp(&b.low_freq));
+        for pair in sorted_bands.windows(2) {
+            if pair[1].low_freq < pair[0].high_freq {
+                return Err(AudioProcessingError::ProcessingError(format!(
+                    "Bands {} Hz - {} Hz and {} Hz - {} Hz overlap",
+                    pair[0].low_freq, pair[0].high_freq, pair[1].low_freq, pair[1].high_freq
+                )));
+            }
+        }
+
+        Ok(Self::new_unchecked(sorted_bands, sample_rate))
+    }
+
+    /// Creates a new multiband compressor without validating the bands.
+    /// 
+    /// Use this only with bands known to be valid (see [`MultibandCompressor::new`]);
+    /// overlapping or out-of-range bands produce meaningless filters.
+    pub fn new_unchecked(bands: Vec<BandParams>, sample_rate: f32) -> Self {
         // Hacer una copia mutable para ordenar
         let mut sorted_bands = bands;
         
         // Ordenar las bandas por frecuencia
-        sorted_bands.sort_by(|a, b| a.low_freq.partial_cmp(&b.low_freq).unwrap());
-        
-        // Verificar que las bandas no se solapen y cubran todo el rango
-        for i in 0..sorted_bands.len() {
-            if i > 0 {
-                assert!(
-                    sorted_bands[i].low_freq >= sorted_bands[i-1].high_freq,
-                    "Bands must be in increasing frequency order and not overlap"
-                );
-            }
-            
-            assert!(
-                sorted_bands[i].low_freq < sorted_bands[i].high_freq,
-                "Invalid frequency range for band {}",
-                i
-            );
-        }
+        sorted_bands.sort_by(|a, b| a.low_freq.total_cmp(&b.low_freq));
         
         // Calcular los coeficientes de los filtros para cada banda
         let mut a_coeffs = Vec::with_capacity(sorted_bands.len());
@@ -109,47 +162,75 @@ impl MultibandCompressor {
 
     /// Processes an audio buffer through the multiband compressor.
     /// 
+    /// With the `rayon` feature enabled, the bands are filtered and compressed
+    /// in parallel; the result is bit-identical to the serial path because the
+    /// bands are summed in the same order.
+    /// 
     /// # Arguments
     /// * `input` - Input audio buffer (mono, normalized to [-1.0, 1.0])
     /// 
     /// # Returns
     /// Processed audio buffer with multiband compression applied
     pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
-        let num_bands = self.bands.len();
-        let mut band_outputs = vec![vec![0.0; input.len()]; num_bands];
+        #[cfg(feature = "rayon")]
+        {
+            self.process_parallel(input)
+        }
+        #[cfg(not(feature = "rayon"))]
+        {
+            self.process_serial(input)
+        }
+    }
+
+    /// Processes an audio buffer and reports the peak level of every band
+    /// 
+    /// The output is the same as [`MultibandCompressor::process`]; it is not
+    /// clipped, so [`MultibandReport::is_clipping`] tells whether the bands
+    /// add up to more than full scale and [`MultibandReport::dominant_band`]
+    /// which band contributes the most.
+    /// 
+    /// # Example
+    /// ```
+    /// use clearcast_core::filters::{BandParams, MultibandCompressor};
+    /// 
+    /// let bands = vec![
+    ///     BandParams { low_freq: 0.0, high_freq: 250.0, ..BandParams::default() },
+    ///     BandParams { low_freq: 250.0, high_freq: 22050.0, ..BandParams::default() },
+    /// ];
+    /// let mut compressor = MultibandCompressor::new(bands, 44100.0).unwrap();
+    /// let (output, report) = compressor.process_with_report(&vec![0.1; 1024]);
+    /// assert_eq!(output.len(), 1024);
+    /// assert_eq!(report.band_peaks.len(), 2);
+    /// ```
+    pub fn process_with_report(&mut self, input: &[f32]) -> (Vec<f32>, MultibandReport) {
+        #[cfg(feature = "rayon")]
+        let band_outputs = self.process_bands_parallel(input);
+        #[cfg(not(feature = "rayon"))]
+        let band_outputs = self.process_bands_serial(input);
+
+        let peak = |signal: &[f32]| signal.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+        let output = mix_bands(&band_outputs, input.len());
+        let report = MultibandReport {
+            band_peaks: band_outputs.iter().map(|band| peak(band)).collect(),
+            summed_peak: peak(&output),
+        };
+
+        (output, report)
+    }
+
+    /// Processes the bands one after another
+    #[cfg_attr(all(feature = "rayon", not(test)), allow(dead_code))]
+    fn process_serial(&mut self, input: &[f32]) -> Vec<f32> {
         let mut output = vec![0.0; input.len()];
 
-        // Process each band
         for (i, band) in self.bands.iter().enumerate() {
-            // Apply bandpass filter
-            for (n, &x) in input.iter().enumerate() {
-                // Update history
-                self.x_history[i][2] = self.x_history[i][1];
-                self.x_history[i][1] = self.x_history[i][0];
-                self.x_history[i][0] = x;
-
-                // Apply filter difference equation (Direct Form I)
-                let y = (self.b_coeffs[i][0] * self.x_history[i][0] +
-                        self.b_coeffs[i][1] * self.x_history[i][1] +
-                        self.b_coeffs[i][2] * self.x_history[i][2] -
-                        self.a_coeffs[i][1] * self.y_history[i][0] -
-                        self.a_coeffs[i][2] * self.y_history[i][1]) / self.a_coeffs[i][0];
-
-                // Update output history
-                self.y_history[i][2] = self.y_history[i][1];
-                self.y_history[i][1] = self.y_history[i][0];
-                self.y_history[i][0] = y;
-
-                band_outputs[i][n] = y;
-            }
-
-            // Apply compression to this band
-            let compressed = compress_rms(
-                &band_outputs[i],
-                band.threshold,
-                band.ratio,
-                band.attack_ms,
-                band.release_ms,
+            let compressed = process_band(
+                input,
+                band,
+                &self.b_coeffs[i],
+                &self.a_coeffs[i],
+                &mut self.x_history[i],
+                &mut self.y_history[i],
                 self.sample_rate,
             );
 
@@ -162,6 +243,48 @@ impl MultibandCompressor {
         output
     }
 
+    /// Processes the bands one after another, keeping each band's output
+    #[cfg_attr(feature = "rayon", allow(dead_code))]
+    fn process_bands_serial(&mut self, input: &[f32]) -> Vec<Vec<f32>> {
+        let sample_rate = self.sample_rate;
+        self.bands
+            .iter()
+            .zip(&self.b_coeffs)
+            .zip(&self.a_coeffs)
+            .zip(self.x_history.iter_mut())
+            .zip(self.y_history.iter_mut())
+            .map(|((((band, b), a), x_history), y_history)| {
+                process_band(input, band, b, a, x_history, y_history, sample_rate)
+            })
+            .collect()
+    }
+
+    /// Processes the bands in parallel and sums them in band order
+    #[cfg(feature = "rayon")]
+    fn process_parallel(&mut self, input: &[f32]) -> Vec<f32> {
+        let band_outputs = self.process_bands_parallel(input);
+        mix_bands(&band_outputs, input.len())
+    }
+
+    /// Processes the bands in parallel, keeping each band's output
+    #[cfg(feature = "rayon")]
+    fn process_bands_parallel(&mut self, input: &[f32]) -> Vec<Vec<f32>> {
+        use rayon::prelude::*;
+
+        let sample_rate = self.sample_rate;
+        // Each band owns its filter history, so the bands never share mutable state
+        self.bands
+            .par_iter()
+            .zip(self.b_coeffs.par_iter())
+            .zip(self.a_coeffs.par_iter())
+            .zip(self.x_history.par_iter_mut())
+            .zip(self.y_history.par_iter_mut())
+            .map(|((((band, b), a), x_history), y_history)| {
+                process_band(input, band, b, a, x_history, y_history, sample_rate)
+            })
+            .collect()
+    }
+
     /// Creates a 2nd order Linkwitz-Riley bandpass filter (cascaded lowpass and highpass)
     /// This provides better frequency response than a single Butterworth filter
     fn butterworth_bandpass(low_freq: f32, high_freq: f32, sample_rate: f32) -> ([f32; 3], [f32; 3]) {
@@ -203,6 +326,62 @@ impl MultibandCompressor {
     }
 }
 
+/// Sums the band outputs in band order
+fn mix_bands(band_outputs: &[Vec<f32>], len: usize) -> Vec<f32> {
+    let mut output = vec![0.0; len];
+    for compressed in band_outputs {
+        for (out, &comp) in output.iter_mut().zip(compressed.iter()) {
+            *out += comp;
+        }
+    }
+    output
+}
+
+/// Filters the input through one band's bandpass and compresses the result
+fn process_band(
+    input: &[f32],
+    band: &BandParams,
+    b_coeffs: &[f32; 3],
+    a_coeffs: &[f32; 3],
+    x_history: &mut [f32],
+    y_history: &mut [f32],
+    sample_rate: f32,
+) -> Vec<f32> {
+    let mut band_output = vec![0.0; input.len()];
+
+    // Apply bandpass filter
+    for (out, &x) in band_output.iter_mut().zip(input.iter()) {
+        // Update history
+        x_history[2] = x_history[1];
+        x_history[1] = x_history[0];
+        x_history[0] = x;
+
+        // Apply filter difference equation (Direct Form I)
+        let y = (b_coeffs[0] * x_history[0] +
+                b_coeffs[1] * x_history[1] +
+                b_coeffs[2] * x_history[2] -
+                a_coeffs[1] * y_history[0] -
+                a_coeffs[2] * y_history[1]) / a_coeffs[0];
+
+        // Update output history
+        y_history[2] = y_history[1];
+        y_history[1] = y_history[0];
+        y_history[0] = y;
+
+        *out = y;
+    }
+
+    // Apply compression to this band
+    compress_rms(
+        &band_output,
+        band.threshold,
+        band.ratio,
+        band.attack_ms,
+        band.release_ms,
+        sample_rate,
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -378,7 +557,7 @@ mod tests {
             },
         ];
         
-        let mut compressor = MultibandCompressor::new(bands, sample_rate);
+        let mut compressor = MultibandCompressor::new(bands, sample_rate).unwrap();
         let output = compressor.process(&signal);
         
         // Basic validation
@@ -389,4 +568,90 @@ mod tests {
         let output_energy: f32 = output.iter().map(|x| x * x).sum();
         assert!(output_energy > 0.0);
     }
+
+    #[test]
+    fn test_new_rejects_invalid_bands() {
+        let sample_rate = 44100.0;
+        let band = |low_freq, high_freq| BandParams { low_freq, high_freq, ..BandParams::default() };
+
+        let valid = vec![band(250.0, 2000.0), band(0.0, 250.0), band(2000.0, 22050.0)];
+        let compressor = MultibandCompressor::new(valid, sample_rate).expect("Valid bands");
+        assert_eq!(compressor.bands.len(), 3);
+        assert_eq!(compressor.bands[0].high_freq, 250.0);
+
+        let invalid = [
+            vec![band(0.0, 500.0), band(250.0, 2000.0)], // solapadas
+            vec![band(0.0, 250.0), band(250.0, 250.0)],  // ancho cero
+            vec![band(1000.0, 500.0)],                   // invertida
+            vec![band(-10.0, 500.0)],                    // frecuencia negativa
+            vec![band(0.0, 30000.0)],                    // por encima de Nyquist
+            vec![band(0.0, f32::NAN)],
+        ];
+        for bands in invalid {
+            let description = format!("{:?}", bands);
+            match MultibandCompressor::new(bands, sample_rate) {
+                Err(AudioProcessingError::ProcessingError(_)) => {}
+                _ => panic!("Bands should be rejected: {}", description),
+            }
+        }
+    }
+
+    #[test]
+    fn test_report_flags_clipping_and_dominant_band() {
+        let sample_rate = 44100.0;
+        // Seno a escala completa cerca del borde superior de la banda baja,
+        // donde su filtro tiene algo de ganancia: la suma supera 1.0
+        let signal = generate_test_signal(800.0, sample_rate, 0.5);
+
+        // Sin compresión para que la ganancia de cada banda sea solo la del filtro
+        let band = |low_freq, high_freq| BandParams { low_freq, high_freq, threshold: 0.0, ratio: 1.0, ..BandParams::default() };
+        let bands = vec![band(0.0, 500.0), band(500.0, sample_rate * 0.5)];
+        let mut compressor = MultibandCompressor::new(bands.clone(), sample_rate).unwrap();
+        let (output, report) = compressor.process_with_report(&signal);
+
+        assert_eq!(report.band_peaks.len(), 2);
+        assert!(report.is_clipping(), "{:?}", report);
+        assert_eq!(report.dominant_band(), Some(0));
+        assert!(report.band_peaks[0] > 10.0 * report.band_peaks[1], "{:?}", report);
+        let output_peak = output.iter().fold(0.0f32, |max, x| max.max(x.abs()));
+        assert_eq!(report.summed_peak, output_peak);
+
+        // La salida es la misma que la de process
+        let mut reference = MultibandCompressor::new(bands, sample_rate).unwrap();
+        assert_eq!(reference.process(&signal), output);
+
+        // A menor nivel la suma queda por debajo de la escala completa
+        let quiet: Vec<f32> = signal.iter().map(|x| 0.5 * x).collect();
+        let (_, report) = compressor.process_with_report(&quiet);
+        assert!(!report.is_clipping(), "{:?}", report);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let sample_rate = 44100.0;
+        let mut signal = generate_test_signal(100.0, sample_rate, 0.5);
+        for (i, sample) in generate_test_signal(3000.0, sample_rate, 0.5).iter().enumerate() {
+            signal[i] += sample * 0.5;
+        }
+
+        let edges = [0.0, 150.0, 500.0, 1500.0, 5000.0, sample_rate * 0.5];
+        let bands: Vec<BandParams> = edges
+            .windows(2)
+            .map(|w| BandParams { low_freq: w[0], high_freq: w[1], ..BandParams::default() })
+            .collect();
+
+        let mut serial = MultibandCompressor::new(bands.clone(), sample_rate).unwrap();
+        let mut parallel = MultibandCompressor::new(bands, sample_rate).unwrap();
+
+        // Dos llamadas seguidas para comprobar también el estado de los filtros
+        for chunk in signal.chunks(signal.len() / 2) {
+            let expected = serial.process_serial(chunk);
+            let actual = parallel.process_parallel(chunk);
+            assert!(
+                expected.iter().zip(actual.iter()).all(|(a, b)| a.to_bits() == b.to_bits()),
+                "Parallel output differs from serial output"
+            );
+        }
+    }
 }
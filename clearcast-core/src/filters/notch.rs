@@ -0,0 +1,132 @@
+//! Notch filters, e.g. to remove mains hum
+//!
+//! A notch is a biquad with a pair of zeros on the unit circle at the notch
+//! frequency, so that frequency is cancelled completely while the rest of the
+//! spectrum passes with unity gain. Its Q sets how narrow the cut is. Mains hum
+//! also has energy at the harmonics of 50/60 Hz, so [`remove_hum`] cascades one
+//! notch per harmonic.
+
+use biquad::frequency::*;
+use biquad::{Biquad, Coefficients, DirectForm1, Type as FilterType};
+
+/// Q of the notches used by [`remove_hum`]: a few Hz wide at 50/60 Hz
+const HUM_NOTCH_Q: f32 = 30.0;
+
+/// Applies a notch (band-reject) filter
+///
+/// A frequency outside the range between 0 Hz and Nyquist, or a non-positive
+/// Q, leaves the signal unchanged (a warning is logged).
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `freq_hz` - Frequency to remove in Hz
+/// * `q` - Quality factor; higher values give a narrower notch
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::notch;
+///
+/// let input: Vec<f32> = (0..4410).map(|i| (i as f32 * 0.05).sin()).collect();
+/// let output = notch(&input, 1000.0, 10.0, 44100.0);
+/// assert_eq!(output.len(), input.len());
+/// ```
+pub fn notch(input: &[f32], freq_hz: f32, q: f32, sample_rate: f32) -> Vec<f32> {
+    let mut output = input.to_vec();
+    match notch_filter(freq_hz, q, sample_rate) {
+        Some(mut filter) => output.iter_mut().for_each(|x| *x = filter.run(*x)),
+        None => log::warn!("notch: cannot build a {} Hz notch with Q {} at {} Hz", freq_hz, q, sample_rate),
+    }
+    output
+}
+
+/// Removes mains hum by cascading notches at a base frequency and its harmonics
+///
+/// Notches are placed at `base_freq`, `2 * base_freq`, ... up to
+/// `num_harmonics * base_freq`; harmonics at or above Nyquist are skipped.
+///
+/// # Arguments
+/// * `input` - Input audio buffer
+/// * `base_freq` - Mains frequency in Hz, usually 50.0 or 60.0
+/// * `num_harmonics` - Number of notches, counting the fundamental
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Example
+/// ```
+/// use clearcast_core::filters::remove_hum;
+///
+/// let input: Vec<f32> = (0..44100).map(|i| (i as f32 * 0.05).sin()).collect();
+/// let output = remove_hum(&input, 50.0, 4, 44100.0);
+/// assert_eq!(output.len(), input.len());
+/// ```
+pub fn remove_hum(input: &[f32], base_freq: f32, num_harmonics: usize, sample_rate: f32) -> Vec<f32> {
+    let mut filters: Vec<DirectForm1<f32>> = (1..=num_harmonics)
+        .map_while(|k| notch_filter(base_freq * k as f32, HUM_NOTCH_Q, sample_rate))
+        .collect();
+    if filters.is_empty() && num_harmonics > 0 {
+        log::warn!("remove_hum: no notch fits below Nyquist for a {} Hz base frequency", base_freq);
+    }
+
+    input
+        .iter()
+        .map(|&x| filters.iter_mut().fold(x, |sample, filter| filter.run(sample)))
+        .collect()
+}
+
+/// Builds a notch biquad, or `None` for a frequency or Q it cannot take
+fn notch_filter(freq_hz: f32, q: f32, sample_rate: f32) -> Option<DirectForm1<f32>> {
+    if !(freq_hz > 0.0 && freq_hz < sample_rate / 2.0 && q > 0.0) {
+        return None;
+    }
+    let coeffs = Coefficients::<f32>::from_params(FilterType::Notch, sample_rate.hz(), freq_hz.hz(), q).ok()?;
+    Some(DirectForm1::<f32>::new(coeffs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    /// Energía de la componente a `freq` (correlación con seno y coseno),
+    /// ignorando el transitorio inicial
+    fn tone_energy(signal: &[f32], freq: f32, sample_rate: f32) -> f32 {
+        let skip = signal.len() / 2;
+        let (mut re, mut im) = (0.0, 0.0);
+        for (i, &x) in signal.iter().enumerate().skip(skip) {
+            let phase = 2.0 * PI * freq * i as f32 / sample_rate;
+            re += x * phase.cos();
+            im += x * phase.sin();
+        }
+        re * re + im * im
+    }
+
+    #[test]
+    fn test_remove_hum_keeps_voice_band() {
+        let sample_rate = 44100.0;
+        let input: Vec<f32> = (0..2 * 44100)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                0.3 * (2.0 * PI * 60.0 * t).sin()
+                    + 0.1 * (2.0 * PI * 180.0 * t).sin()
+                    + 0.3 * (2.0 * PI * 1000.0 * t).sin()
+            })
+            .collect();
+
+        let output = remove_hum(&input, 60.0, 3, sample_rate);
+        assert_eq!(output.len(), input.len());
+
+        // El zumbido y su tercer armónico caen más de 30 dB
+        for freq in [60.0, 180.0] {
+            let ratio = tone_energy(&output, freq, sample_rate) / tone_energy(&input, freq, sample_rate);
+            assert!(ratio < 1e-3, "{} Hz energy ratio {}", freq, ratio);
+        }
+
+        // El tono de 1 kHz pasa prácticamente intacto
+        let ratio = tone_energy(&output, 1000.0, sample_rate) / tone_energy(&input, 1000.0, sample_rate);
+        assert!((ratio - 1.0).abs() < 0.02, "1 kHz energy ratio {}", ratio);
+
+        // Un notch con parámetros no válidos no toca la señal
+        assert_eq!(notch(&input[..100], 0.0, 10.0, sample_rate), input[..100].to_vec());
+        assert_eq!(notch(&input[..100], 60.0, -1.0, sample_rate), input[..100].to_vec());
+    }
+}
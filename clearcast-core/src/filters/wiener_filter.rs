@@ -7,17 +7,318 @@
 
 use ndarray::Array1;
 use num_complex::Complex;
-#[cfg(feature = "native")]
-use realfft::RealFftPlanner;
-use std::f32::consts::PI;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+use std::sync::Arc;
+
+use crate::utils::window::{make_window, WindowType};
+
+/// Filtro de Wiener con planes FFT y búferes reutilizables
+///
+/// Planificar la FFT es costoso en comparación con procesar un bloque pequeño.
+/// `WienerDenoiser` planifica las transformadas y reserva los búferes una sola
+/// vez, de modo que procesar muchos bloques del mismo tamaño no repite ese coste.
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::filters::WienerDenoiser;
+///
+/// let mut denoiser = WienerDenoiser::new(256);
+/// let noise_profile = denoiser.estimate_noise_profile(&vec![0.01; 1024]);
+/// for block in vec![0.1f32; 4096].chunks(1024) {
+///     let processed = denoiser.process(block, &noise_profile, 128, 0.9);
+///     assert_eq!(processed.len(), block.len());
+/// }
+/// ```
+pub struct WienerDenoiser {
+    fft_size: usize,
+    r2c: Arc<dyn RealToComplex<f32>>,
+    c2r: Arc<dyn ComplexToReal<f32>>,
+    window: Vec<f32>,
+    in_buffer: Vec<f32>,
+    spectrum_buffer: Vec<Complex<f32>>,
+    out_buffer: Vec<f32>,
+    r2c_scratch: Vec<Complex<f32>>,
+    c2r_scratch: Vec<Complex<f32>>,
+}
+
+impl WienerDenoiser {
+    /// Crea un filtro para el tamaño de FFT dado (redondeado a potencia de 2)
+    pub fn new(fft_size: usize) -> Self {
+        let fft_size = fft_size.max(1).next_power_of_two();
+
+        // Planificador FFT para optimizar las transformadas
+        let mut planner = RealFftPlanner::<f32>::new();
+        let r2c = planner.plan_fft_forward(fft_size);
+        let c2r = planner.plan_fft_inverse(fft_size);
+
+        // Ventana de Hann para el enventanado
+        let window = make_window(WindowType::Hann, fft_size);
+
+        Self {
+            fft_size,
+            in_buffer: r2c.make_input_vec(),
+            spectrum_buffer: r2c.make_output_vec(),
+            out_buffer: c2r.make_output_vec(),
+            r2c_scratch: r2c.make_scratch_vec(),
+            c2r_scratch: c2r.make_scratch_vec(),
+            r2c,
+            c2r,
+            window,
+        }
+    }
+
+    /// Devuelve el tamaño de la FFT (potencia de 2)
+    pub fn fft_size(&self) -> usize {
+        self.fft_size
+    }
+
+    /// Pone a cero los búferes de trabajo, conservando los planes FFT
+    ///
+    /// Cada llamada a [`WienerDenoiser::process`] es independiente, así que
+    /// esto solo garantiza que no quede rastro de la señal anterior.
+    pub fn reset(&mut self) {
+        self.in_buffer.iter_mut().for_each(|x| *x = 0.0);
+        self.spectrum_buffer.iter_mut().for_each(|x| *x = Complex::new(0.0, 0.0));
+        self.out_buffer.iter_mut().for_each(|x| *x = 0.0);
+    }
+
+    /// Indica si los búferes de trabajo están a cero, como tras [`WienerDenoiser::reset`]
+    #[cfg(test)]
+    pub(crate) fn is_cleared(&self) -> bool {
+        self.in_buffer.iter().all(|&x| x == 0.0)
+            && self.spectrum_buffer.iter().all(|x| x.norm_sqr() == 0.0)
+            && self.out_buffer.iter().all(|&x| x == 0.0)
+    }
+
+    /// Aplica el filtro de Wiener a una señal
+    ///
+    /// Equivalente a [`reduce_noise_wiener`] con el tamaño de FFT del filtro.
+    ///
+    /// # Argumentos
+    /// * `signal`: Señal de entrada con ruido
+    /// * `noise_profile`: Perfil de ruido estimado (espectro de ruido)
+    /// * `hop_size`: Tamaño del salto entre ventanas (normalmente fft_size/2).
+    ///   No puede superar `fft_size`: las tramas dejarían huecos sin cubrir, así
+    ///   que un salto mayor se reduce a `fft_size/2` (al menos 1) con un aviso en el log
+    /// * `smoothing`: Factor de suavizado para la estimación del espectro de la señal (0.0 a 1.0)
+    pub fn process(
+        &mut self,
+        signal: &[f32],
+        noise_profile: &[f32],
+        hop_size: usize,
+        smoothing: f32,
+    ) -> Vec<f32> {
+        // Validación de parámetros
+        if signal.is_empty() || noise_profile.is_empty() || hop_size == 0 {
+            return signal.to_vec();
+        }
+
+        let fft_size = self.fft_size;
+
+        // Con un salto mayor que la trama quedarían muestras sin ninguna ventana.
+        // Con una FFT de 1 punto la mitad sería 0 y el bucle no avanzaría
+        let hop_size = if hop_size > fft_size {
+            let clamped = (fft_size / 2).max(1);
+            log::warn!(
+                "Wiener filter hop size {} exceeds the FFT size {}; using {}",
+                hop_size,
+                fft_size,
+                clamped
+            );
+            clamped
+        } else {
+            hop_size
+        };
+
+        // Número de bandas de frecuencia
+        let num_bins = fft_size / 2 + 1;
+
+        // Validar el tamaño del perfil de ruido
+        let noise_profile = if noise_profile.len() >= num_bins {
+            noise_profile[..num_bins].to_vec()
+        } else {
+            // Si el perfil de ruido es más pequeño, rellenar con ceros
+            let mut padded = vec![0.0; num_bins];
+            let len = noise_profile.len().min(num_bins);
+            padded[..len].copy_from_slice(&noise_profile[..len]);
+            padded
+        };
+
+        // Convertir el perfil de ruido a un array de complejos
+        let noise_spectrum: Vec<Complex<f32>> = noise_profile
+            .iter()
+            .map(|&x| Complex::new(x, 0.0))
+            .collect();
+
+        // Calcular el número de ventanas necesarias
+        let num_windows = (signal.len() as f32 / hop_size as f32).ceil() as usize;
+
+        // Buffer para la señal de salida
+        let mut output = vec![0.0; signal.len() + fft_size];
+        let mut window_sum = vec![0.0; signal.len() + fft_size];
+
+        // Estimación del espectro de la señal
+        let mut signal_estimate = noise_spectrum.clone();
+
+        // Procesar cada ventana
+        for i in 0..num_windows {
+            let start = i * hop_size;
+            let end = (start + fft_size).min(signal.len());
+
+            // Rellenar el buffer de entrada
+            if start >= signal.len() {
+                break;
+            }
+
+            // Aplicar ventana y copiar datos
+            for j in 0..(end - start) {
+                self.in_buffer[j] = signal[start + j] * self.window[j];
+            }
+
+            // Rellenar con ceros si es necesario
+            for j in (end - start)..fft_size {
+                self.in_buffer[j] = 0.0;
+            }
+
+            // Calcular la FFT
+            self.r2c
+                .process_with_scratch(&mut self.in_buffer, &mut self.spectrum_buffer, &mut self.r2c_scratch)
+                .unwrap();
+
+            // Aplicar el filtro de Wiener
+            for j in 0..num_bins {
+                let signal_power = self.spectrum_buffer[j].norm_sqr();
+                let noise_power = noise_spectrum[j].norm_sqr();
+                let snr = signal_power / (signal_power + noise_power + 1e-10);
+
+                // Actualizar la estimación del espectro de la señal
+                signal_estimate[j] = signal_estimate[j] * smoothing +
+                                   (self.spectrum_buffer[j] * snr) * (1.0 - smoothing);
+
+                // Aplicar la ganancia del filtro de Wiener
+                self.spectrum_buffer[j] = signal_estimate[j];
+            }
+
+            // Calcular la IFFT
+            self.c2r
+                .process_with_scratch(&mut self.spectrum_buffer, &mut self.out_buffer, &mut self.c2r_scratch)
+                .unwrap();
+
+            // Reconstruir la señal con solapamiento-suma
+            let scale = 1.0 / (fft_size as f32);
+            for j in 0..fft_size {
+                if start + j < output.len() {
+                    output[start + j] += self.out_buffer[j] * scale * self.window[j];
+                    window_sum[start + j] += self.window[j] * self.window[j];
+                }
+            }
+        }
+
+        // Normalizar por la suma de las ventanas al cuadrado
+        for i in 0..signal.len() {
+            if window_sum[i] > 1e-10 {
+                output[i] /= window_sum[i];
+            }
+        }
+
+        // Asegurarse de que la salida tenga la misma longitud que la entrada
+        output.truncate(signal.len());
+        output
+    }
+
+    /// Estima el perfil de ruido a partir de una señal que solo contiene ruido
+    ///
+    /// Equivalente a [`estimate_noise_profile`] con el tamaño de FFT del filtro.
+    pub fn estimate_noise_profile(&mut self, noise_signal: &[f32]) -> Vec<f32> {
+        self.estimate_noise_profile_with(noise_signal, 0.5, WindowType::Hann)
+    }
+
+    /// Estima el perfil de ruido con solapamiento y ventana configurables
+    ///
+    /// Equivalente a [`estimate_noise_profile_with`] con el tamaño de FFT del filtro.
+    pub fn estimate_noise_profile_with(
+        &mut self,
+        noise_signal: &[f32],
+        overlap_factor: f32,
+        window_type: WindowType,
+    ) -> Vec<f32> {
+        if noise_signal.is_empty() {
+            return Vec::new();
+        }
+
+        let fft_size = self.fft_size;
+        let num_bins = fft_size / 2 + 1;
+
+        // Acumulador para el espectro de potencia
+        let mut power_spectrum = vec![0.0; num_bins];
+        let mut num_windows = 0;
+
+        // La ventana se calcula una sola vez para todas las tramas
+        let window = if window_type == WindowType::Hann {
+            self.window.clone()
+        } else {
+            make_window(window_type, fft_size)
+        };
+
+        // Salto entre ventanas según el solapamiento pedido
+        let overlap = overlap_factor.clamp(0.0, 1.0);
+        let hop_size = (((1.0 - overlap) * fft_size as f32).round() as usize).max(1);
+        let num_windows_total = (noise_signal.len() as f32 / hop_size as f32).ceil() as usize;
+
+        for i in 0..num_windows_total {
+            let start = i * hop_size;
+            let end = (start + fft_size).min(noise_signal.len());
+
+            if start >= noise_signal.len() {
+                break;
+            }
+
+            // Copiar los datos al buffer y aplicar la ventana
+            let len = (end - start).min(fft_size);
+            for i in 0..len {
+                self.in_buffer[i] = noise_signal[start + i] * window[i];
+            }
+
+            // Rellenar con ceros si es necesario
+            for i in len..fft_size {
+                self.in_buffer[i] = 0.0;
+            }
+
+            // Calcular la FFT
+            self.r2c
+                .process_with_scratch(&mut self.in_buffer, &mut self.spectrum_buffer, &mut self.r2c_scratch)
+                .unwrap();
+
+            // Acumular el espectro de potencia
+            for (power, bin) in power_spectrum.iter_mut().zip(self.spectrum_buffer.iter()) {
+                *power += bin.norm_sqr();
+            }
+
+            num_windows += 1;
+        }
+
+        // Promediar el espectro de potencia
+        if num_windows > 0 {
+            for bin in &mut power_spectrum {
+                *bin = (*bin / num_windows as f32).sqrt();
+            }
+        }
+
+        power_spectrum
+    }
+}
 
 /// Aplica un filtro de Wiener para reducir el ruido en una señal de audio
 /// 
+/// Crea un [`WienerDenoiser`] temporal en cada llamada; para procesar muchos
+/// bloques del mismo tamaño conviene reutilizar uno.
+/// 
 /// # Argumentos
 /// * `signal`: Señal de entrada con ruido (slice de f32)
 /// * `noise_profile`: Perfil de ruido estimado (espectro de ruido)
 /// * `fft_size`: Tamaño de la FFT a utilizar
-/// * `hop_size`: Tamaño del salto entre ventanas (normalmente fft_size/2)
+/// * `hop_size`: Tamaño del salto entre ventanas (normalmente fft_size/2). Si
+///   supera `fft_size` se reduce a `fft_size/2`, como en [`WienerDenoiser::process`]
 /// * `smoothing`: Factor de suavizado para la estimación del espectro de la señal (0.0 a 1.0)
 /// 
 /// # Retorno
@@ -44,114 +345,7 @@ pub fn reduce_noise_wiener(
         return signal.to_vec();
     }
 
-    // Asegurarse de que el tamaño de la FFT sea una potencia de 2
-    let fft_size = fft_size.next_power_of_two();
-    
-    // Planificador FFT para optimizar las transformadas
-    let mut planner = RealFftPlanner::<f32>::new();
-    let r2c = planner.plan_fft_forward(fft_size);
-    let c2r = planner.plan_fft_inverse(fft_size);
-    
-    // Número de bandas de frecuencia
-    let num_bins = fft_size / 2 + 1;
-    
-    // Validar el tamaño del perfil de ruido
-    let noise_profile = if noise_profile.len() >= num_bins {
-        noise_profile[..num_bins].to_vec()
-    } else {
-        // Si el perfil de ruido es más pequeño, rellenar con ceros
-        let mut padded = vec![0.0; num_bins];
-        let len = noise_profile.len().min(num_bins);
-        padded[..len].copy_from_slice(&noise_profile[..len]);
-        padded
-    };
-    
-    // Convertir el perfil de ruido a un array de complejos
-    let noise_spectrum: Vec<Complex<f32>> = noise_profile
-        .iter()
-        .map(|&x| Complex::new(x, 0.0))
-        .collect();
-    
-    // Calcular el número de ventanas necesarias
-    let num_windows = (signal.len() as f32 / hop_size as f32).ceil() as usize;
-    
-    // Buffer para la señal de salida
-    let mut output = vec![0.0; signal.len() + fft_size];
-    let mut window_sum = vec![0.0; signal.len() + fft_size];
-    
-    // Ventana de Hann para el enventanado
-    let window: Vec<f32> = (0..fft_size)
-        .map(|i| 0.5 * (1.0 - (2.0 * PI * i as f32 / (fft_size - 1) as f32).cos()))
-        .collect();
-    
-    // Buffer para la transformada
-    let mut in_buffer = r2c.make_input_vec();
-    let mut spectrum_buffer = r2c.make_output_vec();
-    
-    // Estimación del espectro de la señal
-    let mut signal_estimate = noise_spectrum.clone();
-    
-    // Procesar cada ventana
-    for i in 0..num_windows {
-        let start = i * hop_size;
-        let end = (start + fft_size).min(signal.len());
-        
-        // Rellenar el buffer de entrada
-        if start >= signal.len() {
-            break;
-        }
-        
-        // Aplicar ventana y copiar datos
-        for j in 0..(end - start) {
-            in_buffer[j] = signal[start + j] * window[j];
-        }
-        
-        // Rellenar con ceros si es necesario
-        for j in (end - start)..fft_size {
-            in_buffer[j] = 0.0;
-        }
-        
-        // Calcular la FFT
-        r2c.process(&mut in_buffer, &mut spectrum_buffer).unwrap();
-        
-        // Aplicar el filtro de Wiener
-        for j in 0..num_bins {
-            let signal_power = spectrum_buffer[j].norm_sqr();
-            let noise_power = noise_spectrum[j].norm_sqr();
-            let snr = signal_power / (signal_power + noise_power + 1e-10);
-            
-            // Actualizar la estimación del espectro de la señal
-            signal_estimate[j] = signal_estimate[j] * smoothing + 
-                               (spectrum_buffer[j] * snr) * (1.0 - smoothing);
-            
-            // Aplicar la ganancia del filtro de Wiener
-            spectrum_buffer[j] = signal_estimate[j];
-        }
-        
-        // Calcular la IFFT
-        let mut out_buffer = c2r.make_output_vec();
-        c2r.process(&mut spectrum_buffer, &mut out_buffer).unwrap();
-        
-        // Reconstruir la señal con solapamiento-suma
-        let scale = 1.0 / (fft_size as f32);
-        for j in 0..fft_size {
-            if start + j < output.len() {
-                output[start + j] += out_buffer[j] * scale * window[j];
-                window_sum[start + j] += window[j] * window[j];
-            }
-        }
-    }
-    
-    // Normalizar por la suma de las ventanas al cuadrado
-    for i in 0..signal.len() {
-        if window_sum[i] > 1e-10 {
-            output[i] /= window_sum[i];
-        }
-    }
-    
-    // Asegurarse de que la salida tenga la misma longitud que la entrada
-    output.truncate(signal.len());
-    output
+    WienerDenoiser::new(fft_size).process(signal, noise_profile, hop_size, smoothing)
 }
 
 /// Estima el perfil de ruido a partir de una señal que solo contiene ruido
@@ -175,68 +369,45 @@ pub fn estimate_noise_profile(noise_signal: &[f32], fft_size: usize) -> Vec<f32>
     if noise_signal.is_empty() || fft_size == 0 {
         return Vec::new();
     }
-    
-    let fft_size = fft_size.next_power_of_two();
-    let num_bins = fft_size / 2 + 1;
-    
-    // Planificador FFT
-    let mut planner = RealFftPlanner::<f32>::new();
-    let r2c = planner.plan_fft_forward(fft_size);
-    
-    // Buffer para la FFT
-    let mut in_buffer = r2c.make_input_vec();
-    let mut spectrum_buffer = r2c.make_output_vec();
-    
-    // Acumulador para el espectro de potencia
-    let mut power_spectrum = vec![0.0; num_bins];
-    let mut num_windows = 0;
-    
-    // Procesar la señal en ventanas con solapamiento del 50%
-    let hop_size = fft_size / 2;
-    let num_windows_total = (noise_signal.len() as f32 / hop_size as f32).ceil() as usize;
-    
-    for i in 0..num_windows_total {
-        let start = i * hop_size;
-        let end = (start + fft_size).min(noise_signal.len());
-        
-        if start >= noise_signal.len() {
-            break;
-        }
-        
-        // Copiar los datos al buffer y aplicar ventana de Hann
-        let window: Vec<f32> = (0..fft_size)
-            .map(|i| 0.5 * (1.0 - (2.0 * PI * i as f32 / (fft_size - 1) as f32).cos()))
-            .collect();
-            
-        let len = (end - start).min(fft_size);
-        for i in 0..len {
-            in_buffer[i] = noise_signal[start + i] * window[i];
-        }
-        
-        // Rellenar con ceros si es necesario
-        for i in len..fft_size {
-            in_buffer[i] = 0.0;
-        }
-        
-        // Calcular la FFT
-        r2c.process(&mut in_buffer, &mut spectrum_buffer).unwrap();
-        
-        // Acumular el espectro de potencia
-        for j in 0..num_bins {
-            power_spectrum[j] += spectrum_buffer[j].norm_sqr();
-        }
-        
-        num_windows += 1;
-    }
-    
-    // Promediar el espectro de potencia
-    if num_windows > 0 {
-        for bin in &mut power_spectrum {
-            *bin = (*bin / num_windows as f32).sqrt();
-        }
+
+    WienerDenoiser::new(fft_size).estimate_noise_profile(noise_signal)
+}
+
+/// Estima el perfil de ruido con solapamiento y ventana configurables
+///
+/// [`estimate_noise_profile`] usa un solapamiento del 50% y ventana de Hann.
+/// Más solapamiento promedia más tramas y da una estimación más suave, a
+/// cambio de más FFT.
+///
+/// # Argumentos
+/// * `noise_signal`: Señal que contiene solo ruido
+/// * `fft_size`: Tamaño de la FFT a utilizar
+/// * `overlap_factor`: Fracción de solapamiento entre ventanas (0.0 a 1.0, p. ej. 0.75)
+/// * `window`: Ventana de análisis
+///
+/// # Retorno
+/// Vector con la magnitud del espectro de ruido promediado
+///
+/// # Ejemplo
+/// ```
+/// use clearcast_core::filters::wiener_filter::estimate_noise_profile_with;
+/// use clearcast_core::utils::WindowType;
+///
+/// let noise_signal: Vec<f32> = (0..1024).map(|i| 0.01 * (i as f32 * 1.7).sin()).collect();
+/// let profile = estimate_noise_profile_with(&noise_signal, 256, 0.75, WindowType::BlackmanHarris);
+/// assert_eq!(profile.len(), 129);  // fft_size/2 + 1
+/// ```
+pub fn estimate_noise_profile_with(
+    noise_signal: &[f32],
+    fft_size: usize,
+    overlap_factor: f32,
+    window: WindowType,
+) -> Vec<f32> {
+    if noise_signal.is_empty() || fft_size == 0 {
+        return Vec::new();
     }
-    
-    power_spectrum
+
+    WienerDenoiser::new(fft_size).estimate_noise_profile_with(noise_signal, overlap_factor, window)
 }
 
 #[cfg(test)]
@@ -387,6 +558,24 @@ mod tests {
         );
     }
     
+    #[test]
+    fn test_denoiser_matches_free_functions() {
+        let noise: Vec<f32> = (0..2048).map(|i| 0.05 * ((i * 7919 % 1000) as f32 / 500.0 - 1.0)).collect();
+        let signal: Vec<f32> = (0..4096)
+            .map(|i| 0.5 * (i as f32 * 0.07).sin() + noise[i % noise.len()])
+            .collect();
+
+        let mut denoiser = WienerDenoiser::new(512);
+        let profile = denoiser.estimate_noise_profile(&noise);
+        assert_eq!(profile, estimate_noise_profile(&noise, 512));
+
+        // Reutilizar el filtro en varios bloques no debe arrastrar estado
+        for block in signal.chunks(1000) {
+            let expected = reduce_noise_wiener(block, &profile, 512, 128, 0.85);
+            assert_eq!(denoiser.process(block, &profile, 128, 0.85), expected);
+        }
+    }
+    
     #[test]
     fn test_estimate_noise_profile() {
         // Generar señal de ruido aleatorio
@@ -406,6 +595,74 @@ mod tests {
         }
     }
     
+    #[test]
+    fn test_more_overlap_gives_smoother_noise_profile() {
+        // Ruido blanco determinista (generador congruencial lineal)
+        let mut state = 12345u32;
+        let noise_signal: Vec<f32> = (0..16384)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                (state >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+            })
+            .collect();
+
+        // Coeficiente de variación entre bandas, sin DC ni Nyquist
+        let spread = |profile: &[f32]| {
+            let bins = &profile[1..profile.len() - 1];
+            let mean = bins.iter().sum::<f32>() / bins.len() as f32;
+            let variance = bins.iter().map(|x| (x - mean).powi(2)).sum::<f32>() / bins.len() as f32;
+            variance.sqrt() / mean
+        };
+
+        let sparse = estimate_noise_profile_with(&noise_signal, 512, 0.0, WindowType::Hann);
+        let dense = estimate_noise_profile_with(&noise_signal, 512, 0.75, WindowType::Hann);
+        assert_eq!(sparse.len(), dense.len());
+        assert!(
+            spread(&dense) < 0.8 * spread(&sparse),
+            "overlap 0.75: {}, overlap 0.0: {}",
+            spread(&dense),
+            spread(&sparse)
+        );
+
+        // Los valores por defecto coinciden con estimate_noise_profile
+        assert_eq!(
+            estimate_noise_profile_with(&noise_signal, 512, 0.5, WindowType::Hann),
+            estimate_noise_profile(&noise_signal, 512)
+        );
+    }
+    
+    #[test]
+    fn test_hop_larger_than_fft_leaves_no_gaps() {
+        let fft_size = 256;
+        let signal: Vec<f32> = (0..8192).map(|i| 0.3 * (i as f32 * 0.07).sin() + 0.5).collect();
+        // Perfil de ruido despreciable: la salida debería seguir a la entrada
+        let noise_profile = vec![1e-6; fft_size / 2 + 1];
+
+        let output = reduce_noise_wiener(&signal, &noise_profile, fft_size, fft_size * 2, 0.0);
+        assert_eq!(output.len(), signal.len());
+
+        // La señal nunca baja de 0.2, así que ningún cero en la salida es
+        // legítimo (salvo la primera muestra, donde la ventana de Hann vale cero)
+        let gaps = output[1..].iter().filter(|x| x.abs() < 0.1).count();
+        assert_eq!(gaps, 0, "{} samples left uncovered", gaps);
+
+        // Igual que con el salto por defecto de media trama
+        let expected = reduce_noise_wiener(&signal, &noise_profile, fft_size, fft_size / 2, 0.0);
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_hop_clamp_with_single_point_fft() {
+        // Con fft_size = 1 la mitad de la trama es 0; el salto se queda en 1 y la llamada termina
+        let signal: Vec<f32> = (0..64).map(|i| 0.3 * (i as f32 * 0.2).sin()).collect();
+        let mut denoiser = WienerDenoiser::new(1);
+        let output = denoiser.process(&signal, &[1e-6], 4, 0.0);
+        assert_eq!(output.len(), signal.len());
+        assert!(output.iter().all(|x| x.is_finite()));
+
+        assert_eq!(reduce_noise_wiener(&signal, &[1e-6], 1, 4, 0.0), output);
+    }
+
     // Función auxiliar para calcular la relación señal/ruido (SNR) en decibelios
     fn calculate_snr(signal: &[f32], noisy_signal: &[f32]) -> f32 {
         assert_eq!(signal.len(), noisy_signal.len());
@@ -56,6 +56,19 @@ declare module 'clearcast-core' {
      */
     processBuffer(input: Float32Array): Float32Array;
 
+    /**
+     * Enable or disable strict mode; when enabled, processBuffer throws on
+     * stage errors and empty input instead of logging them
+     * @param strict Whether to report errors
+     */
+    setStrict(strict: boolean): void;
+
+    /**
+     * Set the output ceiling of processBuffer and compress (default 0.95)
+     * @param ceiling Highest absolute output value (above 0.0, at most 1.0)
+     */
+    setCeiling(ceiling: number): void;
+
     /**
      * Apply compression to an audio buffer
      * @param input Float32Array containing the audio samples
@@ -74,6 +87,66 @@ declare module 'clearcast-core' {
     ): Float32Array;
   }
 
+  /**
+   * Complete ClearCast chain: Wiener denoising, EQ, compression, limiting and RMS normalization
+   */
+  export class WasmClearCastProcessor {
+    /**
+     * Create a processor with default settings
+     * @param sampleRate Sample rate of the audio in Hz
+     */
+    constructor(sampleRate: number);
+
+    /**
+     * Set the EQ band gains
+     * @param lowGain Gain for low frequencies in dB
+     * @param midGain Gain for mid frequencies in dB
+     * @param highGain Gain for high frequencies in dB
+     */
+    configureEq(lowGain: number, midGain: number, highGain: number): void;
+
+    /**
+     * Configure the compressor
+     * @param threshold Threshold in dBFS
+     * @param ratio Compression ratio (e.g., 4.0 for 4:1)
+     * @param attackMs Attack time in milliseconds
+     * @param releaseMs Release time in milliseconds
+     */
+    configureCompressor(threshold: number, ratio: number, attackMs: number, releaseMs: number): void;
+
+    /**
+     * Set the target RMS level of the output
+     * @param targetRms Target RMS level (0.0 to 1.0)
+     */
+    setTargetRms(targetRms: number): void;
+
+    /**
+     * Configure the noise reduction stage; throws on invalid parameters
+     * @param noiseProfile Noise magnitude spectrum (fftSize / 2 + 1 bins)
+     * @param fftSize FFT window size (power of two)
+     * @param hopSize Hop between windows (1 to fftSize)
+     * @param smoothing Smoothing factor (0.0 to 1.0)
+     */
+    configureNoiseReduction(
+      noiseProfile: Float32Array,
+      fftSize: number,
+      hopSize: number,
+      smoothing: number
+    ): void;
+
+    /**
+     * Clear all intermediate filter state before processing an unrelated clip
+     */
+    reset(): void;
+
+    /**
+     * Run a buffer through the whole chain
+     * @param input Float32Array containing the audio samples
+     * @returns Processed audio as Float32Array
+     */
+    processAudio(input: Float32Array): Float32Array;
+  }
+
   /**
    * Initialize the WebAssembly module
    * @returns Promise that resolves when the module is initialized
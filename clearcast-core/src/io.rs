@@ -0,0 +1,198 @@
+//! WAV file helpers (requires the `wav` feature)
+//!
+//! Thin wrappers around `hound` that convert between WAV files and the `f32`
+//! sample buffers used throughout the crate. Integer PCM is scaled to the
+//! range [-1.0, 1.0]; 32-bit float files are read as-is.
+
+use std::io;
+use std::path::Path;
+
+use crate::utils::deinterleave;
+
+/// Reads a WAV file as mono samples
+///
+/// Multi-channel files are mixed down to mono by averaging the channels; use
+/// [`read_wav_multi`] to keep them separate.
+///
+/// # Returns
+/// The samples and the file's sample rate in Hz
+///
+/// # Example
+/// ```no_run
+/// use clearcast_core::io::read_wav;
+///
+/// let (samples, sample_rate) = read_wav("input.wav")?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn read_wav<P: AsRef<Path>>(path: P) -> Result<(Vec<f32>, u32), io::Error> {
+    let (channels, sample_rate) = read_wav_multi(path)?;
+
+    if channels.len() == 1 {
+        let mono = channels.into_iter().next().unwrap_or_default();
+        return Ok((mono, sample_rate));
+    }
+
+    let num_frames = channels.first().map_or(0, Vec::len);
+    let scale = 1.0 / channels.len() as f32;
+    let mono = (0..num_frames)
+        .map(|i| channels.iter().map(|channel| channel[i]).sum::<f32>() * scale)
+        .collect();
+
+    Ok((mono, sample_rate))
+}
+
+/// Reads a WAV file keeping each channel in its own buffer
+///
+/// # Returns
+/// One buffer per channel (deinterleaved) and the file's sample rate in Hz
+pub fn read_wav_multi<P: AsRef<Path>>(path: P) -> Result<(Vec<Vec<f32>>, u32), io::Error> {
+    let mut reader = hound::WavReader::open(path).map_err(to_io_error)?;
+    let spec = reader.spec();
+    let num_channels = spec.channels.max(1) as usize;
+
+    let interleaved: Vec<f32> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .collect::<Result<_, _>>()
+            .map_err(to_io_error)?,
+        hound::SampleFormat::Int => {
+            let scale = 1.0 / (1i64 << (spec.bits_per_sample - 1)) as f32;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f32 * scale))
+                .collect::<Result<_, _>>()
+                .map_err(to_io_error)?
+        }
+    };
+
+    Ok((deinterleave(&interleaved, num_channels), spec.sample_rate))
+}
+
+/// Writes mono samples to a 16-bit PCM WAV file
+///
+/// Samples are scaled by 32768, the same factor [`read_wav`] divides by, so
+/// a file read and written back keeps its exact sample values. Samples
+/// outside the 16-bit range (-1.0 to 32767/32768) are clipped.
+///
+/// # Example
+/// ```no_run
+/// use clearcast_core::io::write_wav;
+///
+/// let samples = vec![0.0, 0.5, -0.5];
+/// write_wav("output.wav", &samples, 44100)?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_wav<P: AsRef<Path>>(path: P, samples: &[f32], sample_rate: u32) -> Result<(), io::Error> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = hound::WavWriter::create(path, spec).map_err(to_io_error)?;
+    for &sample in samples {
+        let value = (sample * 32768.0).round().clamp(i16::MIN as f32, i16::MAX as f32) as i16;
+        writer.write_sample(value).map_err(to_io_error)?;
+    }
+    writer.finalize().map_err(to_io_error)
+}
+
+/// Converts a `hound` error into an `io::Error`, keeping I/O errors intact
+fn to_io_error(error: hound::Error) -> io::Error {
+    match error {
+        hound::Error::IoError(e) => e,
+        other => io::Error::new(io::ErrorKind::InvalidData, other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_path(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("clearcast_{}_{}.wav", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_wav_round_trip() {
+        let sample_rate = 44100;
+        let sine: Vec<f32> = (0..4410)
+            .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+
+        let path = temp_path("round_trip");
+        write_wav(&path, &sine, sample_rate).unwrap();
+        let (read_back, read_rate) = read_wav(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(read_rate, sample_rate);
+        assert_eq!(read_back.len(), sine.len());
+
+        // Error de cuantización de 16 bits: medio LSB
+        let half_lsb = 0.5 / 32768.0;
+        for (&original, &decoded) in sine.iter().zip(read_back.iter()) {
+            assert!((original - decoded).abs() <= half_lsb, "{} vs {}", original, decoded);
+        }
+    }
+
+    #[test]
+    fn test_wav_round_trip_is_exact_for_16_bit_values() {
+        // Lectura y escritura usan la misma escala, así que los valores de 16 bits no cambian
+        let samples: Vec<f32> = [i16::MIN, -12345, -1, 0, 1, 12345, i16::MAX]
+            .iter()
+            .map(|&value| value as f32 / 32768.0)
+            .collect();
+
+        let path = temp_path("exact");
+        write_wav(&path, &samples, 8000).unwrap();
+        let (first, _) = read_wav(&path).unwrap();
+        write_wav(&path, &first, 8000).unwrap();
+        let (second, _) = read_wav(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(first, samples);
+        assert_eq!(second, samples);
+
+        // El fondo de escala y lo que lo supera se recortan al rango de 16 bits
+        write_wav(&path, &[1.0, -1.0, 2.0, -2.0], 8000).unwrap();
+        let (clipped, _) = read_wav(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+        let max = i16::MAX as f32 / 32768.0;
+        assert_eq!(clipped, vec![max, -1.0, max, -1.0]);
+    }
+
+    #[test]
+    fn test_read_wav_multi_deinterleaves() {
+        let path = temp_path("stereo");
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 48000,
+            bits_per_sample: 32,
+            sample_format: hound::SampleFormat::Float,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).unwrap();
+        for i in 0..100 {
+            writer.write_sample(i as f32 / 100.0).unwrap();
+            writer.write_sample(-(i as f32) / 100.0).unwrap();
+        }
+        writer.finalize().unwrap();
+
+        let (channels, sample_rate) = read_wav_multi(&path).unwrap();
+        let (mono, _) = read_wav(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert_eq!(sample_rate, 48000);
+        assert_eq!(channels.len(), 2);
+        assert_eq!(channels[0][10], 0.1);
+        assert_eq!(channels[1][10], -0.1);
+        // La mezcla a mono promedia ambos canales
+        assert!(mono.iter().all(|&x| x.abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_read_missing_file() {
+        let err = read_wav(temp_path("does_not_exist")).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+}
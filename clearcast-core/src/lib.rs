@@ -4,26 +4,59 @@
 //! normalization, and compression for the ClearCast application.
 //!
 //! # Features
+//! - `std` (default) - Everything that needs the standard library: the engine,
+//!   the processor, FFT-based filters and most effects
+//! - `alloc` - Without `std`, adds the helpers that return new buffers
 //! - `wasm` - Enables WebAssembly compilation and JavaScript bindings
-//! - `native` - Enables native compilation (default)
+//! - `native` - Native (non-WASM) builds; implies `std` and adds `rustfft`
+//! - `wav` - Enables WAV file reading and writing helpers in [`io`]
+//! - `rayon` - Processes the bands of [`filters::MultibandCompressor`] in parallel
+//!
+//! # `no_std`
+//! With `default-features = false` the crate builds without the standard
+//! library and without an allocator. What remains works on caller-provided
+//! slices: [`filters::apply_gain_in_place`], [`effects::SoftLimiter`] and
+//! [`effects::soft_limit_buffer`], [`filters::ParametricEQ`] and the dB and
+//! sample helpers in [`utils`]. Since `std` is a default feature, a crate that
+//! turns the defaults off and still needs the engine must enable `std` (or a
+//! feature that implies it, such as `native`).
 
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 #![warn(rustdoc::missing_crate_level_docs)]
 #![cfg_attr(feature = "wasm", allow(clippy::unused_unit))]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 #[cfg(feature = "wasm")]
 use wasm_bindgen::prelude::*;
 
 // Import modules
+#[cfg(feature = "std")]
 pub mod engine;
+#[cfg(feature = "alloc")]
+mod error;
 pub mod filters;
 pub mod utils;
 pub mod effects;
+#[cfg(feature = "std")]
 pub mod processor;
+#[cfg(feature = "wav")]
+pub mod io;
 
 /// Re-export the main audio processing engine and error type
-pub use engine::{AudioEngine, AudioProcessingError};
-pub use effects::{AudioEffect, Delay};
+#[cfg(feature = "std")]
+pub use engine::{
+    AudioEngine, EngineConfig, EngineEffect, NoiseReductionMode, NonFiniteHandling, NormalizeMode,
+    ProcessReport, StreamingEngine,
+};
+#[cfg(feature = "alloc")]
+pub use error::AudioProcessingError;
+pub use effects::AudioEffect;
+#[cfg(feature = "std")]
+pub use effects::Delay;
+#[cfg(feature = "std")]
 pub use processor::ClearCastProcessor;
 
 // Función auxiliar para registrar errores en la consola de JavaScript
@@ -32,11 +65,17 @@ fn console_error(msg: &str) {
     web_sys::console::error_1(&wasm_bindgen::JsValue::from_str(msg));
 }
 
+/// Output ceiling of the WASM pipeline while the engine has no `safety_ceiling`
+#[cfg(feature = "wasm")]
+const DEFAULT_CEILING: f32 = 0.95;
+
 /// WebAssembly bindings for ClearCast core functionality
 #[cfg(feature = "wasm")]
 #[wasm_bindgen]
 pub struct WasmAudioEngine {
     engine: AudioEngine,
+    /// Return stage errors to JavaScript instead of logging them and continuing
+    strict: bool,
 }
 
 #[cfg(feature = "wasm")]
@@ -64,6 +103,7 @@ impl WasmAudioEngine {
         
         WasmAudioEngine {
             engine: AudioEngine::new(),
+            strict: false,
         }
     }
     
@@ -77,8 +117,36 @@ impl WasmAudioEngine {
         Ok(WasmAudioEngine {
             engine: AudioEngine::with_settings(noise_threshold, target_level)
                 .map_err(|e| JsValue::from_str(&e.to_string()))?,
+            strict: false,
         })
     }
+
+    /// Enable or disable strict mode
+    ///
+    /// In strict mode `processBuffer` rejects with the error of the first
+    /// failing stage (or an empty input) instead of logging it to the console
+    /// and carrying on. Lenient by default.
+    #[wasm_bindgen(js_name = setStrict)]
+    pub fn set_strict(&mut self, strict: bool) {
+        self.strict = strict;
+    }
+
+    /// Set the output ceiling (0.0 exclusive to 1.0)
+    ///
+    /// Every output of `processBuffer` and `compress` is clamped to it. The
+    /// default of 0.95 leaves some headroom; use 1.0 to allow full scale.
+    /// The value is stored as the engine's `safety_ceiling`.
+    #[wasm_bindgen(js_name = setCeiling)]
+    pub fn set_ceiling(&mut self, ceiling: f32) -> Result<(), JsValue> {
+        if ceiling.is_nan() || ceiling <= 0.0 || ceiling > 1.0 {
+            return Err(JsValue::from_str(&format!(
+                "Ceiling {} must be above 0.0 and at most 1.0",
+                ceiling
+            )));
+        }
+        self.engine.safety_ceiling = Some(ceiling);
+        Ok(())
+    }
     
     /// Process an audio buffer with all enabled effects
     /// 
@@ -89,58 +157,70 @@ impl WasmAudioEngine {
     /// A new Float32Array with the processed audio
     #[wasm_bindgen(js_name = processBuffer)]
     pub fn process_buffer(&self, input: &[f32]) -> Result<Vec<f32>, JsValue> {
+        self.process_samples(input).map_err(|e| JsValue::from_str(&e.to_string()))
+    }
+
+    /// Runs the processing chain of [`WasmAudioEngine::process_buffer`]
+    fn process_samples(&self, input: &[f32]) -> Result<Vec<f32>, AudioProcessingError> {
         if input.is_empty() {
-            return Ok(Vec::new());
+            return if self.strict { Err(AudioProcessingError::EmptyBuffer) } else { Ok(Vec::new()) };
         }
-        
+
+        // Avisar si la entrada llega a plena escala en lugar de recortarla en silencio
+        let clips = utils::clip_report(input, 1.0);
+        if clips.is_clipping() {
+            console_error(&format!(
+                "Input clipping: {} samples at or above full scale (first at {}, peak {:.3}); \
+                 consider lowering the input gain",
+                clips.count,
+                clips.first_index.unwrap_or(0),
+                clips.max_abs
+            ));
+        }
+
         // Convert input to Vec<f32> y asegurarse de que los valores estén en el rango [-1.0, 1.0]
         let mut samples: Vec<f32> = input.iter()
             .map(|&x| x.max(-1.0).min(1.0))
             .collect();
         
         // Aplicar reducción de ruido si está habilitada (con parámetros conservadores)
-        if self.engine.noise_reduction_threshold > 0.0 {
+        if self.engine.noise_reduction_threshold() > 0.0 {
             let mut audio = ndarray::Array1::from_vec(samples.clone());
             if let Err(e) = self.engine.apply_noise_reduction(&mut audio) {
-                console_error(&format!("Noise reduction warning: {}", e));
-                // Continuar incluso si hay un error en la reducción de ruido
+                // En modo permisivo se continúa incluso si hay un error en la reducción de ruido
+                self.stage_error("Noise reduction", e)?;
             } else {
                 samples = audio.to_vec();
             }
         }
         
         // Aplicar normalización con un margen de seguridad
-        if self.engine.target_peak > 0.0 && self.engine.target_peak <= 1.0 {
+        if self.engine.target_peak() > 0.0 && self.engine.target_peak() <= 1.0 {
             let mut audio = ndarray::Array1::from_vec(samples.clone());
             if let Err(e) = self.engine.normalize_audio(&mut audio) {
-                console_error(&format!("Normalization warning: {}", e));
-                // Continuar incluso si hay un error en la normalización
+                // En modo permisivo se continúa incluso si hay un error en la normalización
+                self.stage_error("Normalization", e)?;
             } else {
                 samples = audio.to_vec();
                 
                 // Asegurarse de que no haya clipping después de la normalización
-                for sample in &mut samples {
-                    *sample = sample.max(-0.99).min(0.99);
-                }
+                utils::simd::clamp_in_place(&mut samples, self.ceiling());
             }
         }
         
         // Aplicar efectos si hay alguno
         if !self.engine.effects.is_empty() {
             if let Err(e) = self.engine.apply_effects(&mut samples) {
-                console_error(&format!("Effects processing warning: {}", e));
-                // Continuar incluso si hay un error en los efectos
+                // En modo permisivo se continúa incluso si hay un error en los efectos
+                self.stage_error("Effects processing", e)?;
             }
         }
         
         // Aplicar limitador de picos suave para evitar distorsión
-        // con un margen de seguridad del 5% para evitar el recorte
         self.engine.apply_soft_limiter(&mut samples);
         
-        // Asegurarse una vez más de que los valores estén en el rango [-1.0, 1.0]
-        for sample in &mut samples {
-            *sample = sample.max(-0.95).min(0.95);
-        }
+        // Asegurarse una vez más de que ninguna muestra supere el techo
+        utils::simd::clamp_in_place(&mut samples, self.ceiling());
         
         Ok(samples)
     }
@@ -190,14 +270,148 @@ impl WasmAudioEngine {
         );
         
         // Asegurarse de que no haya clipping
+        let ceiling = self.ceiling();
         let output = output.into_iter()
-            .map(|x| x.max(-0.95).min(0.95))
+            .map(|x| x.clamp(-ceiling, ceiling))
             .collect::<Vec<f32>>();
         
         Ok(output)
     }
 }
 
+#[cfg(feature = "wasm")]
+impl WasmAudioEngine {
+    /// Output ceiling applied by the processing chain
+    fn ceiling(&self) -> f32 {
+        self.engine.safety_ceiling.unwrap_or(DEFAULT_CEILING)
+    }
+
+    /// Returns the error of a failed stage in strict mode, or logs it and carries on
+    ///
+    /// The strict error names the stage, like the lenient warning does.
+    fn stage_error(&self, stage: &str, error: AudioProcessingError) -> Result<(), AudioProcessingError> {
+        if self.strict {
+            let detail = match error {
+                AudioProcessingError::ProcessingError(message) => message,
+                other => other.to_string(),
+            };
+            return Err(AudioProcessingError::ProcessingError(format!("{} failed: {}", stage, detail)));
+        }
+        console_error(&format!("{} warning: {}", stage, error));
+        Ok(())
+    }
+}
+
+/// WebAssembly bindings for the complete [`ClearCastProcessor`] chain
+/// (Wiener denoising, EQ, compression, limiting and RMS normalization)
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub struct WasmClearCastProcessor {
+    processor: ClearCastProcessor,
+}
+
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+impl WasmClearCastProcessor {
+    /// Create a processor with default settings
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Sample rate of the audio in Hz
+    #[wasm_bindgen(constructor)]
+    pub fn new(sample_rate: f32) -> Result<WasmClearCastProcessor, JsValue> {
+        if !(sample_rate.is_finite() && sample_rate > 0.0) {
+            return Err(JsValue::from_str(&format!("Invalid sample rate: {}", sample_rate)));
+        }
+
+        Ok(WasmClearCastProcessor {
+            processor: ClearCastProcessor::new(sample_rate),
+        })
+    }
+
+    /// Set the gains of the low, mid and high EQ bands in dB
+    #[wasm_bindgen(js_name = configureEq)]
+    pub fn configure_eq(&mut self, low_gain: f32, mid_gain: f32, high_gain: f32) {
+        self.processor.configure_eq(low_gain, mid_gain, high_gain);
+    }
+
+    /// Set the compressor threshold (dBFS), ratio, attack and release (ms)
+    #[wasm_bindgen(js_name = configureCompressor)]
+    pub fn configure_compressor(&mut self, threshold: f32, ratio: f32, attack_ms: f32, release_ms: f32) {
+        self.processor.configure_compressor(threshold, ratio, attack_ms, release_ms);
+    }
+
+    /// Set the target RMS level of the output (0.0 to 1.0)
+    #[wasm_bindgen(js_name = setTargetRms)]
+    pub fn set_target_rms(&mut self, target_rms: f32) {
+        self.processor.set_target_rms(target_rms);
+    }
+
+    /// Configure the Wiener noise reduction stage
+    ///
+    /// Every call replaces the previous configuration. The parameters are
+    /// validated here so that a bad value from JavaScript is reported instead
+    /// of silently disabling denoising.
+    ///
+    /// # Arguments
+    /// * `noise_profile` - Noise magnitude spectrum, `fft_size / 2 + 1` bins
+    /// * `fft_size` - FFT window size (power of two)
+    /// * `hop_size` - Hop between windows (1 to `fft_size`)
+    /// * `smoothing` - Smoothing factor (0.0 to 1.0)
+    #[wasm_bindgen(js_name = configureNoiseReduction)]
+    pub fn configure_noise_reduction(
+        &mut self,
+        noise_profile: &[f32],
+        fft_size: usize,
+        hop_size: usize,
+        smoothing: f32,
+    ) -> Result<(), JsValue> {
+        if fft_size < 2 || !fft_size.is_power_of_two() {
+            return Err(JsValue::from_str(&format!("FFT size {} is not a power of two", fft_size)));
+        }
+        if noise_profile.len() != fft_size / 2 + 1 {
+            return Err(JsValue::from_str(&format!(
+                "The noise profile has {} bins, expected {} for an FFT size of {}",
+                noise_profile.len(),
+                fft_size / 2 + 1,
+                fft_size
+            )));
+        }
+        if hop_size == 0 || hop_size > fft_size {
+            return Err(JsValue::from_str(&format!(
+                "Hop size {} must be between 1 and the FFT size ({})",
+                hop_size, fft_size
+            )));
+        }
+        if !(0.0..=1.0).contains(&smoothing) {
+            return Err(JsValue::from_str(&format!(
+                "Smoothing {} must be between 0.0 and 1.0",
+                smoothing
+            )));
+        }
+
+        self.processor
+            .configure_noise_reduction(noise_profile.to_vec(), fft_size, hop_size, smoothing);
+        Ok(())
+    }
+
+    /// Clear all intermediate filter state before processing an unrelated clip
+    pub fn reset(&mut self) {
+        self.processor.reset();
+    }
+
+    /// Run a buffer through the whole chain
+    ///
+    /// # Arguments
+    /// * `input` - A Float32Array containing the audio samples
+    ///
+    /// # Returns
+    /// A new Float32Array with the processed audio
+    #[wasm_bindgen(js_name = processAudio)]
+    pub fn process_audio(&mut self, input: &[f32]) -> Vec<f32> {
+        self.processor.process_audio(input)
+    }
+}
+
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global allocator.
 #[cfg(all(feature = "wasm", feature = "wee_alloc"))]
 mod wasm_alloc {
@@ -333,3 +547,109 @@ mod tests {
         ));
     }
 }
+
+#[cfg(all(test, feature = "wasm"))]
+mod wasm_tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_strict_mode_reports_empty_buffer() {
+        let mut engine = WasmAudioEngine::new();
+        assert_eq!(engine.process_samples(&[]).unwrap(), Vec::<f32>::new());
+
+        engine.set_strict(true);
+        assert!(matches!(engine.process_samples(&[]), Err(AudioProcessingError::EmptyBuffer)));
+        assert!(engine.process_buffer(&[]).is_err());
+
+        // Valid input is processed as usual
+        assert_eq!(engine.process_samples(&[0.1, -0.2, 0.3]).unwrap().len(), 3);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_strict_stage_error_names_the_stage() {
+        let mut engine = WasmAudioEngine::new();
+        assert!(engine.stage_error("Normalization", AudioProcessingError::EmptyBuffer).is_ok());
+
+        engine.set_strict(true);
+        let error = engine
+            .stage_error("Normalization", AudioProcessingError::EmptyBuffer)
+            .unwrap_err();
+        assert!(error.to_string().contains("Normalization failed"), "{}", error);
+
+        let error = engine
+            .stage_error("Effects processing", AudioProcessingError::ProcessingError("effect lock poisoned".to_string()))
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Audio processing error: Effects processing failed: effect lock poisoned");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_ceiling_allows_full_scale() {
+        let limiter = engine::LimiterConfig { threshold: 1.0, knee_width: 0.0, ..Default::default() };
+        let mut engine = WasmAudioEngine {
+            engine: AudioEngine::with_limiter(0.0, 1.0, limiter).unwrap(),
+            strict: false,
+        };
+        let input: Vec<f32> = (0..1000).map(|i| 0.5 * (i as f32 * 0.05).sin()).collect();
+        let peak = |output: Vec<f32>| output.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+
+        // Por defecto la salida se queda en 0.95
+        assert_eq!(peak(engine.process_samples(&input).unwrap()), DEFAULT_CEILING);
+
+        engine.set_ceiling(1.0).unwrap();
+        let output_peak = peak(engine.process_samples(&input).unwrap());
+        assert!(output_peak > 0.999 && output_peak <= 1.0, "Peak {}", output_peak);
+    }
+
+    #[wasm_bindgen_test]
+    fn test_wasm_clearcast_processor_chain() {
+        let sample_rate = 16000.0;
+        let fft_size = 256;
+        let input: Vec<f32> = (0..4000)
+            .map(|i| {
+                let t = i as f32 / sample_rate;
+                0.4 * (2.0 * std::f32::consts::PI * 440.0 * t).sin() + 0.02 * (i as f32 * 12.9898).sin()
+            })
+            .collect();
+
+        let mut processor = WasmClearCastProcessor::new(sample_rate).unwrap();
+        processor.configure_eq(3.0, 0.0, -2.0);
+        processor.configure_compressor(-18.0, 3.0, 5.0, 50.0);
+        processor.set_target_rms(0.2);
+        processor
+            .configure_noise_reduction(&vec![0.01; fft_size / 2 + 1], fft_size, fft_size / 2, 0.9)
+            .unwrap();
+
+        let output = processor.process_audio(&input);
+        assert_eq!(output.len(), input.len());
+        assert!(output.iter().all(|x| x.is_finite() && x.abs() <= 1.0));
+
+        let rms = (output.iter().map(|x| x * x).sum::<f32>() / output.len() as f32).sqrt();
+        assert!((rms - 0.2).abs() < 0.01, "Output RMS {}", rms);
+
+        // The bindings drive the same chain as the native processor
+        let mut native = ClearCastProcessor::new(sample_rate);
+        native.configure_eq(3.0, 0.0, -2.0);
+        native.configure_compressor(-18.0, 3.0, 5.0, 50.0);
+        native.set_target_rms(0.2);
+        native.configure_noise_reduction(vec![0.01; fft_size / 2 + 1], fft_size, fft_size / 2, 0.9);
+        assert_eq!(output, native.process_audio(&input));
+
+        assert!(processor.process_audio(&[]).is_empty());
+    }
+
+    #[wasm_bindgen_test]
+    fn test_noise_profile_must_match_fft_size() {
+        let mut processor = WasmClearCastProcessor::new(16000.0).unwrap();
+        assert!(processor.configure_noise_reduction(&[0.01; 129], 256, 128, 0.9).is_ok());
+
+        for bins in [0, 1, 128, 130, 257] {
+            let profile = vec![0.01; bins];
+            assert!(
+                processor.configure_noise_reduction(&profile, 256, 128, 0.9).is_err(),
+                "{} bins accepted",
+                bins
+            );
+        }
+    }
+}
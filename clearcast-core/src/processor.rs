@@ -1,26 +1,125 @@
 //! ClearCastProcessor integrates multiple audio processing effects into a single pipeline.
-//! The processing is applied in the following order:
+//! By default the processing is applied in the following order:
 //! 1. Wiener filter for noise reduction
 //! 2. Parametric EQ for frequency shaping
-//! 3. Multiband compression
+//! 3. RMS compression, matched to the reference's crest factor when calibrated
 //! 4. Soft limiting to prevent clipping
 //! 5. RMS normalization
+//!
+//! The order can be changed, and stages left out, with
+//! [`ClearCastProcessor::set_stage_order`].
 
 use crate::filters::{
     compressor::compress_rms,
     equalizer::parametric_eq,
-    wiener_filter::reduce_noise_wiener,
+    wiener_filter::WienerDenoiser,
 };
+use crate::utils::{db_to_linear, linear_to_db};
+use biquad::{Biquad, Coefficients, DirectForm1, ToHertz, Type as FilterType, Q_BUTTERWORTH_F32};
 use ndarray::Array1;
 
+/// Crossover frequencies (Hz) separating the low/mid/high bands used for spectral balance,
+/// matching the corners of the parametric EQ's shelves
+const BALANCE_CROSSOVERS: (f32, f32) = (250.0, 2500.0);
+/// Maximum EQ correction applied when matching a reference (in dB)
+const MAX_MATCH_EQ_DB: f32 = 12.0;
+/// Maximum compression ratio used when matching a reference
+const MAX_MATCH_RATIO: f32 = 20.0;
+
+/// A processing stage of [`ClearCastProcessor`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Stage {
+    /// Wiener filter noise reduction
+    Denoise,
+    /// Parametric EQ
+    Eq,
+    /// RMS compression
+    Compress,
+    /// Soft limiting
+    Limit,
+    /// RMS normalization
+    Normalize,
+}
+
+/// Stage order used by a new processor
+pub const DEFAULT_STAGE_ORDER: [Stage; 5] = [
+    Stage::Denoise,
+    Stage::Eq,
+    Stage::Compress,
+    Stage::Limit,
+    Stage::Normalize,
+];
+
+/// Level measurements of a buffer used to match a reference clip
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReferenceProfile {
+    /// RMS level in dBFS
+    pub rms_db: f32,
+    /// Crest factor (peak to RMS ratio) in dB
+    pub crest_db: f32,
+    /// Share of the energy in the low, mid and high bands, in dB relative to the total
+    pub band_levels_db: [f32; 3],
+}
+
+impl ReferenceProfile {
+    /// Measures loudness, crest factor and spectral balance of a buffer
+    ///
+    /// Returns `None` for empty or silent buffers.
+    pub fn measure(samples: &[f32], sample_rate: f32) -> Option<Self> {
+        let energy: f32 = samples.iter().map(|&x| x * x).sum();
+        if samples.is_empty() || energy <= f32::MIN_POSITIVE {
+            return None;
+        }
+
+        let rms = (energy / samples.len() as f32).sqrt();
+        let peak = samples.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+
+        // Energy below the low crossover and above the high crossover; the rest is mid
+        let band_energy = |filter_type: FilterType<f32>, freq: f32| -> f32 {
+            match Coefficients::<f32>::from_params(filter_type, sample_rate.hz(), freq.hz(), Q_BUTTERWORTH_F32) {
+                Ok(coeffs) => {
+                    let mut filter = DirectForm1::<f32>::new(coeffs);
+                    samples.iter().map(|&x| filter.run(x).powi(2)).sum()
+                }
+                Err(_) => 0.0,
+            }
+        };
+        let low = band_energy(FilterType::LowPass, BALANCE_CROSSOVERS.0);
+        let high = band_energy(FilterType::HighPass, BALANCE_CROSSOVERS.1);
+        let mid = (energy - low - high).max(0.0);
+
+        let share_db = |band: f32| 10.0 * (band.max(energy * 1e-6) / energy).log10();
+
+        Some(Self {
+            rms_db: linear_to_db(rms),
+            crest_db: linear_to_db(peak / rms),
+            band_levels_db: [share_db(low), share_db(mid), share_db(high)],
+        })
+    }
+}
+
+/// Wiener filter settings given to [`ClearCastProcessor::configure_noise_reduction`]
+#[derive(Debug, Clone, PartialEq)]
+struct NoiseReductionSettings {
+    noise_profile: Vec<f32>,
+    fft_size: usize,
+    hop_size: usize,
+    smoothing: f32,
+}
+
 /// Main processor that combines multiple audio effects
 pub struct ClearCastProcessor {
     sample_rate: f32,
-    noise_profile: Vec<f32>,
+    /// `None` until noise reduction is configured, which skips the Wiener filter
+    noise_reduction: Option<NoiseReductionSettings>,
+    /// Wiener filter with its FFT plans, kept between calls
+    denoiser: Option<WienerDenoiser>,
     eq_bands: (f32, f32, f32), // (low, mid, high) gains in dB
     compressor_params: (f32, f32, f32, f32), // (threshold, ratio, attack, release)
     target_rms: f32,
     limiter_threshold: f32,
+    reference: Option<ReferenceProfile>,
+    stages: Vec<Stage>,
 }
 
 impl ClearCastProcessor {
@@ -31,17 +130,87 @@ impl ClearCastProcessor {
     pub fn new(sample_rate: f32) -> Self {
         Self {
             sample_rate,
-            noise_profile: vec![0.01; 1024], // Default noise profile
+            noise_reduction: None,          // No denoising until a noise profile is given
+            denoiser: None,
             eq_bands: (0.0, 0.0, 0.0),      // Flat EQ by default
             compressor_params: (-20.0, 4.0, 10.0, 100.0), // threshold, ratio, attack, release
             target_rms: 0.1,                // Target RMS level (0.0 to 1.0)
             limiter_threshold: 0.95,        // Limiter threshold (0.0 to 1.0)
+            reference: None,
+            stages: DEFAULT_STAGE_ORDER.to_vec(),
         }
     }
 
+    /// Calibrates the processor to make its output sound like a reference clip
+    ///
+    /// Measures the reference's loudness, crest factor and spectral balance.
+    /// While calibrated, normalization targets the reference loudness, and
+    /// each call to [`ClearCastProcessor::process_audio`] measures its input
+    /// and derives EQ gains and compressor settings that move its spectral
+    /// balance and crest factor towards the reference. These replace the
+    /// configured EQ, compressor and target RMS only for as long as the
+    /// reference is set; the configured values themselves are left untouched.
+    /// Silent references are ignored.
+    ///
+    /// # Arguments
+    /// * `reference` - Example clip with the desired sound
+    /// * `sample_rate` - Sample rate of the reference in Hz
+    pub fn calibrate_from_reference(&mut self, reference: &[f32], sample_rate: f32) {
+        let Some(profile) = ReferenceProfile::measure(reference, sample_rate) else {
+            return;
+        };
+
+        self.reference = Some(profile);
+    }
+
+    /// Returns the reference measured by the last calibration, if any
+    pub fn reference_profile(&self) -> Option<&ReferenceProfile> {
+        self.reference.as_ref()
+    }
+
+    /// Forgets the reference so the configured EQ, compressor and target RMS are used again
+    pub fn clear_calibration(&mut self) {
+        self.reference = None;
+    }
+
+
+    /// Sets the order in which [`ClearCastProcessor::process_audio`] runs the stages
+    ///
+    /// Stages left out of `order` are skipped, so an empty slice passes the
+    /// audio through unchanged.
+    ///
+    /// # Example
+    /// ```
+    /// use clearcast_core::processor::{ClearCastProcessor, Stage};
+    ///
+    /// let mut processor = ClearCastProcessor::new(44100.0);
+    /// // EQ before denoising, no compression
+    /// processor.set_stage_order(&[Stage::Eq, Stage::Denoise, Stage::Limit, Stage::Normalize]);
+    /// assert_eq!(processor.stage_order().len(), 4);
+    /// ```
+    pub fn set_stage_order(&mut self, order: &[Stage]) {
+        self.stages = order.to_vec();
+    }
+
+    /// Returns the stages run by [`ClearCastProcessor::process_audio`], in order
+    pub fn stage_order(&self) -> &[Stage] {
+        &self.stages
+    }
+
+    /// Clears all intermediate filter state
+    ///
+    /// Call this between unrelated clips so nothing from one leaks into the
+    /// next; the configuration (and any calibration) is kept.
+    pub fn reset(&mut self) {
+        if let Some(denoiser) = self.denoiser.as_mut() {
+            denoiser.reset();
+        }
+    }
 
     /// Configures the noise reduction parameters
     /// 
+    /// Each call replaces the previous configuration entirely.
+    /// 
     /// # Arguments
     /// * `noise_profile` - The noise profile to use for the Wiener filter
     /// * `fft_size` - Size of the FFT window (must be a power of 2)
@@ -54,11 +223,13 @@ impl ClearCastProcessor {
         hop_size: usize,
         smoothing: f32,
     ) {
-        self.noise_profile = noise_profile;
-        // Store these parameters for later use
-        self.noise_profile.push(fft_size as f32);
-        self.noise_profile.push(hop_size as f32);
-        self.noise_profile.push(smoothing);
+        self.denoiser = (fft_size > 0).then(|| WienerDenoiser::new(fft_size));
+        self.noise_reduction = Some(NoiseReductionSettings {
+            noise_profile,
+            fft_size,
+            hop_size,
+            smoothing,
+        });
     }
 
     /// Configures the parametric EQ
@@ -71,7 +242,7 @@ impl ClearCastProcessor {
         self.eq_bands = (low_gain, mid_gain, high_gain);
     }
 
-    /// Configures the multiband compressor
+    /// Configures the RMS compressor
     /// 
     /// # Arguments
     /// * `threshold` - Threshold in dBFS where compression begins
@@ -111,6 +282,12 @@ impl ClearCastProcessor {
         }
     }
 
+    /// Target RMS level in use: the reference loudness while calibrated
+    fn effective_target_rms(&self) -> f32 {
+        self.reference
+            .map_or(self.target_rms, |reference| db_to_linear(reference.rms_db).min(1.0))
+    }
+
     /// Normalizes the audio to the target RMS level
     fn normalize_rms(&self, samples: &mut [f32]) {
         // Calculate current RMS
@@ -123,7 +300,7 @@ impl ClearCastProcessor {
         }
         
         // Calculate scaling factor
-        let scale = self.target_rms / rms;
+        let scale = self.effective_target_rms() / rms;
         
         // Apply scaling
         for sample in samples.iter_mut() {
@@ -131,8 +308,79 @@ impl ClearCastProcessor {
         }
     }
 
+    /// EQ gains that move the input's spectral balance to the reference's
+    fn match_eq(reference: &ReferenceProfile, input: &ReferenceProfile) -> (f32, f32, f32) {
+        let gain = |band: usize| {
+            (reference.band_levels_db[band] - input.band_levels_db[band]).clamp(-MAX_MATCH_EQ_DB, MAX_MATCH_EQ_DB)
+        };
+        (gain(0), gain(1), gain(2))
+    }
+
+    /// Compressor settings that shrink the input's crest factor to the reference's
+    fn match_compressor(&self, reference: &ReferenceProfile, input: &ReferenceProfile) -> (f32, f32, f32, f32) {
+        let (_, _, attack_ms, release_ms) = self.compressor_params;
+
+        if input.crest_db > reference.crest_db && reference.crest_db > 0.0 {
+            // Compressing above the average level divides the peak excess by the ratio
+            let ratio = (input.crest_db / reference.crest_db).clamp(1.0, MAX_MATCH_RATIO);
+            (input.rms_db, ratio, attack_ms, release_ms)
+        } else {
+            // The input is already as dense as the reference
+            (0.0, 1.0, attack_ms, release_ms)
+        }
+    }
+
+    /// Profiles of the reference and of `samples`, if calibrated and `samples` isn't silent
+    fn match_profiles(&self, samples: &[f32]) -> Option<(ReferenceProfile, ReferenceProfile)> {
+        let reference = self.reference?;
+        Some((reference, ReferenceProfile::measure(samples, self.sample_rate)?))
+    }
+
+    /// Applies the Wiener filter, if noise reduction is configured
+    fn denoise(&mut self, samples: &mut Vec<f32>) {
+        if let (Some(settings), Some(denoiser)) = (&self.noise_reduction, self.denoiser.as_mut()) {
+            *samples = denoiser.process(
+                samples,
+                &settings.noise_profile,
+                settings.hop_size,
+                settings.smoothing,
+            );
+        }
+    }
+
+    /// Applies the parametric EQ, matching the reference's spectral balance if calibrated
+    fn equalize(&self, samples: &mut Vec<f32>) {
+        let bands = match self.match_profiles(samples) {
+            Some((reference, input)) => Self::match_eq(&reference, &input),
+            None => self.eq_bands,
+        };
+
+        if bands != (0.0, 0.0, 0.0) {
+            *samples = parametric_eq(samples, self.sample_rate, bands.0, bands.1, bands.2);
+        }
+    }
+
+    /// Applies RMS compression, matching the reference's crest factor if calibrated
+    fn compress(&self, samples: &mut Vec<f32>) {
+        let params = match self.match_profiles(samples) {
+            Some((reference, input)) => self.match_compressor(&reference, &input),
+            None => self.compressor_params,
+        };
+
+        *samples = compress_rms(
+            samples,
+            params.0, // threshold
+            params.1, // ratio
+            params.2, // attack
+            params.3, // release
+            self.sample_rate,
+        );
+    }
+
     /// Processes an audio buffer through the entire processing chain
     /// 
+    /// The stages run in the order set by [`ClearCastProcessor::set_stage_order`].
+    /// 
     /// # Arguments
     /// * `input` - Input audio buffer
     /// 
@@ -143,49 +391,18 @@ impl ClearCastProcessor {
             return Vec::new();
         }
 
-        // 1. Apply noise reduction (Wiener filter)
-        let mut processed = if self.noise_profile.len() > 3 {
-            let fft_size = self.noise_profile[self.noise_profile.len() - 3] as usize;
-            let hop_size = self.noise_profile[self.noise_profile.len() - 2] as usize;
-            let smoothing = self.noise_profile[self.noise_profile.len() - 1];
-            let noise_profile = &self.noise_profile[..self.noise_profile.len() - 3];
-            
-            if !noise_profile.is_empty() {
-                reduce_noise_wiener(input, noise_profile, fft_size, hop_size, smoothing)
-            } else {
-                input.to_vec()
+        let mut processed = input.to_vec();
+        // The stages borrow the processor mutably, so iterate over a copy of the order
+        for stage in self.stages.clone() {
+            match stage {
+                Stage::Denoise => self.denoise(&mut processed),
+                Stage::Eq => self.equalize(&mut processed),
+                Stage::Compress => self.compress(&mut processed),
+                Stage::Limit => self.apply_soft_limiter(&mut processed),
+                Stage::Normalize => self.normalize_rms(&mut processed),
             }
-        } else {
-            input.to_vec()
-        };
-
-        // 2. Apply parametric EQ
-        if self.eq_bands != (0.0, 0.0, 0.0) {
-            processed = parametric_eq(
-                &processed,
-                self.sample_rate,
-                self.eq_bands.0,
-                self.eq_bands.1,
-                self.eq_bands.2,
-            );
         }
 
-        // 3. Apply compression
-        processed = compress_rms(
-            &processed,
-            self.compressor_params.0, // threshold
-            self.compressor_params.1, // ratio
-            self.compressor_params.2, // attack
-            self.compressor_params.3, // release
-            self.sample_rate,
-        );
-
-        // 4. Apply soft limiter
-        self.apply_soft_limiter(&mut processed);
-
-        // 5. Normalize to target RMS
-        self.normalize_rms(&mut processed);
-
         processed
     }
 }
@@ -240,4 +457,183 @@ mod tests {
         let result = processor.process_audio(&[]);
         assert!(result.is_empty());
     }
+
+    #[test]
+    fn test_configure_noise_reduction_replaces_previous() {
+        let sample_rate = 16000.0;
+        let input: Vec<f32> = (0..4000)
+            .map(|i| 0.3 * (i as f32 * 0.2).sin() + 0.05 * (i as f32 * 7.1).sin())
+            .collect();
+
+        let mut processor = ClearCastProcessor::new(sample_rate);
+        processor.configure_noise_reduction(vec![0.02; 257], 512, 256, 0.5);
+        processor.configure_noise_reduction(vec![0.05; 65], 128, 64, 0.8);
+
+        // Nothing from the first call survives, not even trailing values
+        let expected = NoiseReductionSettings {
+            noise_profile: vec![0.05; 65],
+            fft_size: 128,
+            hop_size: 64,
+            smoothing: 0.8,
+        };
+        assert_eq!(processor.noise_reduction, Some(expected));
+
+        let mut fresh = ClearCastProcessor::new(sample_rate);
+        fresh.configure_noise_reduction(vec![0.05; 65], 128, 64, 0.8);
+        assert_eq!(processor.process_audio(&input), fresh.process_audio(&input));
+
+        // A profile with fewer than 3 bins is still applied
+        let mut short = ClearCastProcessor::new(sample_rate);
+        short.configure_noise_reduction(vec![0.2; 2], 2, 1, 0.5);
+        let mut unfiltered = ClearCastProcessor::new(sample_rate);
+        assert_ne!(short.process_audio(&input), unfiltered.process_audio(&input));
+    }
+
+    #[test]
+    fn test_reset_between_clips() {
+        let sample_rate = 16000.0;
+        let configure = |processor: &mut ClearCastProcessor| {
+            processor.configure_noise_reduction(vec![0.02; 129], 256, 128, 0.7);
+            processor.configure_eq(3.0, -1.0, 2.0);
+            processor.configure_compressor(-24.0, 3.0, 5.0, 80.0);
+        };
+
+        let first: Vec<f32> = (0..6000).map(|i| 0.8 * (i as f32 * 0.031).sin()).collect();
+        let second: Vec<f32> = (0..3000)
+            .map(|i| 0.2 * (i as f32 * 0.4).sin() + 0.05 * (i as f32 * 3.3).cos())
+            .collect();
+
+        // The denoiser's work buffers are what carries over from one clip to the next
+        let mut processor = ClearCastProcessor::new(sample_rate);
+        configure(&mut processor);
+        processor.process_audio(&first);
+        assert!(!processor.denoiser.as_ref().unwrap().is_cleared());
+
+        processor.reset();
+        assert!(processor.denoiser.as_ref().unwrap().is_cleared());
+        let after_reset = processor.process_audio(&second);
+
+        let mut fresh = ClearCastProcessor::new(sample_rate);
+        configure(&mut fresh);
+        assert_eq!(after_reset, fresh.process_audio(&second));
+    }
+
+    #[test]
+    fn test_stage_order_skips_compression() {
+        let sample_rate = 44100.0;
+        let signal: Vec<f32> = (0..8820)
+            .map(|i| {
+                let level = if (i / 1000) % 2 == 0 { 0.9 } else { 0.1 };
+                level * (2.0 * std::f32::consts::PI * 440.0 * i as f32 / sample_rate).sin()
+            })
+            .collect();
+
+        let mut processor = ClearCastProcessor::new(sample_rate);
+        processor.configure_eq(3.0, 0.0, -3.0);
+        processor.configure_compressor(-30.0, 8.0, 5.0, 50.0);
+        let compressed = processor.process_audio(&signal);
+
+        processor.set_stage_order(&[Stage::Denoise, Stage::Eq, Stage::Limit, Stage::Normalize]);
+        let uncompressed = processor.process_audio(&signal);
+        assert_ne!(compressed, uncompressed);
+
+        // The same chain built by hand, without the compressor
+        let mut expected = parametric_eq(&signal, sample_rate, 3.0, 0.0, -3.0);
+        processor.apply_soft_limiter(&mut expected);
+        processor.normalize_rms(&mut expected);
+        assert_eq!(uncompressed, expected);
+
+        // Without stages the audio passes through untouched
+        processor.set_stage_order(&[]);
+        assert_eq!(processor.process_audio(&signal), signal);
+    }
+
+    #[test]
+    fn test_calibrate_from_reference() {
+        let sample_rate = 44100.0;
+        let tone = |i: usize, freq: f32| (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate).sin();
+
+        // Referencia densa y fuerte: poco factor de cresta, mucha energía en graves
+        let reference: Vec<f32> = (0..44100)
+            .map(|i| 0.4 * tone(i, 150.0) + 0.1 * tone(i, 1000.0))
+            .collect();
+
+        // Señal de prueba dinámica: frases suaves con golpes fuertes
+        let signal: Vec<f32> = (0..44100)
+            .map(|i| {
+                let level = if (i / 2205) % 4 == 0 { 0.8 } else { 0.1 };
+                level * (0.3 * tone(i, 150.0) + 0.7 * tone(i, 1000.0))
+            })
+            .collect();
+
+        let target = ReferenceProfile::measure(&reference, sample_rate).unwrap();
+
+        let mut default_processor = ClearCastProcessor::new(sample_rate);
+        let default_output = default_processor.process_audio(&signal);
+        let default_profile = ReferenceProfile::measure(&default_output, sample_rate).unwrap();
+
+        let mut calibrated = ClearCastProcessor::new(sample_rate);
+        calibrated.calibrate_from_reference(&reference, sample_rate);
+        assert_eq!(calibrated.reference_profile(), Some(&target));
+        let calibrated_output = calibrated.process_audio(&signal);
+        let calibrated_profile = ReferenceProfile::measure(&calibrated_output, sample_rate).unwrap();
+
+        let loudness_error = |p: &ReferenceProfile| (p.rms_db - target.rms_db).abs();
+        let crest_error = |p: &ReferenceProfile| (p.crest_db - target.crest_db).abs();
+        let balance_error = |p: &ReferenceProfile| (p.band_levels_db[0] - target.band_levels_db[0]).abs();
+
+        assert!(
+            loudness_error(&calibrated_profile) < loudness_error(&default_profile),
+            "Loudness: calibrated {:?}, default {:?}, reference {:?}",
+            calibrated_profile, default_profile, target
+        );
+        assert!(
+            crest_error(&calibrated_profile) < crest_error(&default_profile),
+            "Crest factor: calibrated {:?}, default {:?}, reference {:?}",
+            calibrated_profile, default_profile, target
+        );
+        assert!(balance_error(&calibrated_profile) < balance_error(&default_profile));
+
+        // Sin referencia se vuelve a la configuración manual
+        calibrated.clear_calibration();
+        assert!(calibrated.reference_profile().is_none());
+    }
+
+    #[test]
+    fn test_clear_calibration_restores_settings() {
+        let sample_rate = 44100.0;
+        let tone = |i: usize, freq: f32| (2.0 * std::f32::consts::PI * freq * i as f32 / sample_rate).sin();
+        let reference: Vec<f32> = (0..44100).map(|i| 0.4 * tone(i, 150.0)).collect();
+        let signal: Vec<f32> = (0..22050)
+            .map(|i| if (i / 2205) % 3 == 0 { 0.8 } else { 0.1 } * tone(i, 1000.0))
+            .collect();
+
+        let mut processor = ClearCastProcessor::new(sample_rate);
+        processor.configure_eq(3.0, -2.0, 1.0);
+        processor.configure_compressor(-18.0, 2.5, 5.0, 80.0);
+        processor.set_target_rms(0.2);
+        let manual_output = processor.process_audio(&signal);
+
+        processor.calibrate_from_reference(&reference, sample_rate);
+        assert_ne!(processor.process_audio(&signal), manual_output);
+
+        // La calibración no toca la configuración manual
+        assert_eq!(processor.eq_bands, (3.0, -2.0, 1.0));
+        assert_eq!(processor.compressor_params, (-18.0, 2.5, 5.0, 80.0));
+        assert_eq!(processor.target_rms, 0.2);
+
+        processor.clear_calibration();
+        assert_eq!(processor.process_audio(&signal), manual_output);
+
+        // Lo configurado durante la calibración se aplica al quitarla
+        processor.calibrate_from_reference(&reference, sample_rate);
+        processor.configure_eq(0.0, 0.0, 0.0);
+        processor.process_audio(&signal);
+        assert_eq!(processor.eq_bands, (0.0, 0.0, 0.0));
+        processor.clear_calibration();
+        let mut flat = ClearCastProcessor::new(sample_rate);
+        flat.configure_compressor(-18.0, 2.5, 5.0, 80.0);
+        flat.set_target_rms(0.2);
+        assert_eq!(processor.process_audio(&signal), flat.process_audio(&signal));
+    }
 }
@@ -0,0 +1,99 @@
+//! Channel layout conversion
+//!
+//! The processing chain works on one mono buffer at a time. These helpers
+//! convert between mono, separate stereo channels and the interleaved layout
+//! (`L R L R ...`) used by WAV files and most audio APIs.
+
+/// Duplicates a mono buffer into identical left and right channels
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::mono_to_stereo;
+/// let (left, right) = mono_to_stereo(&[0.5, -0.25]);
+/// assert_eq!(left, right);
+/// ```
+pub fn mono_to_stereo(mono: &[f32]) -> (Vec<f32>, Vec<f32>) {
+    (mono.to_vec(), mono.to_vec())
+}
+
+/// Mixes a stereo pair down to mono by averaging the channels
+///
+/// If the channels differ in length the result is as long as the shorter one.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::stereo_to_mono;
+/// assert_eq!(stereo_to_mono(&[1.0, 0.5], &[0.0, 0.5]), vec![0.5, 0.5]);
+/// ```
+pub fn stereo_to_mono(left: &[f32], right: &[f32]) -> Vec<f32> {
+    left.iter().zip(right).map(|(l, r)| 0.5 * (l + r)).collect()
+}
+
+/// Interleaves separate channel buffers into one buffer of frames
+///
+/// Only complete frames are written, so the output has as many frames as the
+/// shortest channel.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::interleave;
+/// let interleaved = interleave(&[vec![1.0, 2.0], vec![-1.0, -2.0]]);
+/// assert_eq!(interleaved, vec![1.0, -1.0, 2.0, -2.0]);
+/// ```
+pub fn interleave(channels: &[Vec<f32>]) -> Vec<f32> {
+    let num_frames = channels.iter().map(Vec::len).min().unwrap_or(0);
+    let mut interleaved = Vec::with_capacity(num_frames * channels.len());
+    for i in 0..num_frames {
+        interleaved.extend(channels.iter().map(|channel| channel[i]));
+    }
+    interleaved
+}
+
+/// Splits an interleaved buffer into one buffer per channel
+///
+/// A trailing incomplete frame is dropped. `num_channels` of 0 is treated as 1.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::deinterleave;
+/// let channels = deinterleave(&[1.0, -1.0, 2.0, -2.0], 2);
+/// assert_eq!(channels, vec![vec![1.0, 2.0], vec![-1.0, -2.0]]);
+/// ```
+pub fn deinterleave(interleaved: &[f32], num_channels: usize) -> Vec<Vec<f32>> {
+    let num_channels = num_channels.max(1);
+    let mut channels = vec![Vec::with_capacity(interleaved.len() / num_channels); num_channels];
+    for frame in interleaved.chunks_exact(num_channels) {
+        for (channel, &sample) in channels.iter_mut().zip(frame) {
+            channel.push(sample);
+        }
+    }
+    channels
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_interleave_round_trip() {
+        let channels: Vec<Vec<f32>> = (0..3)
+            .map(|c| (0..100).map(|i| (i * 3 + c) as f32).collect())
+            .collect();
+
+        let interleaved = interleave(&channels);
+        assert_eq!(interleaved.len(), 300);
+        assert_eq!(&interleaved[..4], &[0.0, 1.0, 2.0, 3.0]);
+        assert_eq!(deinterleave(&interleaved, 3), channels);
+
+        // Un marco incompleto al final se descarta
+        assert_eq!(deinterleave(&interleaved[..299], 3)[2].len(), 99);
+        assert!(interleave(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_stereo_to_mono_of_identical_channels() {
+        let mono: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+        let (left, right) = mono_to_stereo(&mono);
+        assert_eq!(stereo_to_mono(&left, &right), mono);
+    }
+}
@@ -0,0 +1,125 @@
+//! Dither and quantization for fixed-point export
+//!
+//! Rounding float audio to 16 bits turns the rounding error into distortion
+//! that follows the signal, which is audible on quiet passages and fades.
+//! Adding triangular-PDF (TPDF) noise of one LSB before quantizing decorrelates
+//! that error from the signal and turns it into a constant, benign noise floor.
+//!
+//! The LSB matches the scaling of the WAV reader and writer (`io::read_wav`,
+//! `io::write_wav`), where -1.0 maps to `-2^(bit_depth - 1)` (e.g. `i16::MIN`
+//! for 16 bits), so a quantized buffer is written without further rounding.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+/// Supported bit depths; 1 bit has no positive level under this scaling
+const MIN_BIT_DEPTH: u32 = 2;
+const MAX_BIT_DEPTH: u32 = 24;
+
+/// Seed source for the noise generator, advanced on every call so
+/// consecutive buffers don't get the same noise
+static SEED: AtomicU32 = AtomicU32::new(0x9E37_79B9);
+
+/// Size of one quantization step for the given bit depth
+fn lsb(bit_depth: u32) -> f32 {
+    let bit_depth = bit_depth.clamp(MIN_BIT_DEPTH, MAX_BIT_DEPTH);
+    1.0 / (1u32 << (bit_depth - 1)) as f32
+}
+
+/// Adds triangular-PDF dither noise of one LSB peak to every sample
+///
+/// Call this right before [`quantize`] (or before writing a fixed-point file).
+/// The noise is the sum of two independent uniform values, so it lies in
+/// `[-1, 1]` LSB with an RMS of `LSB / sqrt(6)`.
+///
+/// # Arguments
+/// * `buffer` - Audio samples, modified in place
+/// * `bit_depth` - Target bit depth of the export (2 to 24)
+pub fn apply_tpdf_dither(buffer: &mut [f32], bit_depth: u32) {
+    let half_lsb = 0.5 * lsb(bit_depth);
+
+    // xorshift32 with a fresh seed per call: fast and good enough for dither
+    let mut state = SEED.fetch_add(0x9E37_79B9, Ordering::Relaxed) | 1;
+    let mut uniform = move || {
+        state ^= state << 13;
+        state ^= state >> 17;
+        state ^= state << 5;
+        // Uniform in [-1, 1)
+        (state as f32 / u32::MAX as f32) * 2.0 - 1.0
+    };
+
+    for sample in buffer.iter_mut() {
+        *sample += half_lsb * (uniform() + uniform());
+    }
+}
+
+/// Rounds every sample to the nearest level representable at `bit_depth`
+///
+/// Samples are clamped first to the representable range, -1.0 to one LSB
+/// below 1.0.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::dither::quantize;
+/// let mut samples = vec![0.2, -0.3, 1.5];
+/// quantize(&mut samples, 2);
+/// assert_eq!(samples, vec![0.0, -0.5, 0.5]);
+/// ```
+pub fn quantize(buffer: &mut [f32], bit_depth: u32) {
+    let lsb = lsb(bit_depth);
+    for sample in buffer.iter_mut() {
+        *sample = (sample.clamp(-1.0, 1.0 - lsb) / lsb).round() * lsb;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tpdf_dither_noise_floor() {
+        let lsb = lsb(16);
+        let mut noise = vec![0.0f32; 100_000];
+        apply_tpdf_dither(&mut noise, 16);
+
+        // Silence becomes a triangular noise floor of one LSB peak
+        assert!(noise.iter().all(|x| x.abs() <= lsb));
+        let rms = (noise.iter().map(|x| x * x).sum::<f32>() / noise.len() as f32).sqrt();
+        let expected = lsb / 6.0f32.sqrt();
+        assert!((rms - expected).abs() < 0.05 * expected, "Dither RMS {} expected {}", rms, expected);
+
+        // After quantizing only the three levels around zero remain
+        quantize(&mut noise, 16);
+        assert!(noise.iter().all(|&x| x == 0.0 || (x.abs() - lsb).abs() < 1e-9));
+        assert!(noise.iter().any(|&x| x != 0.0));
+
+        // Consecutive calls produce different noise
+        let mut first = vec![0.0f32; 64];
+        let mut second = vec![0.0f32; 64];
+        apply_tpdf_dither(&mut first, 16);
+        apply_tpdf_dither(&mut second, 16);
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_dither_leaves_full_scale_signal_intact() {
+        let signal: Vec<f32> = (0..48000)
+            .map(|i| (2.0 * std::f32::consts::PI * 997.0 * i as f32 / 48000.0).sin())
+            .collect();
+
+        let mut exported = signal.clone();
+        apply_tpdf_dither(&mut exported, 16);
+        quantize(&mut exported, 16);
+
+        assert!(exported.iter().all(|x| x.abs() <= 1.0));
+        let error_rms = (exported
+            .iter()
+            .zip(&signal)
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum::<f32>()
+            / signal.len() as f32)
+            .sqrt();
+
+        // The added error stays around the 16-bit noise floor (about -96 dBFS)
+        assert!(error_rms < 2.0 * lsb(16), "Error RMS {}", error_rms);
+    }
+}
@@ -0,0 +1,281 @@
+//! Level metering helpers
+//!
+//! The sample peak of a buffer can under-read the level of the reconstructed
+//! analog waveform, because the highest point of the waveform may fall between
+//! two samples. The true-peak meter estimates those inter-sample peaks by
+//! oversampling the signal with a windowed-sinc interpolator, in the spirit of
+//! ITU-R BS.1770.
+//!
+//! The crest factor and dynamic range measurements describe how much the level
+//! of a signal moves, which is what compressor settings have to deal with.
+//!
+//! The stereo correlation meter tells how much of a stereo mix would cancel
+//! out when summed to mono.
+
+use std::f32::consts::PI;
+
+use super::{linear_to_db, simd};
+
+/// Oversampling factor used by the true-peak meter
+const OVERSAMPLING: usize = 4;
+/// Number of input samples considered on each side of an interpolated point
+const HALF_TAPS: usize = 8;
+
+/// Estimates the true (inter-sample) peak of a buffer as a linear amplitude
+///
+/// The result is never lower than the sample peak. Non-finite samples are
+/// ignored, along with the interpolated points they would poison.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::true_peak;
+/// // A sine at fs/4 sampled 45° off its crests never hits ±1.0 on a sample
+/// let samples: Vec<f32> = (0..64)
+///     .map(|n| (std::f32::consts::FRAC_PI_2 * n as f32 + std::f32::consts::FRAC_PI_4).sin())
+///     .collect();
+/// assert!(true_peak(&samples) > 0.95);
+/// ```
+pub fn true_peak(samples: &[f32]) -> f32 {
+    let mut peak = simd::peak_abs(samples);
+    if samples.len() < 2 {
+        return peak;
+    }
+
+    let kernels = interpolation_kernels();
+    let len = samples.len() as isize;
+
+    for n in 0..samples.len() - 1 {
+        for kernel in &kernels {
+            let mut acc = 0.0f32;
+            for (tap, &coeff) in kernel.iter().enumerate() {
+                let idx = n as isize + tap as isize - HALF_TAPS as isize + 1;
+                if (0..len).contains(&idx) {
+                    acc += samples[idx as usize] * coeff;
+                }
+            }
+            if acc.is_finite() {
+                peak = peak.max(acc.abs());
+            }
+        }
+    }
+
+    peak
+}
+
+/// Estimates the true peak of a buffer in dBTP (dB relative to full scale)
+///
+/// Silent buffers return negative infinity.
+pub fn true_peak_dbtp(samples: &[f32]) -> f32 {
+    20.0 * true_peak(samples).log10()
+}
+
+/// Percentile of the windowed RMS levels treated as the loud part of the signal
+const LOUD_PERCENTILE: f32 = 0.95;
+/// Percentile of the windowed RMS levels treated as the quiet part of the signal
+const QUIET_PERCENTILE: f32 = 0.10;
+
+/// Root mean square of the finite samples of a buffer (0.0 if there are none)
+pub(crate) fn rms(samples: &[f32]) -> f32 {
+    let (sum, count) = samples
+        .iter()
+        .filter(|x| x.is_finite())
+        .fold((0.0f64, 0usize), |(sum, count), &x| (sum + (x * x) as f64, count + 1));
+    if count == 0 {
+        0.0
+    } else {
+        (sum / count as f64).sqrt() as f32
+    }
+}
+
+/// Ratio between the peak and the RMS level of a buffer, in dB
+///
+/// A full-scale square wave measures 0 dB and a sine about 3 dB; speech and
+/// unprocessed acoustic recordings usually measure 12 to 20 dB. Empty and
+/// silent buffers return 0.0. Non-finite samples are ignored.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::crest_factor;
+/// let square = [0.5, -0.5, 0.5, -0.5];
+/// assert!(crest_factor(&square).abs() < 1e-6);
+/// ```
+pub fn crest_factor(buffer: &[f32]) -> f32 {
+    let rms = rms(buffer);
+    if rms > 0.0 {
+        linear_to_db(simd::peak_abs(buffer) / rms)
+    } else {
+        0.0
+    }
+}
+
+/// Spread between the loud and quiet passages of a buffer, in dB
+///
+/// The buffer is cut into consecutive windows of `window_ms` and the RMS level
+/// of each one is measured. The result is the difference between the 95th and
+/// the 10th percentile of those levels, so a few isolated bursts or pauses
+/// don't dominate the measurement. Silent windows count at [`super::MIN_DB`].
+/// Returns 0.0 when the buffer is shorter than one window.
+///
+/// # Arguments
+/// * `buffer` - Audio samples
+/// * `window_ms` - Length of each RMS window in milliseconds
+/// * `sample_rate` - Sample rate in Hz
+pub fn dynamic_range_db(buffer: &[f32], window_ms: f32, sample_rate: f32) -> f32 {
+    let window = (window_ms * 0.001 * sample_rate).round().max(1.0) as usize;
+    let mut levels: Vec<f32> = buffer.chunks_exact(window).map(|w| linear_to_db(rms(w))).collect();
+    if levels.is_empty() {
+        return 0.0;
+    }
+
+    levels.sort_by(f32::total_cmp);
+    let percentile = |p: f32| levels[((levels.len() - 1) as f32 * p).round() as usize];
+    percentile(LOUD_PERCENTILE) - percentile(QUIET_PERCENTILE)
+}
+
+/// Correlation between the two channels of a stereo signal, from -1.0 to 1.0
+///
+/// This is the Pearson correlation coefficient of the samples. +1.0 means
+/// both channels carry the same waveform (up to a gain), so the mix is mono
+/// compatible; values near 0.0 mean unrelated channels, such as a wide
+/// reverb; negative values mean the channels are out of phase and will
+/// cancel when summed to mono. Only the first `min(left.len(), right.len())`
+/// sample pairs are compared. Empty input, a silent or constant channel, or
+/// non-finite samples return 0.0.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::stereo_correlation;
+/// let left = [0.5, -0.25, 0.1, -0.4];
+/// let inverted: Vec<f32> = left.iter().map(|x| -x).collect();
+/// assert!((stereo_correlation(&left, &left) - 1.0).abs() < 1e-6);
+/// assert!((stereo_correlation(&left, &inverted) + 1.0).abs() < 1e-6);
+/// ```
+pub fn stereo_correlation(left: &[f32], right: &[f32]) -> f32 {
+    let len = left.len().min(right.len());
+    if len == 0 {
+        return 0.0;
+    }
+    let (left, right) = (&left[..len], &right[..len]);
+
+    let mean = |channel: &[f32]| channel.iter().map(|&x| x as f64).sum::<f64>() / len as f64;
+    let (left_mean, right_mean) = (mean(left), mean(right));
+
+    let (mut covariance, mut left_power, mut right_power) = (0.0f64, 0.0f64, 0.0f64);
+    for (&l, &r) in left.iter().zip(right) {
+        let (l, r) = (l as f64 - left_mean, r as f64 - right_mean);
+        covariance += l * r;
+        left_power += l * l;
+        right_power += r * r;
+    }
+
+    let norm = (left_power * right_power).sqrt();
+    if norm > 0.0 && norm.is_finite() {
+        (covariance / norm).clamp(-1.0, 1.0) as f32
+    } else {
+        0.0
+    }
+}
+
+/// Builds the windowed-sinc kernels for each fractional position between samples
+///
+/// Returned as a fixed-size array so measuring the true peak never allocates.
+fn interpolation_kernels() -> [[f32; 2 * HALF_TAPS]; OVERSAMPLING - 1] {
+    core::array::from_fn(|index| {
+        let frac = (index + 1) as f32 / OVERSAMPLING as f32;
+        let mut kernel = [0.0f32; 2 * HALF_TAPS];
+        for (tap, coeff) in kernel.iter_mut().enumerate() {
+            // Distance from the interpolated point to this input sample
+            let x = tap as f32 - HALF_TAPS as f32 + 1.0 - frac;
+            let sinc = if x.abs() < f32::EPSILON { 1.0 } else { (PI * x).sin() / (PI * x) };
+            let window = 0.5 * (1.0 + (PI * x / HALF_TAPS as f32).cos());
+            *coeff = sinc * window;
+        }
+        kernel
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    #[test]
+    fn test_true_peak_detects_inter_sample_peak() {
+        // Seno a fs/4 desfasado 45°: todas las muestras valen ±0.707
+        let samples: Vec<f32> = (0..256)
+            .map(|n| (FRAC_PI_2 * n as f32 + FRAC_PI_4).sin())
+            .collect();
+        let sample_peak = samples.iter().fold(0.0f32, |max, &x| max.max(x.abs()));
+
+        assert!(sample_peak < 0.71);
+        let tp = true_peak(&samples);
+        assert!((tp - 1.0).abs() < 0.02, "Expected true peak near 1.0, got {}", tp);
+    }
+
+    #[test]
+    fn test_true_peak_never_below_sample_peak() {
+        let samples = vec![0.0, 0.0, 0.8, 0.0, 0.0];
+        assert!(true_peak(&samples) >= 0.8);
+        assert_eq!(true_peak(&[0.5]), 0.5);
+        assert_eq!(true_peak(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_crest_factor_of_sine() {
+        let sine: Vec<f32> = (0..48000)
+            .map(|n| 0.8 * (2.0 * PI * 440.0 * n as f32 / 48000.0).sin())
+            .collect();
+
+        // Pico / RMS de un seno = sqrt(2), unos 3.01 dB
+        let crest = crest_factor(&sine);
+        assert!((crest - 3.01).abs() < 0.05, "Crest factor {} dB", crest);
+        assert_eq!(crest_factor(&[0.0; 8]), 0.0);
+        assert_eq!(crest_factor(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_dynamic_range_of_loud_and_quiet_sections() {
+        let sample_rate = 8000.0;
+        let tone = |n: usize, amplitude: f32| amplitude * (2.0 * PI * 200.0 * n as f32 / sample_rate).sin();
+
+        // Un tono constante apenas tiene rango dinámico
+        let steady: Vec<f32> = (0..8000).map(|n| tone(n, 0.5)).collect();
+        assert!(dynamic_range_db(&steady, 50.0, sample_rate) < 0.5);
+
+        // Secciones de medio segundo que alternan entre 0.5 y 0.005 (40 dB menos)
+        let alternating: Vec<f32> = (0..40000)
+            .map(|n| tone(n, if (n / 4000) % 2 == 0 { 0.5 } else { 0.005 }))
+            .collect();
+        let range = dynamic_range_db(&alternating, 50.0, sample_rate);
+        assert!((range - 40.0).abs() < 1.0, "Dynamic range {} dB", range);
+
+        assert_eq!(dynamic_range_db(&steady[..10], 50.0, sample_rate), 0.0);
+    }
+
+    #[test]
+    fn test_stereo_correlation() {
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = rand::rngs::StdRng::seed_from_u64(351);
+        let left: Vec<f32> = (0..44100).map(|_| rng.gen_range(-0.5..0.5)).collect();
+        let right: Vec<f32> = (0..44100).map(|_| rng.gen_range(-0.5..0.5)).collect();
+
+        // Canales idénticos (aunque con distinta ganancia) e invertidos
+        let quieter: Vec<f32> = left.iter().map(|x| 0.3 * x).collect();
+        let inverted: Vec<f32> = left.iter().map(|x| -x).collect();
+        assert!((stereo_correlation(&left, &left) - 1.0).abs() < 1e-6);
+        assert!((stereo_correlation(&left, &quieter) - 1.0).abs() < 1e-6);
+        assert!((stereo_correlation(&left, &inverted) + 1.0).abs() < 1e-6);
+
+        // Ruido independiente: cerca de cero (el error típico es 1/√n ≈ 0.005)
+        let independent = stereo_correlation(&left, &right);
+        assert!(independent.abs() < 0.02, "Correlation {}", independent);
+
+        // Mezcla a partes iguales de una señal común y ruido independiente
+        let half: Vec<f32> = left.iter().zip(&right).map(|(l, r)| l + r).collect();
+        assert!((stereo_correlation(&left, &half) - 0.5f32.sqrt()).abs() < 0.02);
+
+        assert_eq!(stereo_correlation(&[], &left), 0.0);
+        assert_eq!(stereo_correlation(&[0.0; 16], &left[..16]), 0.0);
+    }
+}
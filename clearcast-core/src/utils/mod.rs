@@ -1,30 +1,328 @@
 //! Utility functions for ClearCast
 
-use std::f32::consts::PI;
+use core::f32::consts::PI;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+pub mod sample;
+pub mod simd;
+#[cfg(feature = "std")]
+pub mod channels;
+#[cfg(feature = "std")]
+pub mod dither;
+#[cfg(feature = "std")]
+pub mod meter;
+#[cfg(feature = "std")]
+pub mod pitch;
+#[cfg(feature = "std")]
+pub mod spectrum;
+#[cfg(feature = "std")]
+pub mod window;
+
+pub use sample::Sample;
+#[cfg(feature = "std")]
+pub use channels::{deinterleave, interleave, mono_to_stereo, stereo_to_mono};
+#[cfg(feature = "std")]
+pub use dither::{apply_tpdf_dither, quantize};
+#[cfg(feature = "std")]
+pub use meter::{crest_factor, dynamic_range_db, stereo_correlation, true_peak, true_peak_dbtp};
+#[cfg(feature = "std")]
+pub use pitch::detect_pitch;
+#[cfg(feature = "std")]
+pub use spectrum::magnitude_spectrum;
+#[cfg(feature = "std")]
+pub use window::{make_window, WindowType};
 
 /// Converts frequency in Hz to angular frequency (radians/sample)
 pub fn hz_to_radians(frequency: f32, sample_rate: f32) -> f32 {
     2.0 * PI * frequency / sample_rate
 }
 
+/// Lowest level returned by [`linear_to_db`], used for silence (in dB)
+pub const MIN_DB: f32 = -120.0;
+
+/// Converts a level in dB to a linear gain factor
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::db_to_linear;
+/// assert!((db_to_linear(-6.0) - 0.501).abs() < 1e-3);
+/// assert_eq!(db_to_linear(0.0), 1.0);
+/// ```
+pub fn db_to_linear(db: f32) -> f32 {
+    f32::from_db(db)
+}
+
+/// Converts a linear gain factor to dB
+///
+/// Zero, negative and NaN inputs return [`MIN_DB`] instead of negative
+/// infinity, and so does anything quieter than that floor.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::{linear_to_db, MIN_DB};
+/// assert!((linear_to_db(0.5) + 6.02).abs() < 1e-2);
+/// assert_eq!(linear_to_db(0.0), MIN_DB);
+/// ```
+pub fn linear_to_db(linear: f32) -> f32 {
+    linear.to_db()
+}
+
+/// Converts a sample amplitude to dBFS (dB relative to a full scale of 1.0)
+///
+/// The sign of the sample is ignored, so -0.5 and 0.5 are both about -6 dBFS.
+pub fn amplitude_to_dbfs(amplitude: f32) -> f32 {
+    linear_to_db(amplitude.abs())
+}
+
 /// Normalizes a vector of audio samples to the range [-1.0, 1.0]
-pub fn normalize_audio(samples: &mut [f32]) {
+///
+/// Works on `f32` and `f64` buffers alike.
+pub fn normalize_audio<T: Sample>(samples: &mut [T]) {
     if samples.is_empty() {
         return;
     }
 
     // Find the maximum absolute value
-    let max_val = samples
-        .iter()
-        .fold(0.0f32, |max, &x| max.max(x.abs()));
+    let max_val = simd::peak_abs(samples);
 
     // Avoid division by zero
-    if max_val > 0.0 {
-        let scale = 1.0 / max_val;
-        for sample in samples.iter_mut() {
-            *sample *= scale;
+    if max_val > T::zero() {
+        simd::scale_in_place(samples, max_val.recip());
+    }
+}
+
+/// Summary of the samples that reach a clipping threshold
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ClipReport {
+    /// Number of samples at or beyond the threshold
+    pub count: usize,
+    /// Highest absolute sample value in the buffer
+    pub max_abs: f32,
+    /// Index of the first sample at or beyond the threshold
+    pub first_index: Option<usize>,
+}
+
+impl ClipReport {
+    /// Whether any sample reached the threshold
+    pub fn is_clipping(&self) -> bool {
+        self.count > 0
+    }
+}
+
+/// Counts the samples whose absolute value is at or beyond `threshold`
+///
+/// Use a threshold of 1.0 to count samples at or above full scale.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::count_clipped;
+/// assert_eq!(count_clipped(&[0.5, 1.0, -1.2, 0.99], 1.0), 2);
+/// ```
+pub fn count_clipped(buffer: &[f32], threshold: f32) -> usize {
+    buffer.iter().filter(|x| x.abs() >= threshold).count()
+}
+
+/// Reports how many samples clip, the first one that does and the overall peak
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::clip_report;
+/// let report = clip_report(&[0.5, 1.0, -1.2, 0.99], 1.0);
+/// assert_eq!(report.count, 2);
+/// assert_eq!(report.first_index, Some(1));
+/// assert_eq!(report.max_abs, 1.2);
+/// ```
+pub fn clip_report(buffer: &[f32], threshold: f32) -> ClipReport {
+    let mut report = ClipReport::default();
+    for (i, &x) in buffer.iter().enumerate() {
+        let level = x.abs();
+        report.max_abs = report.max_abs.max(level);
+        if level >= threshold {
+            report.count += 1;
+            report.first_index.get_or_insert(i);
         }
     }
+    report
+}
+
+/// Applies a linear fade-in over the first `fade_samples` samples
+///
+/// The first sample is silenced and the gain rises linearly to unity. A fade
+/// longer than the buffer ramps across the whole buffer.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::fade_in;
+/// let mut samples = vec![1.0; 4];
+/// fade_in(&mut samples, 2);
+/// assert_eq!(samples, vec![0.0, 0.5, 1.0, 1.0]);
+/// ```
+pub fn fade_in(buffer: &mut [f32], fade_samples: usize) {
+    let len = fade_samples.min(buffer.len());
+    for (i, sample) in buffer[..len].iter_mut().enumerate() {
+        *sample *= i as f32 / len as f32;
+    }
+}
+
+/// Applies a linear fade-out over the last `fade_samples` samples
+///
+/// The mirror image of [`fade_in`]: the last sample is silenced.
+pub fn fade_out(buffer: &mut [f32], fade_samples: usize) {
+    let len = fade_samples.min(buffer.len());
+    let start = buffer.len() - len;
+    for (i, sample) in buffer[start..].iter_mut().rev().enumerate() {
+        *sample *= i as f32 / len as f32;
+    }
+}
+
+/// Applies a fade-in and a fade-out to a buffer
+///
+/// When both fades overlap, their gains multiply.
+pub fn apply_fades(buffer: &mut [f32], fade_in_samples: usize, fade_out_samples: usize) {
+    fade_in(buffer, fade_in_samples);
+    fade_out(buffer, fade_out_samples);
+}
+
+/// Finds the non-silent region of a buffer, e.g. to trim the edges of a clip
+///
+/// A sample is silent when its level is at or below `threshold_db`. Leading and
+/// trailing silence is only trimmed when it lasts at least `min_silence_ms`, so
+/// a short breath before the first word is kept.
+///
+/// # Arguments
+/// * `buffer` - Input audio buffer
+/// * `threshold_db` - Level in dBFS at or below which a sample counts as silence
+/// * `min_silence_ms` - Shortest leading or trailing silence that gets trimmed
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Returns
+/// Start (inclusive) and end (exclusive) sample indices of the region to keep.
+/// An all-silent buffer gives the empty range `(0, 0)`.
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::trim_silence;
+/// let samples = [0.0, 0.0, 0.5, -0.5, 0.0];
+/// assert_eq!(trim_silence(&samples, -60.0, 0.0, 1000.0), (2, 4));
+/// assert_eq!(trim_silence(&[0.0; 8], -60.0, 0.0, 1000.0), (0, 0));
+/// ```
+pub fn trim_silence(buffer: &[f32], threshold_db: f32, min_silence_ms: f32, sample_rate: f32) -> (usize, usize) {
+    let threshold = db_to_linear(threshold_db);
+    let is_sound = |x: &f32| x.abs() > threshold;
+
+    let Some(first) = buffer.iter().position(is_sound) else {
+        return (0, 0);
+    };
+    let last = buffer.iter().rposition(is_sound).unwrap_or(first) + 1;
+
+    let min_silence = (min_silence_ms * sample_rate / 1000.0) as usize;
+    let start = if first >= min_silence { first } else { 0 };
+    let end = if buffer.len() - last >= min_silence { last } else { buffer.len() };
+    (start, end)
+}
+
+/// Returns the part of a buffer left after trimming its leading and trailing silence
+///
+/// See [`trim_silence`] for the meaning of the arguments.
+pub fn trim_silence_buffer(buffer: &[f32], threshold_db: f32, min_silence_ms: f32, sample_rate: f32) -> &[f32] {
+    let (start, end) = trim_silence(buffer, threshold_db, min_silence_ms, sample_rate);
+    &buffer[start..end]
+}
+
+/// Where the silence is inserted when padding a buffer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PadPosition {
+    /// Insert the silence before the audio
+    Start,
+    /// Insert the silence after the audio
+    End,
+    /// Split the silence between both sides, centering the audio
+    /// (the extra sample, if any, goes at the end)
+    Both,
+}
+
+/// Pads a buffer with silence so its length becomes a multiple of `multiple`
+///
+/// Buffers whose length is already a multiple are returned unchanged. A
+/// `multiple` of 0 or 1 is treated as "no alignment".
+///
+/// # Arguments
+/// * `samples` - Input audio buffer
+/// * `multiple` - Grid size in samples
+/// * `position` - Where to place the inserted silence
+///
+/// # Returns
+/// New buffer whose length is the next multiple of `multiple`
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::{pad_to_multiple, PadPosition};
+/// let padded = pad_to_multiple(&[1.0; 100], 64, PadPosition::End);
+/// assert_eq!(padded.len(), 128);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn pad_to_multiple(samples: &[f32], multiple: usize, position: PadPosition) -> Vec<f32> {
+    if multiple <= 1 {
+        return samples.to_vec();
+    }
+
+    let remainder = samples.len() % multiple;
+    if remainder == 0 {
+        return samples.to_vec();
+    }
+
+    let padding = multiple - remainder;
+    let padded_len = samples.len() + padding;
+    let leading = match position {
+        PadPosition::Start => padding,
+        PadPosition::End => 0,
+        PadPosition::Both => padding / 2,
+    };
+
+    let mut output = alloc::vec![0.0; padded_len];
+    output[leading..leading + samples.len()].copy_from_slice(samples);
+    output
+}
+
+/// Compensates a latency difference between the two channels of a stereo chain
+///
+/// When left and right run through chains with different latency (e.g. only
+/// one of them has a lookahead limiter), the channel that comes out earlier is
+/// delayed with leading silence so both line up again. The other channel is
+/// padded at the end so both buffers keep the same length.
+///
+/// # Arguments
+/// * `left` - Left channel, modified in place
+/// * `right` - Right channel, modified in place
+/// * `latency_diff` - Latency of the left chain minus latency of the right
+///   chain, in samples. Positive values delay `right`, negative values delay `left`
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::align_channels;
+/// let mut left = vec![0.0, 0.0, 1.0];
+/// let mut right = vec![1.0, 0.0, 0.0];
+/// align_channels(&mut left, &mut right, 2);
+/// assert_eq!(left, right);
+/// ```
+#[cfg(feature = "alloc")]
+pub fn align_channels(left: &mut Vec<f32>, right: &mut Vec<f32>, latency_diff: i64) {
+    let delay = latency_diff.unsigned_abs() as usize;
+    let (early, late) = if latency_diff > 0 {
+        (right, left)
+    } else {
+        (left, right)
+    };
+
+    if delay > 0 {
+        early.splice(0..0, core::iter::repeat_n(0.0, delay));
+    }
+
+    let len = early.len().max(late.len());
+    early.resize(len, 0.0);
+    late.resize(len, 0.0);
 }
 
 #[cfg(test)]
@@ -48,4 +346,142 @@ mod tests {
         normalize_audio(&mut samples);
         assert_eq!(samples, [0.5, 1.0, -0.5]);
     }
+
+    #[test]
+    fn test_db_linear_round_trip() {
+        for db in [-96.0, -60.0, -20.0, -6.0, 0.0, 6.0, 24.0] {
+            assert!((linear_to_db(db_to_linear(db)) - db).abs() < 1e-4, "{} dB", db);
+        }
+        for linear in [1e-5, 0.01, 0.5, 1.0, 2.0, 10.0] {
+            let round_trip = db_to_linear(linear_to_db(linear));
+            assert!((round_trip - linear).abs() <= linear * 1e-5, "{}", linear);
+        }
+        assert!((amplitude_to_dbfs(-0.5) - amplitude_to_dbfs(0.5)).abs() < f32::EPSILON);
+    }
+
+    #[test]
+    fn test_linear_to_db_floor() {
+        assert_eq!(linear_to_db(0.0), MIN_DB);
+        assert_eq!(linear_to_db(-1.0), MIN_DB);
+        assert_eq!(linear_to_db(f32::NAN), MIN_DB);
+        assert_eq!(linear_to_db(1e-9), MIN_DB);
+        assert_eq!(amplitude_to_dbfs(0.0), -120.0);
+    }
+
+    #[test]
+    fn test_clip_report() {
+        let mut signal: Vec<f32> = (0..1000).map(|i| 0.8 * (i as f32 * 0.01).sin()).collect();
+        signal[250] = 1.0;
+        signal[600] = -1.5;
+        signal[601] = 1.1;
+
+        assert_eq!(count_clipped(&signal, 1.0), 3);
+        let report = clip_report(&signal, 1.0);
+        assert_eq!(report.count, 3);
+        assert_eq!(report.first_index, Some(250));
+        assert_eq!(report.max_abs, 1.5);
+        assert!(report.is_clipping());
+
+        // A lower threshold also catches the peaks of the sine
+        assert!(count_clipped(&signal, 0.79) > 3);
+
+        let clean = clip_report(&[0.1, -0.2], 1.0);
+        assert_eq!(clean, ClipReport { count: 0, max_abs: 0.2, first_index: None });
+        assert!(!clean.is_clipping());
+    }
+
+    #[test]
+    fn test_fades() {
+        let mut samples = vec![1.0; 100];
+        apply_fades(&mut samples, 10, 20);
+
+        assert!(samples[0].abs() < 1e-6);
+        assert!(samples[99].abs() < 1e-6);
+        assert!((samples[5] - 0.5).abs() < 1e-6);
+        assert!((samples[89] - 0.5).abs() < 1e-6);
+        assert!(samples[10..80].iter().all(|&x| x == 1.0));
+
+        // A fade longer than the buffer ramps across all of it
+        let mut short = vec![1.0; 4];
+        fade_in(&mut short, 1000);
+        assert_eq!(short, vec![0.0, 0.25, 0.5, 0.75]);
+        let mut short = vec![1.0; 4];
+        fade_out(&mut short, 1000);
+        assert_eq!(short, vec![0.75, 0.5, 0.25, 0.0]);
+
+        let mut empty: Vec<f32> = Vec::new();
+        apply_fades(&mut empty, 10, 10);
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_pad_to_multiple() {
+        let samples = vec![1.0; 100];
+
+        let end = pad_to_multiple(&samples, 64, PadPosition::End);
+        assert_eq!(end.len(), 128);
+        assert!(end[..100].iter().all(|&x| x == 1.0));
+        assert!(end[100..].iter().all(|&x| x == 0.0));
+
+        let start = pad_to_multiple(&samples, 64, PadPosition::Start);
+        assert_eq!(start.len(), 128);
+        assert!(start[..28].iter().all(|&x| x == 0.0));
+        assert!(start[28..].iter().all(|&x| x == 1.0));
+
+        let both = pad_to_multiple(&samples, 64, PadPosition::Both);
+        assert_eq!(both.len(), 128);
+        assert!(both[..14].iter().all(|&x| x == 0.0));
+        assert!(both[14..114].iter().all(|&x| x == 1.0));
+        assert!(both[114..].iter().all(|&x| x == 0.0));
+
+        // Already aligned buffers are left untouched
+        assert_eq!(pad_to_multiple(&[1.0; 128], 64, PadPosition::End).len(), 128);
+    }
+
+    #[test]
+    fn test_trim_silence() {
+        let sample_rate = 8000.0;
+        // Medio segundo de ruido muy bajo a cada lado de un tono de un segundo
+        let noise = |i: usize| 1e-5 * (i as f32 * 1.7).sin();
+        let mut signal: Vec<f32> = (0..4000).map(noise).collect();
+        signal.extend((0..8000).map(|i| 0.5 * (2.0 * PI * 440.0 * i as f32 / sample_rate).sin()));
+        signal.extend((0..4000).map(noise));
+
+        // El rango cae dentro de una ventana de 1 ms alrededor del tono
+        let (start, end) = trim_silence(&signal, -40.0, 100.0, sample_rate);
+        assert!((4000..4008).contains(&start), "start {}", start);
+        assert!((11992..=12000).contains(&end), "end {}", end);
+        assert_eq!(trim_silence_buffer(&signal, -40.0, 100.0, sample_rate).len(), end - start);
+
+        // Un silencio más corto que el mínimo se conserva
+        assert_eq!(trim_silence(&signal[3800..12200], -40.0, 100.0, sample_rate).0, 0);
+        assert_eq!(trim_silence(&signal[3800..12200], -40.0, 100.0, sample_rate).1, 8400);
+
+        // Todo silencio: rango vacío
+        assert_eq!(trim_silence(&signal[..4000], -40.0, 100.0, sample_rate), (0, 0));
+        assert!(trim_silence_buffer(&[], -40.0, 100.0, sample_rate).is_empty());
+    }
+
+    #[test]
+    fn test_align_channels() {
+        let signal: Vec<f32> = (0..64).map(|i| (i as f32 * 0.3).sin()).collect();
+        let latency = 5;
+
+        // El canal izquierdo pasa por una cadena con 5 muestras más de latencia
+        let mut left = vec![0.0; latency];
+        left.extend_from_slice(&signal);
+        let mut right = signal.clone();
+
+        align_channels(&mut left, &mut right, latency as i64);
+        assert_eq!(left.len(), right.len());
+        assert_eq!(left, right);
+
+        // Latencia negativa: el canal derecho es el retrasado
+        let mut left = signal.clone();
+        let mut right = vec![0.0; latency];
+        right.extend_from_slice(&signal);
+
+        align_channels(&mut left, &mut right, -(latency as i64));
+        assert_eq!(left, right);
+    }
 }
@@ -0,0 +1,110 @@
+//! Fundamental frequency estimation
+//!
+//! Uses the YIN algorithm (de Cheveigné and Kawahara, 2002): the buffer is
+//! compared with delayed copies of itself, and the shortest delay at which it
+//! nearly repeats is taken as the period. Normalizing the difference function
+//! by its running mean makes the threshold independent of the signal level
+//! and avoids picking multiples of the period.
+
+/// Lowest fundamental frequency searched for, in Hz
+const MIN_FREQUENCY: f32 = 50.0;
+/// Shortest period searched for, in samples
+const MIN_PERIOD: usize = 2;
+/// Normalized difference below which a delay counts as a period. Lower values
+/// reject more noisy material
+const YIN_THRESHOLD: f32 = 0.15;
+
+/// Detects the fundamental frequency of a buffer with the YIN algorithm
+///
+/// Searches periods between 2 samples and 1/50 s, so the buffer should hold
+/// at least two periods of the lowest pitch of interest (about 40 ms for
+/// 50 Hz). Returns `None` when no period repeats clearly enough, e.g. for
+/// silence, noise or buffers that are too short.
+///
+/// # Arguments
+/// * `buffer` - Input audio buffer
+/// * `sample_rate` - Sample rate in Hz
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::detect_pitch;
+/// let sine: Vec<f32> = (0..2048)
+///     .map(|n| (2.0 * std::f32::consts::PI * 220.0 * n as f32 / 44100.0).sin())
+///     .collect();
+/// let pitch = detect_pitch(&sine, 44100.0).unwrap();
+/// assert!((pitch - 220.0).abs() < 2.0);
+/// assert_eq!(detect_pitch(&[0.0; 2048], 44100.0), None);
+/// ```
+pub fn detect_pitch(buffer: &[f32], sample_rate: f32) -> Option<f32> {
+    if sample_rate <= 0.0 || buffer.iter().all(|&x| x == 0.0) {
+        return None;
+    }
+
+    let max_period = ((sample_rate / MIN_FREQUENCY) as usize).min(buffer.len() / 2);
+    if max_period <= MIN_PERIOD {
+        return None;
+    }
+    let window = buffer.len() - max_period;
+
+    // Difference function normalized by its cumulative mean
+    let mut normalized = vec![1.0; max_period + 1];
+    let mut running_sum = 0.0;
+    for tau in 1..=max_period {
+        let difference: f32 = buffer[..window]
+            .iter()
+            .zip(&buffer[tau..tau + window])
+            .map(|(a, b)| (a - b) * (a - b))
+            .sum();
+        running_sum += difference;
+        if running_sum > 0.0 {
+            normalized[tau] = difference * tau as f32 / running_sum;
+        }
+    }
+
+    // First dip below the threshold, followed down to its local minimum
+    let mut tau = (MIN_PERIOD..max_period).find(|&tau| normalized[tau] < YIN_THRESHOLD)?;
+    while tau + 1 < max_period && normalized[tau + 1] < normalized[tau] {
+        tau += 1;
+    }
+
+    // Parabolic interpolation for a period between samples
+    let (prev, here, next) = (normalized[tau - 1], normalized[tau], normalized[tau + 1]);
+    let curvature = prev - 2.0 * here + next;
+    let offset = if curvature > 0.0 { 0.5 * (prev - next) / curvature } else { 0.0 };
+
+    Some(sample_rate / (tau as f32 + offset))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::{Rng, SeedableRng};
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_detect_pitch_of_sine_and_noise() {
+        let sample_rate = 44100.0;
+        let sine: Vec<f32> = (0..4096)
+            .map(|n| 0.5 * (2.0 * PI * 440.0 * n as f32 / sample_rate).sin())
+            .collect();
+        let pitch = detect_pitch(&sine, sample_rate).expect("A sine has a pitch");
+        assert!((pitch - 440.0).abs() < 4.4, "Detected {} Hz", pitch);
+
+        // Un tono grave con armónicos sigue dando la fundamental, no un armónico
+        let voiced: Vec<f32> = (0..4096)
+            .map(|n| {
+                let t = 2.0 * PI * 110.0 * n as f32 / sample_rate;
+                0.5 * t.sin() + 0.3 * (2.0 * t).sin() + 0.2 * (3.0 * t).sin()
+            })
+            .collect();
+        let pitch = detect_pitch(&voiced, sample_rate).expect("A harmonic tone has a pitch");
+        assert!((pitch - 110.0).abs() < 1.1, "Detected {} Hz", pitch);
+
+        // Ruido blanco, silencio y búferes demasiado cortos no tienen tono
+        let mut rng = rand::rngs::StdRng::seed_from_u64(364);
+        let noise: Vec<f32> = (0..4096).map(|_| rng.gen_range(-0.5..0.5)).collect();
+        assert_eq!(detect_pitch(&noise, sample_rate), None);
+        assert_eq!(detect_pitch(&[0.0; 4096], sample_rate), None);
+        assert_eq!(detect_pitch(&sine[..4], sample_rate), None);
+    }
+}
@@ -0,0 +1,48 @@
+//! Floating-point sample types
+//!
+//! Most of the crate works on `f32`, which is what browsers and audio APIs
+//! hand out. Long offline renders can accumulate rounding error in the
+//! smoothing filters, so the core gain functions also accept `f64` through the
+//! [`Sample`] trait. `f32` stays the default everywhere and is the only type
+//! exposed to WASM.
+
+use num_traits::{Float, NumAssign};
+
+use super::MIN_DB;
+
+/// Floating-point type usable as an audio sample (`f32` or `f64`)
+pub trait Sample: Float + NumAssign + Default + core::fmt::Debug + Send + Sync + 'static {
+    /// Converts an `f64` constant to this type, rounding if needed
+    fn from_f64(value: f64) -> Self;
+
+    /// Converts a level in dB to a linear gain factor, like [`super::db_to_linear`]
+    fn from_db(db: Self) -> Self {
+        Self::from_f64(10.0).powf(db / Self::from_f64(20.0))
+    }
+
+    /// Converts a linear gain factor to dB, like [`super::linear_to_db`]
+    ///
+    /// Zero, negative and NaN inputs return [`MIN_DB`].
+    fn to_db(self) -> Self {
+        let min_db = Self::from_f64(MIN_DB as f64);
+        if self > Self::zero() {
+            (Self::from_f64(20.0) * self.log10()).max(min_db)
+        } else {
+            min_db
+        }
+    }
+}
+
+impl Sample for f32 {
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value as f32
+    }
+}
+
+impl Sample for f64 {
+    #[inline(always)]
+    fn from_f64(value: f64) -> Self {
+        value
+    }
+}
@@ -0,0 +1,141 @@
+//! Vectorization-friendly kernels for the gain hot paths
+//!
+//! The plain `for x in samples { *x *= gain }` loops are usually
+//! autovectorized, but not reliably once they are mixed with reductions or
+//! branches. These helpers process the buffer in fixed chunks of [`LANES`]
+//! independent accumulators, a shape that LLVM turns into SIMD instructions on
+//! stable Rust, with a scalar loop for the remainder. The scalar reference
+//! versions are kept public so the results can be compared and benchmarked.
+
+use super::Sample;
+
+/// Number of samples processed per unrolled step
+pub const LANES: usize = 4;
+
+/// Multiplies every sample by `gain` in place
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::simd::scale_in_place;
+/// let mut samples = vec![1.0, 0.5, -0.5, -1.0, 0.25];
+/// scale_in_place(&mut samples, 2.0);
+/// assert_eq!(samples, vec![2.0, 1.0, -1.0, -2.0, 0.5]);
+/// ```
+pub fn scale_in_place<T: Sample>(samples: &mut [T], gain: T) {
+    let mut chunks = samples.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        chunk[0] *= gain;
+        chunk[1] *= gain;
+        chunk[2] *= gain;
+        chunk[3] *= gain;
+    }
+    scale_in_place_scalar(chunks.into_remainder(), gain);
+}
+
+/// Scalar reference implementation of [`scale_in_place`]
+pub fn scale_in_place_scalar<T: Sample>(samples: &mut [T], gain: T) {
+    for x in samples.iter_mut() {
+        *x *= gain;
+    }
+}
+
+/// Returns the highest absolute sample value (0.0 for an empty buffer)
+///
+/// Non-finite samples (NaN and ±infinity) are ignored.
+pub fn peak_abs<T: Sample>(samples: &[T]) -> T {
+    let mut lanes = [T::zero(); LANES];
+    let mut chunks = samples.chunks_exact(LANES);
+    for chunk in &mut chunks {
+        lanes[0] = lanes[0].max(finite_abs(chunk[0]));
+        lanes[1] = lanes[1].max(finite_abs(chunk[1]));
+        lanes[2] = lanes[2].max(finite_abs(chunk[2]));
+        lanes[3] = lanes[3].max(finite_abs(chunk[3]));
+    }
+
+    let tail = peak_abs_scalar(chunks.remainder());
+    lanes[0].max(lanes[1]).max(lanes[2].max(lanes[3])).max(tail)
+}
+
+/// Scalar reference implementation of [`peak_abs`]
+pub fn peak_abs_scalar<T: Sample>(samples: &[T]) -> T {
+    samples.iter().fold(T::zero(), |max, &x| max.max(finite_abs(x)))
+}
+
+/// Absolute value of a sample, or 0.0 if it is not finite
+#[inline(always)]
+fn finite_abs<T: Sample>(x: T) -> T {
+    let abs = x.abs();
+    // NaN also fails the comparison
+    if abs < T::infinity() { abs } else { T::zero() }
+}
+
+/// Clamps every sample to `[-limit, limit]` in place
+///
+/// NaN samples are left untouched.
+pub fn clamp_in_place(samples: &mut [f32], limit: f32) {
+    let mut chunks = samples.chunks_exact_mut(LANES);
+    for chunk in &mut chunks {
+        chunk[0] = clamp_sample(chunk[0], limit);
+        chunk[1] = clamp_sample(chunk[1], limit);
+        chunk[2] = clamp_sample(chunk[2], limit);
+        chunk[3] = clamp_sample(chunk[3], limit);
+    }
+    clamp_in_place_scalar(chunks.into_remainder(), limit);
+}
+
+/// Scalar reference implementation of [`clamp_in_place`]
+pub fn clamp_in_place_scalar(samples: &mut [f32], limit: f32) {
+    for x in samples.iter_mut() {
+        *x = clamp_sample(*x, limit);
+    }
+}
+
+#[inline(always)]
+fn clamp_sample(x: f32, limit: f32) -> f32 {
+    if x > limit {
+        limit
+    } else if x < -limit {
+        -limit
+    } else {
+        x
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_buffer(len: usize) -> Vec<f32> {
+        (0..len).map(|i| 1.7 * (i as f32 * 0.37).sin() - 0.2).collect()
+    }
+
+    #[test]
+    fn test_unrolled_matches_scalar() {
+        // Longitudes que no son múltiplo de LANES para cubrir el resto escalar
+        for len in [0, 1, 3, 4, 7, 64, 65_537] {
+            let input = test_buffer(len);
+
+            let mut unrolled = input.clone();
+            let mut scalar = input.clone();
+            scale_in_place(&mut unrolled, 0.731);
+            scale_in_place_scalar(&mut scalar, 0.731);
+            assert_eq!(unrolled, scalar);
+
+            assert_eq!(peak_abs(&input).to_bits(), peak_abs_scalar(&input).to_bits());
+
+            let mut poisoned = input.clone();
+            if len > 2 {
+                poisoned[len / 2] = f32::NAN;
+                poisoned[len - 1] = f32::NEG_INFINITY;
+            }
+            assert_eq!(peak_abs(&poisoned).to_bits(), peak_abs_scalar(&poisoned).to_bits());
+            assert!(peak_abs(&poisoned).is_finite());
+
+            let mut unrolled = input.clone();
+            let mut scalar = input;
+            clamp_in_place(&mut unrolled, 0.9);
+            clamp_in_place_scalar(&mut scalar, 0.9);
+            assert_eq!(unrolled, scalar);
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! FFT-based spectrum analysis for meters, displays and tests
+
+use realfft::RealFftPlanner;
+
+use super::window::{make_window, WindowType};
+
+/// Computes the magnitude spectrum of a signal with a single windowed FFT
+///
+/// The whole signal is one frame, so the resolution is
+/// `sample_rate / signal.len()` Hz. Magnitudes are linear and normalized so a
+/// sine of amplitude `a` centered on a bin reads `a` in that bin, whatever the
+/// window.
+///
+/// # Arguments
+/// * `signal` - Input audio buffer
+/// * `sample_rate` - Sample rate in Hz
+/// * `window` - Window applied to the signal before the FFT
+///
+/// # Returns
+/// `(frequency, magnitude)` pairs from DC up to Nyquist (`signal.len() / 2 + 1`
+/// bins), or an empty vector for an empty signal
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::{magnitude_spectrum, WindowType};
+///
+/// let signal: Vec<f32> = (0..1024).map(|i| (i as f32 * 0.3).sin()).collect();
+/// let spectrum = magnitude_spectrum(&signal, 44100.0, WindowType::Hann);
+/// assert_eq!(spectrum.len(), 513);
+/// assert_eq!(spectrum[512].0, 22050.0);
+/// ```
+pub fn magnitude_spectrum(signal: &[f32], sample_rate: f32, window: WindowType) -> Vec<(f32, f32)> {
+    if signal.is_empty() {
+        return Vec::new();
+    }
+
+    let n = signal.len();
+    let window = make_window(window, n);
+    let mut planner = RealFftPlanner::<f32>::new();
+    let fft = planner.plan_fft_forward(n);
+    let mut frame: Vec<f32> = signal.iter().zip(&window).map(|(x, w)| x * w).collect();
+    let mut spectrum = fft.make_output_vec();
+    fft.process(&mut frame, &mut spectrum).unwrap();
+
+    // Magnitude of a unit sine in its bin after windowing
+    let full_scale = (window.iter().sum::<f32>() / 2.0).max(f32::MIN_POSITIVE);
+    let bin_width = sample_rate / n as f32;
+    spectrum
+        .iter()
+        .enumerate()
+        .map(|(bin, value)| (bin as f32 * bin_width, value.norm() / full_scale))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::PI;
+
+    #[test]
+    fn test_peak_bin_matches_sine_frequency() {
+        let sample_rate = 44100.0;
+        let n = 4096;
+        let bin_width = sample_rate / n as f32;
+
+        // Un tono centrado en un bin y otro entre dos bins
+        for freq in [93.0 * bin_width, 1000.0, 5000.0] {
+            let signal: Vec<f32> = (0..n).map(|i| 0.5 * (2.0 * PI * freq * i as f32 / sample_rate).sin()).collect();
+            for window in [WindowType::Hann, WindowType::BlackmanHarris] {
+                let spectrum = magnitude_spectrum(&signal, sample_rate, window);
+                assert_eq!(spectrum.len(), n / 2 + 1);
+
+                let (peak_freq, peak_mag) = spectrum.iter().copied().fold((0.0, 0.0), |max, bin| {
+                    if bin.1 > max.1 {
+                        bin
+                    } else {
+                        max
+                    }
+                });
+                assert!((peak_freq - freq).abs() <= bin_width / 2.0, "{} Hz peak at {} Hz", freq, peak_freq);
+                assert!(peak_mag > 0.4 && peak_mag < 0.51, "{} Hz magnitude {}", freq, peak_mag);
+            }
+        }
+
+        assert!(magnitude_spectrum(&[], sample_rate, WindowType::Hann).is_empty());
+    }
+}
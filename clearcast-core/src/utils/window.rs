@@ -0,0 +1,145 @@
+//! Window functions for frame-based (FFT) processing
+//!
+//! All windows are symmetric: sample `i` and sample `n - 1 - i` are equal, and
+//! the peak sits in the middle of the frame. A window of length 1 is `[1.0]`.
+
+use std::f32::consts::PI;
+
+/// Window shapes supported by [`make_window`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowType {
+    /// Raised cosine reaching zero at both ends
+    Hann,
+    /// Raised cosine with non-zero ends and a lower first sidelobe than Hann
+    Hamming,
+    /// Three-term cosine window with better sidelobe rejection
+    Blackman,
+    /// Four-term cosine window with very low sidelobes (about -92 dB)
+    BlackmanHarris,
+}
+
+impl WindowType {
+    /// Cosine-sum coefficients `a0, a1, ...` of the window
+    fn coefficients(self) -> &'static [f32] {
+        match self {
+            WindowType::Hann => &[0.5, 0.5],
+            WindowType::Hamming => &[0.54, 0.46],
+            WindowType::Blackman => &[0.42, 0.5, 0.08],
+            WindowType::BlackmanHarris => &[0.35875, 0.48829, 0.14128, 0.01168],
+        }
+    }
+}
+
+/// Builds a window of the given type and length
+///
+/// # Example
+/// ```
+/// use clearcast_core::utils::{make_window, WindowType};
+/// let window = make_window(WindowType::Hann, 5);
+/// assert_eq!(window.len(), 5);
+/// assert!((window[2] - 1.0).abs() < 1e-6);
+/// ```
+pub fn make_window(window_type: WindowType, n: usize) -> Vec<f32> {
+    cosine_sum(window_type.coefficients(), n)
+}
+
+/// Hann window of length `n`
+pub fn hann(n: usize) -> Vec<f32> {
+    make_window(WindowType::Hann, n)
+}
+
+/// Hamming window of length `n`
+pub fn hamming(n: usize) -> Vec<f32> {
+    make_window(WindowType::Hamming, n)
+}
+
+/// Blackman window of length `n`
+pub fn blackman(n: usize) -> Vec<f32> {
+    make_window(WindowType::Blackman, n)
+}
+
+/// 4-term Blackman-Harris window of length `n`
+pub fn blackman_harris(n: usize) -> Vec<f32> {
+    make_window(WindowType::BlackmanHarris, n)
+}
+
+/// Evaluates `a0 - a1 cos(x) + a2 cos(2x) - ...` over a symmetric frame
+fn cosine_sum(coefficients: &[f32], n: usize) -> Vec<f32> {
+    if n <= 1 {
+        return vec![1.0; n];
+    }
+
+    let step = 2.0 * PI / (n - 1) as f32;
+    (0..n)
+        .map(|i| {
+            coefficients
+                .iter()
+                .enumerate()
+                .map(|(k, &a)| {
+                    let term = a * (step * (k * i) as f32).cos();
+                    if k % 2 == 0 { term } else { -term }
+                })
+                .sum()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [WindowType; 4] = [
+        WindowType::Hann,
+        WindowType::Hamming,
+        WindowType::Blackman,
+        WindowType::BlackmanHarris,
+    ];
+
+    #[test]
+    fn test_windows_are_symmetric_with_unit_peak() {
+        for window_type in ALL {
+            for n in [2, 63, 64, 1024] {
+                let window = make_window(window_type, n);
+                assert_eq!(window.len(), n);
+                for i in 0..n / 2 {
+                    assert!(
+                        (window[i] - window[n - 1 - i]).abs() < 1e-5,
+                        "{:?} (n = {}) is not symmetric at {}",
+                        window_type,
+                        n,
+                        i
+                    );
+                }
+                if n % 2 == 1 {
+                    assert!((window[n / 2] - 1.0).abs() < 1e-5, "{:?} peak", window_type);
+                }
+            }
+        }
+        assert_eq!(make_window(WindowType::Hann, 1), vec![1.0]);
+        assert!(make_window(WindowType::Blackman, 0).is_empty());
+    }
+
+    #[test]
+    fn test_window_endpoints_and_sums() {
+        // Over a symmetric frame the cosine terms cancel except at the last
+        // sample, so the sum is a0 * (n - 1) plus the endpoint value
+        let n = 1025;
+        let cases = [
+            (hann(n), 0.5, 0.0),
+            (hamming(n), 0.54, 0.08),
+            (blackman(n), 0.42, 0.0),
+            (blackman_harris(n), 0.35875, 6.0e-5),
+        ];
+
+        for (window, a0, endpoint) in cases {
+            assert!((window[0] - endpoint).abs() < 1e-5, "endpoint {}", window[0]);
+            assert!((window[n - 1] - endpoint).abs() < 1e-5, "endpoint {}", window[n - 1]);
+
+            let sum: f32 = window.iter().sum();
+            let expected = a0 * (n - 1) as f32 + endpoint;
+            assert!((sum - expected).abs() < 1e-2, "sum {} expected {}", sum, expected);
+        }
+
+        assert!(hann(n)[0].abs() < 1e-6);
+    }
+}
@@ -1,4 +1,5 @@
 use clearcast_core::filters::equalizer::{parametric_eq, Band};
+use clearcast_core::utils::{magnitude_spectrum, WindowType};
 use std::f32::consts::PI;
 
 // Helper function to generate a sine wave
@@ -24,30 +25,6 @@ fn calculate_rms(signal: &[f32]) -> f32 {
     (sum_sq / signal.len() as f32).sqrt()
 }
 
-// Helper function to calculate frequency spectrum using FFT
-fn calculate_spectrum(signal: &[f32], sample_rate: f32) -> Vec<(f32, f32)> {
-    use rustfft::{FftPlanner, num_complex::Complex};
-    
-    let mut planner = FftPlanner::new();
-    let fft = planner.plan_fft_forward(signal.len());
-    
-    // Convert to complex numbers
-    let mut buffer: Vec<Complex<f32>> = signal.iter().map(|&x| Complex { re: x, im: 0.0 }).collect();
-    
-    // Perform FFT
-    fft.process(&mut buffer);
-    
-    // Calculate magnitude spectrum
-    let n = buffer.len();
-    let fft_bin_width = sample_rate / n as f32;
-    
-    (0..n/2).map(|i| {
-        let freq = i as f32 * fft_bin_width;
-        let mag = (buffer[i].re.powi(2) + buffer[i].im.powi(2)).sqrt() / (n as f32).sqrt();
-        (freq, mag)
-    }).collect()
-}
-
 // Helper function to find peak frequency in a spectrum
 fn find_peak_frequency(spectrum: &[(f32, f32)]) -> f32 {
     spectrum.iter()
@@ -124,7 +101,7 @@ fn test_parametric_eq_frequency_response() {
     let processed = parametric_eq(&combined, sample_rate, boost_db, -boost_db/2.0, boost_db);
     
     // Calculate frequency spectrum of the processed signal
-    let spectrum = calculate_spectrum(&processed, sample_rate);
+    let spectrum = magnitude_spectrum(&processed, sample_rate, WindowType::Hann);
     
     // Find peaks in different frequency ranges
     let low_band: Vec<_> = spectrum.iter().filter(|(f, _)| *f < 200.0).collect();
@@ -173,7 +150,7 @@ fn test_parametric_eq_noise() {
     let processed = parametric_eq(&noise, sample_rate, low_gain, mid_gain, high_gain);
     
     // Calculate frequency spectrum
-    let spectrum = calculate_spectrum(&processed, sample_rate);
+    let spectrum = magnitude_spectrum(&processed, sample_rate, WindowType::Hann);
     
     // Calculate average magnitude in each band
     let (low_sum, low_count) = spectrum.iter()
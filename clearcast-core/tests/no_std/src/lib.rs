@@ -0,0 +1,31 @@
+//! Build check for the `no_std` subset of clearcast-core
+//!
+//! Chains everything that must stay available without `std` and without an
+//! allocator, so that gating something behind `std` by mistake breaks the
+//! bare-metal build in CI.
+
+#![no_std]
+
+use clearcast_core::effects::{AudioEffect, KneeShape, SoftLimiter};
+use clearcast_core::filters::{apply_gain_in_place, Band, ParametricEQ};
+use clearcast_core::utils::{db_to_linear, linear_to_db, simd, Sample};
+
+/// Runs a block through gain, EQ and limiter, returning the output peak in dBFS
+pub fn process_block(block: &mut [f32], sample_rate: f32) -> f32 {
+    apply_gain_in_place(block, db_to_linear(-3.0));
+
+    let mut eq = ParametricEQ::new(sample_rate, 2.0, -1.0, 3.0).expect("valid EQ settings");
+    eq.set_frequency(Band::Mid, 1000.0);
+    eq.process_buffer(block);
+
+    let mut limiter = SoftLimiter::new(0.8, 0.1);
+    limiter.set_knee_shape(KneeShape::Cubic);
+    limiter.process_buffer(block);
+
+    linear_to_db(simd::peak_abs(block))
+}
+
+/// The generic paths must also build in `f64`
+pub fn scale_f64(block: &mut [f64], gain_db: f64) {
+    apply_gain_in_place(block, f64::from_db(gain_db));
+}